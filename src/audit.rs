@@ -0,0 +1,404 @@
+//! Registro de auditoría: cada comando privilegiado y cada cambio de ficheros se agrega
+//! como una línea JSON a un fichero que solo crece.
+
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+use std::time::Instant;
+
+use console::style;
+use dialoguer::{Confirm, Input, theme::ColorfulTheme};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{CommandOutput, GlusterBackend};
+use crate::error::{Error, Result};
+use crate::t;
+
+pub const AUDIT_PATH: &str = "/var/log/cluster_almacenamiento/audit.jsonl";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    Command,
+    WriteFile,
+    CreateDir,
+    RemoveDir,
+    RemoveFile,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// RFC 3339 en hora local.
+    pub timestamp: String,
+    /// Quien lanzó la herramienta (`SUDO_USER` si se usó sudo).
+    pub user: String,
+    pub kind: AuditKind,
+    /// Programa ejecutado, o la ruta afectada en los cambios de ficheros.
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    pub duration_ms: u64,
+    /// Líneas eliminadas (`- `) y agregadas (`+ `) al reescribir un fichero.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<String>,
+}
+
+impl AuditEntry {
+    fn summary(&self) -> String {
+        match self.kind {
+            AuditKind::Command => format!("sudo {} {}", self.target, self.args.join(" ")),
+            AuditKind::WriteFile => t!("audit.write_file", path = self.target),
+            AuditKind::CreateDir => t!("audit.create_dir", path = self.target),
+            AuditKind::RemoveDir => t!("audit.remove_dir", path = self.target),
+            AuditKind::RemoveFile => t!("audit.remove_file", path = self.target),
+        }
+    }
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mark = if self.success { "✅" } else { "❌" };
+        write!(
+            f,
+            "{} {} {} {} ({} ms)",
+            self.timestamp,
+            self.user,
+            mark,
+            self.summary(),
+            self.duration_ms
+        )?;
+        if let Some(code) = self.exit_code.filter(|c| *c != 0) {
+            write!(f, "{}", t!("audit.exit_code", code = code))?;
+        }
+        for change in &self.changes {
+            write!(f, "\n      {}", change)?;
+        }
+        if !self.stderr.trim().is_empty() {
+            write!(f, "\n      {}", self.stderr.trim())?;
+        }
+        Ok(())
+    }
+}
+
+/// Envuelve otro backend y registra las operaciones que modifican el sistema.
+pub struct AuditBackend<'a> {
+    inner: &'a dyn GlusterBackend,
+    path: String,
+    user: String,
+    warned: Cell<bool>,
+}
+
+impl<'a> AuditBackend<'a> {
+    pub fn new(inner: &'a dyn GlusterBackend, path: &str, user: &str) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            user: user.to_string(),
+            warned: Cell::new(false),
+        }
+    }
+
+    fn record(&self, kind: AuditKind, target: &str, started: Instant, fill: impl FnOnce(&mut AuditEntry)) {
+        let mut entry = AuditEntry {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            user: self.user.clone(),
+            kind,
+            target: target.to_string(),
+            args: vec![],
+            success: true,
+            exit_code: None,
+            stderr: String::new(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            changes: vec![],
+        };
+        fill(&mut entry);
+
+        let line = match serde_json::to_string(&entry) {
+            Ok(json) => json + "\n",
+            Err(_) => return,
+        };
+        // Un fallo del registro no debe interrumpir la operación; se avisa una sola vez.
+        if let Err(e) = self.inner.append_file(&self.path, &line)
+            && !self.warned.replace(true)
+        {
+            eprintln!("{}", t!("audit.write_failed", path = self.path, error = e));
+        }
+    }
+
+    fn record_fs(&self, kind: AuditKind, path: &str, started: Instant, result: &io::Result<()>, changes: Vec<String>) {
+        self.record(kind, path, started, |entry| {
+            entry.changes = changes;
+            if let Err(e) = result {
+                entry.success = false;
+                entry.stderr = e.to_string();
+            }
+        });
+    }
+}
+
+impl GlusterBackend for AuditBackend<'_> {
+    fn exec(&self, program: &str, args: &[&str], privileged: bool) -> io::Result<CommandOutput> {
+        if !privileged {
+            return self.inner.exec(program, args, privileged);
+        }
+
+        let started = Instant::now();
+        let result = self.inner.exec(program, args, privileged);
+        self.record(AuditKind::Command, program, started, |entry| {
+            entry.args = args.iter().map(|a| a.to_string()).collect();
+            match &result {
+                Ok(output) => {
+                    entry.success = output.success;
+                    entry.exit_code = output.code;
+                    entry.stderr = output.stderr.clone();
+                }
+                Err(e) => {
+                    entry.success = false;
+                    entry.stderr = e.to_string();
+                }
+            }
+        });
+        result
+    }
+
+    fn exec_query(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        self.inner.exec_query(program, args)
+    }
+
+    fn read_file(&self, path: &str) -> io::Result<String> {
+        self.inner.read_file(path)
+    }
+
+    fn write_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        let started = Instant::now();
+        let before = self.inner.read_file(path).unwrap_or_default();
+        let result = self.inner.write_file(path, contents);
+        let changes = crate::dryrun::line_changes(&before, contents);
+        self.record_fs(AuditKind::WriteFile, path, started, &result, changes);
+        result
+    }
+
+    fn append_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        self.inner.append_file(path, contents)
+    }
+
+    fn create_dir_all(&self, path: &str) -> io::Result<()> {
+        let started = Instant::now();
+        let result = self.inner.create_dir_all(path);
+        self.record_fs(AuditKind::CreateDir, path, started, &result, vec![]);
+        result
+    }
+
+    fn remove_dir_all(&self, path: &str) -> io::Result<()> {
+        let started = Instant::now();
+        let result = self.inner.remove_dir_all(path);
+        self.record_fs(AuditKind::RemoveDir, path, started, &result, vec![]);
+        result
+    }
+
+    fn remove_file(&self, path: &str) -> io::Result<()> {
+        let started = Instant::now();
+        let result = self.inner.remove_file(path);
+        self.record_fs(AuditKind::RemoveFile, path, started, &result, vec![]);
+        result
+    }
+
+    fn list_dirs(&self, path: &str) -> io::Result<Vec<String>> {
+        self.inner.list_dirs(path)
+    }
+
+    fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        self.inner.list_files(path)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.inner.exists(path)
+    }
+
+    fn user_exists(&self, name: &str) -> bool {
+        self.inner.user_exists(name)
+    }
+
+    fn current_user(&self) -> String {
+        self.inner.current_user()
+    }
+
+    fn local_hostname(&self) -> String {
+        self.inner.local_hostname()
+    }
+}
+
+/// Usuario real detrás de `sudo`; si no se usó sudo, el usuario actual.
+pub fn invoking_user(backend: &dyn GlusterBackend) -> String {
+    std::env::var("SUDO_USER")
+        .ok()
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| backend.current_user())
+}
+
+/// Entradas del registro en orden cronológico; las líneas corruptas se ignoran.
+pub fn read(backend: &dyn GlusterBackend, path: &str) -> Result<Vec<AuditEntry>> {
+    match backend.read_file(path) {
+        Ok(text) => Ok(text.lines().filter_map(|l| serde_json::from_str(l).ok()).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(Error::Message(t!("audit.read_failed", path = path, error = e))),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    pub user: Option<String>,
+    /// Texto a buscar en el comando, sus argumentos o la ruta.
+    pub text: Option<String>,
+    pub failed_only: bool,
+    /// Cuántas de las entradas más recientes mostrar.
+    pub limit: Option<usize>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.user.as_ref().is_none_or(|u| entry.user == *u)
+            && self.text.as_ref().is_none_or(|t| entry.summary().contains(t.as_str()))
+            && (!self.failed_only || !entry.success)
+    }
+
+    pub fn apply<'e>(&self, entries: &'e [AuditEntry]) -> Vec<&'e AuditEntry> {
+        let matching: Vec<&AuditEntry> = entries.iter().filter(|e| self.matches(e)).collect();
+        let skip = self.limit.map_or(0, |limit| matching.len().saturating_sub(limit));
+        matching.into_iter().skip(skip).collect()
+    }
+}
+
+pub fn print_entries(entries: &[&AuditEntry]) {
+    if entries.is_empty() {
+        println!("{}", t!("audit.empty"));
+        return;
+    }
+    for entry in entries {
+        if entry.success {
+            println!("   {}", entry);
+        } else {
+            println!("   {}", style(entry).red());
+        }
+    }
+}
+
+/// Menú para consultar el registro con filtros opcionales.
+pub fn browse(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    println!("\n{}", t!("audit.title", path = AUDIT_PATH));
+
+    let entries = read(backend, AUDIT_PATH)?;
+
+    let user: String = Input::with_theme(&theme)
+        .with_prompt(t!("audit.user_prompt"))
+        .allow_empty(true)
+        .interact_text()?;
+    let text: String = Input::with_theme(&theme)
+        .with_prompt(t!("audit.text_prompt"))
+        .allow_empty(true)
+        .interact_text()?;
+    let failed_only = Confirm::with_theme(&theme)
+        .with_prompt(t!("audit.failed_prompt"))
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    let filter = AuditFilter {
+        user: Some(user.trim().to_string()).filter(|u| !u.is_empty()),
+        text: Some(text.trim().to_string()).filter(|t| !t.is_empty()),
+        failed_only,
+        limit: Some(50),
+    };
+    print_entries(&filter.apply(&entries));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::FakeBackend;
+    use crate::layout::VolumeLayout;
+
+    const LOG: &str = "/var/log/test/audit.jsonl";
+
+    #[test]
+    fn records_commands_and_file_changes() {
+        let fake = FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_file("/etc/hosts", "127.0.0.1 localhost\n")
+            .with_dir("/gluster");
+        let backend = AuditBackend::new(&fake, LOG, "operador");
+
+        crate::hosts::add_entry(&backend, "192.168.1.11", "vm2").unwrap();
+        crate::bricks::create(&backend, "a").unwrap();
+        crate::volume::create(&backend, "datos", &VolumeLayout::Distribute, &["vm1:/gluster/a".to_string()], false, false)
+            .unwrap();
+        // Las consultas no se registran.
+        crate::volinfo::fetch(&backend, None).unwrap();
+
+        let entries = read(&fake, LOG).unwrap();
+        let kinds: Vec<AuditKind> = entries.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AuditKind::WriteFile,
+                AuditKind::CreateDir,
+                AuditKind::Command,
+                AuditKind::Command,
+                AuditKind::Command
+            ]
+        );
+        assert!(entries.iter().all(|e| e.user == "operador" && e.success));
+        assert_eq!(entries[0].target, "/etc/hosts");
+        assert_eq!(entries[0].changes, vec!["+ 192.168.1.11 vm2"]);
+        assert_eq!(entries[4].target, "gluster");
+        assert_eq!(entries[4].args[..3], ["--mode=script", "volume", "create"]);
+        assert_eq!(entries[4].exit_code, Some(0));
+    }
+
+    #[test]
+    fn failed_commands_keep_stderr() {
+        let fake = FakeBackend::new();
+        let backend = AuditBackend::new(&fake, LOG, "root");
+
+        assert!(crate::volume::start(&backend, "nada").is_err());
+
+        let entries = read(&fake, LOG).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].success);
+        assert_eq!(entries[0].exit_code, Some(1));
+        assert!(entries[0].stderr.contains("does not exist"));
+    }
+
+    #[test]
+    fn filter_by_user_text_and_failures() {
+        let fake = FakeBackend::new().with_dir("/gluster");
+        for (user, brick) in [("ana", "a"), ("luis", "b"), ("ana", "c")] {
+            crate::bricks::create(&AuditBackend::new(&fake, LOG, user), brick).unwrap();
+        }
+        crate::bricks::create(&AuditBackend::new(&fake, LOG, "luis"), "a").unwrap_err();
+        let backend = AuditBackend::new(&fake, LOG, "luis");
+        crate::volume::stop(&backend, "nada").unwrap_err();
+
+        let entries = read(&fake, LOG).unwrap();
+        let by_user = AuditFilter { user: Some("ana".into()), ..Default::default() };
+        assert_eq!(by_user.apply(&entries).len(), 6);
+
+        let by_text = AuditFilter { text: Some("/gluster/b".into()), ..Default::default() };
+        assert_eq!(by_text.apply(&entries).len(), 3);
+
+        let failed = AuditFilter { failed_only: true, ..Default::default() };
+        let failures = failed.apply(&entries);
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].args.contains(&"nada".to_string()));
+
+        let last = AuditFilter { limit: Some(2), ..Default::default() };
+        assert_eq!(last.apply(&entries), entries.iter().skip(entries.len() - 2).collect::<Vec<_>>());
+    }
+}
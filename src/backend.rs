@@ -0,0 +1,254 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{Error, Result};
+use crate::t;
+
+/// Resultado de un comando externo ya terminado.
+pub struct CommandOutput {
+    pub success: bool,
+    /// Código de salida; `None` si el proceso terminó por una señal.
+    pub code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Todo lo que la herramienta necesita del sistema: comandos (`gluster`, `mount`, ...) y ficheros.
+///
+/// Los módulos nunca lanzan procesos ni tocan el disco directamente, así el mismo
+/// flujo funciona contra el sistema real o contra el modelo en memoria de los tests.
+pub trait GlusterBackend {
+    /// Ejecuta `program args`. Los comandos `privileged` se lanzan con sudo y modifican el sistema.
+    fn exec(&self, program: &str, args: &[&str], privileged: bool) -> io::Result<CommandOutput>;
+    /// Ejecuta `sudo program args` para una consulta que no modifica nada (ej. `lvs`).
+    fn exec_query(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput>;
+    fn read_file(&self, path: &str) -> io::Result<String>;
+    fn write_file(&self, path: &str, contents: &str) -> io::Result<()>;
+    /// Agrega `contents` al final de `path`, creándolo si no existe.
+    fn append_file(&self, path: &str, contents: &str) -> io::Result<()>;
+    fn create_dir_all(&self, path: &str) -> io::Result<()>;
+    fn remove_dir_all(&self, path: &str) -> io::Result<()>;
+    fn remove_file(&self, path: &str) -> io::Result<()>;
+    /// Nombres de los subdirectorios de `path`.
+    fn list_dirs(&self, path: &str) -> io::Result<Vec<String>>;
+    /// Nombres de los ficheros de `path`.
+    fn list_files(&self, path: &str) -> io::Result<Vec<String>>;
+    fn exists(&self, path: &str) -> bool;
+    fn user_exists(&self, name: &str) -> bool;
+    fn current_user(&self) -> String;
+    /// Nombre de este nodo, para saber qué bricks de una especificación son locales.
+    fn local_hostname(&self) -> String;
+    /// En modo simulación los comandos privilegiados y las escrituras no se aplican.
+    fn is_dry_run(&self) -> bool {
+        false
+    }
+}
+
+impl dyn GlusterBackend + '_ {
+    /// Consulta de solo lectura: `gluster <args>`; devuelve stdout o stderr como error.
+    pub fn gluster(&self, args: &[&str]) -> Result<String> {
+        self.run(&Invocation::new("gluster", args, Access::User))
+    }
+
+    /// Consulta de solo lectura con `--xml`; en caso de fallo devuelve el `opErrstr` de gluster.
+    pub fn gluster_xml(&self, args: &[&str]) -> Result<String> {
+        let mut full = args.to_vec();
+        full.push("--xml");
+        self.gluster(&full).map_err(|e| match e {
+            Error::CommandFailed { command, stderr } => Error::CommandFailed {
+                command,
+                stderr: crate::xml::error_message(stderr),
+            },
+            other => other,
+        })
+    }
+
+    /// `sudo gluster --mode=script <args>`: sin prompts (y/n), la confirmación ya la hace esta herramienta.
+    pub fn sudo_gluster(&self, args: &[&str]) -> Result<String> {
+        let mut full = vec!["--mode=script"];
+        full.extend_from_slice(args);
+        self.sudo("gluster", &full)
+    }
+
+    /// Comando privilegiado del sistema (`sudo <program> <args>`), mostrando qué se ejecuta.
+    pub fn sudo(&self, program: &str, args: &[&str]) -> Result<String> {
+        let invocation = Invocation::new(program, args, Access::Change);
+        if self.is_dry_run() {
+            println!("{}", t!("backend.would_run"));
+        } else {
+            println!("{}", t!("backend.running"));
+        }
+        println!("{}", invocation);
+        self.run(&invocation)
+    }
+
+    /// Consulta con sudo que no cambia nada (ej. `lvs`): no se anuncia, se ejecuta también en
+    /// simulación y no se registra en la auditoría.
+    pub fn sudo_query(&self, program: &str, args: &[&str]) -> Result<String> {
+        self.run(&Invocation::new(program, args, Access::Query))
+    }
+
+    /// Comando sin privilegios (ej. `mount` para listar montajes).
+    pub fn command(&self, program: &str, args: &[&str]) -> Result<String> {
+        self.run(&Invocation::new(program, args, Access::User))
+    }
+
+    fn run(&self, invocation: &Invocation) -> Result<String> {
+        let args: Vec<&str> = invocation.args.iter().map(String::as_str).collect();
+        let result = match invocation.access {
+            Access::User => self.exec(&invocation.program, &args, false),
+            Access::Query => self.exec_query(&invocation.program, &args),
+            Access::Change => self.exec(&invocation.program, &args, true),
+        };
+        let output = match result {
+            Ok(output) => output,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::CommandNotFound(invocation.program.clone()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if output.success {
+            return Ok(output.stdout);
+        }
+
+        let stderr = if output.stderr.trim().is_empty() {
+            output.stdout
+        } else {
+            output.stderr
+        };
+        // Con sudo, un programa inexistente no falla al lanzar el proceso sino con este mensaje.
+        if stderr.contains("command not found") {
+            return Err(Error::CommandNotFound(invocation.program.clone()));
+        }
+        Err(Error::CommandFailed {
+            command: invocation.to_string(),
+            stderr,
+        })
+    }
+}
+
+/// Con qué permisos se lanza un comando y si modifica el sistema.
+#[derive(Clone, Copy, PartialEq)]
+enum Access {
+    User,
+    /// Con sudo, solo para leer.
+    Query,
+    /// Con sudo, modificando el sistema.
+    Change,
+}
+
+/// Un comando tal como se mostraría en la terminal.
+struct Invocation {
+    program: String,
+    args: Vec<String>,
+    access: Access,
+}
+
+impl Invocation {
+    fn new(program: &str, args: &[&str], access: Access) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            access,
+        }
+    }
+}
+
+impl std::fmt::Display for Invocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.access != Access::User {
+            write!(f, "sudo ")?;
+        }
+        write!(f, "{} {}", self.program, self.args.join(" "))
+    }
+}
+
+/// Backend real: lanza procesos y usa el sistema de ficheros local.
+pub struct SystemBackend;
+
+impl GlusterBackend for SystemBackend {
+    fn exec(&self, program: &str, args: &[&str], privileged: bool) -> io::Result<CommandOutput> {
+        let output = if privileged {
+            Command::new("sudo").arg(program).args(args).output()?
+        } else {
+            Command::new(program).args(args).output()?
+        };
+
+        Ok(CommandOutput {
+            success: output.status.success(),
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
+    }
+
+    fn exec_query(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        self.exec(program, args, true)
+    }
+
+    fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn write_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        fs::write(path, contents)
+    }
+
+    fn append_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(contents.as_bytes())
+    }
+
+    fn create_dir_all(&self, path: &str) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn remove_dir_all(&self, path: &str) -> io::Result<()> {
+        fs::remove_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &str) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn list_dirs(&self, path: &str) -> io::Result<Vec<String>> {
+        Ok(fs::read_dir(path)?
+            .flatten()
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect())
+    }
+
+    fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        Ok(fs::read_dir(path)?
+            .flatten()
+            .filter(|e| e.path().is_file())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect())
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists()
+    }
+
+    fn user_exists(&self, name: &str) -> bool {
+        users::get_user_by_name(name).is_some()
+    }
+
+    fn current_user(&self) -> String {
+        whoami::username()
+    }
+
+    fn local_hostname(&self) -> String {
+        whoami::fallible::hostname().unwrap_or_default()
+    }
+}
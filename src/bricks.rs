@@ -1,130 +1,151 @@
-use std::{fs, path::Path, process::Command};
 use dialoguer::{theme::ColorfulTheme, Input, Select, Confirm};
 
-fn create_brick() {
+use crate::backend::GlusterBackend;
+use crate::error::{self, Error, Result};
+use crate::t;
+
+const BRICKS_PATH: &str = "/gluster";
+
+fn create_brick(backend: &dyn GlusterBackend) -> Result<()> {
     let brick_name: String = Input::new()
-        .with_prompt("Nombre del brick (ej. datos, respaldo)")
-        .interact_text()
-        .unwrap();
+        .with_prompt(t!("bricks.name_prompt"))
+        .interact_text()?;
 
-    let full_path = format!("/gluster/{}", brick_name);
-    let path = Path::new(&full_path);
+    create(backend, &brick_name)
+}
 
-    if path.exists() {
-        println!("⚠️ El brick ya existe: {}", path.display());
-        return;
-    }
+/// Crea `/gluster/<nombre>` y asigna propiedad y permisos al usuario actual.
+pub fn create(backend: &dyn GlusterBackend, brick_name: &str) -> Result<()> {
+    let full_path = format!("{}/{}", BRICKS_PATH, brick_name);
 
-    match fs::create_dir_all(path) {
-        Ok(_) => println!("✅ Brick creado: {}", path.display()),
-        Err(e) => {
-            eprintln!("❌ Error al crear el directorio: {e}");
-            return;
-        }
+    if backend.exists(&full_path) {
+        return Err(Error::Message(t!("bricks.already_exists", path = full_path)));
     }
 
-    let user = whoami::username();
+    backend.create_dir_all(&full_path).map_err(|e| t!("bricks.create_dir_failed", error = e))?;
+    println!("{}", t!("bricks.created", path = full_path));
 
-    let _ = Command::new("sudo")
-        .arg("chown")
-        .arg(format!("{user}:{user}"))
-        .arg(&full_path)
-        .status();
+    let user = backend.current_user();
 
-    let _ = Command::new("sudo")
-        .arg("chmod")
-        .arg("775")
-        .arg(&full_path)
-        .status();
+    let _ = backend.sudo("chown", &[&format!("{user}:{user}"), &full_path]);
+    let _ = backend.sudo("chmod", &["775", &full_path]);
 
-    println!("🔐 Permisos y propiedad asignados correctamente.");
+    println!("{}", t!("bricks.permissions_set"));
+    Ok(())
 }
 
-fn list_bricks() {
-    let base_path = Path::new("/gluster/");
-    println!("\n📄 Lista de bricks en /gluster/");
-
-    match fs::read_dir(base_path) {
-        Ok(entries) => {
-            let mut count = 0;
-            for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    println!("🧱 {}", entry.file_name().to_string_lossy());
-                    count += 1;
-                }
-            }
+/// Nombres de los directorios de brick existentes en `/gluster`.
+pub fn list(backend: &dyn GlusterBackend) -> Result<Vec<String>> {
+    backend
+        .list_dirs(BRICKS_PATH)
+        .map_err(|_| Error::Message(t!("bricks.base_unreadable", path = BRICKS_PATH)))
+}
 
-            if count == 0 {
-                println!("⚠️ No hay bricks creados.");
+fn list_bricks(backend: &dyn GlusterBackend) {
+    println!("\n{}", t!("bricks.list_title", path = BRICKS_PATH));
+
+    match list(backend) {
+        Ok(bricks) if bricks.is_empty() => println!("{}", t!("bricks.none_created")),
+        Ok(bricks) => {
+            for brick in bricks {
+                println!("🧱 {}", brick);
             }
         }
-        Err(_) => println!("❌ No se pudo acceder a /gluster/. ¿Existe?"),
+        Err(e) => println!("{}", e),
     }
 }
 
-pub fn delete_brick() {
+/// Elimina el directorio del brick con todo su contenido.
+pub fn delete(backend: &dyn GlusterBackend, brick_name: &str) -> Result<()> {
+    let full_path = format!("{}/{}", BRICKS_PATH, brick_name);
+
+    backend
+        .remove_dir_all(&full_path)
+        .map_err(|e| t!("bricks.delete_failed", path = full_path, error = e))?;
+    println!("{}", t!("bricks.deleted", name = brick_name));
+    Ok(())
+}
+
+pub fn delete_brick(backend: &dyn GlusterBackend) -> Result<()> {
     let theme = ColorfulTheme::default();
-    let gluster_path = "/gluster";
-
-    let bricks = match fs::read_dir(gluster_path) {
-        Ok(entries) => entries
-            .filter_map(Result::ok)
-            .filter(|e| e.path().is_dir())
-            .map(|e| e.file_name().into_string().unwrap_or_default())
-            .collect::<Vec<String>>(),
-        Err(_) => {
-            println!("❌ No se pudo acceder al directorio de bricks en {}", gluster_path);
-            return;
-        }
-    };
+
+    let bricks = list(backend)?;
 
     if bricks.is_empty() {
-        println!("📁 No hay bricks disponibles en {}.", gluster_path);
-        return;
+        println!("{}", t!("bricks.none_available", path = BRICKS_PATH));
+        return Ok(());
     }
 
     let selection = Select::with_theme(&theme)
-        .with_prompt("Selecciona el brick que deseas eliminar")
+        .with_prompt(t!("bricks.delete_prompt"))
         .items(&bricks)
         .default(0)
-        .interact()
-        .unwrap();
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
     let selected_brick = &bricks[selection];
-    let full_path = format!("{}/{}", gluster_path, selected_brick);
+    let full_path = format!("{}/{}", BRICKS_PATH, selected_brick);
 
     if Confirm::with_theme(&theme)
-        .with_prompt(format!("¿Estás seguro de que quieres eliminar '{}'", full_path))
+        .with_prompt(t!("bricks.delete_confirm", path = full_path))
         .default(false)
-        .interact()
-        .unwrap()
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
     {
-        match fs::remove_dir_all(&full_path) {
-            Ok(_) => println!("🗑️ Brick '{}' eliminado correctamente.", selected_brick),
-            Err(e) => println!("❌ No se pudo eliminar '{}': {e}", full_path),
-        }
+        delete(backend, selected_brick)?;
     }
+    Ok(())
 }
 
-pub fn manage_bricks() {
+pub fn manage_bricks(backend: &dyn GlusterBackend) -> Result<()> {
     loop {
-        println!("\n🧱 Gestión de bricks GlusterFS");
+        println!("\n{}", t!("bricks.menu_title"));
 
-        let options = vec!["Crear nuevo brick", "Listar bricks existentes", "Eliminar un brick", "Salir"];
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Elige una opción")
+        let options = vec![t!("bricks.menu_create"), t!("bricks.menu_list"), t!("bricks.menu_delete"), t!("menu.exit")];
+        // Esc vuelve al menú principal
+        let Some(selection) = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("common.choose_option"))
             .items(&options)
             .default(0)
-            .interact()
-            .unwrap();
+            .interact_opt()?
+        else {
+            break;
+        };
 
         match selection {
-            0 => create_brick(),
-            1 => list_bricks(),
-            2 => delete_brick(),
-            3 => break,
-            _ => continue,
+            0 => error::report(create_brick(backend)),
+            1 => list_bricks(backend),
+            2 => error::report(delete_brick(backend)),
+            _ => break,
         }
     }
+    Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::FakeBackend;
+
+    #[test]
+    fn create_list_and_delete() {
+        let fake = FakeBackend::new().with_dir(BRICKS_PATH);
+
+        create(&fake, "datos").unwrap();
+        assert!(create(&fake, "datos").is_err());
+        assert_eq!(list(&fake).unwrap(), vec!["datos"]);
+        assert_eq!(
+            fake.commands(),
+            vec!["chown root:root /gluster/datos", "chmod 775 /gluster/datos"]
+        );
+
+        delete(&fake, "datos").unwrap();
+        assert!(list(&fake).unwrap().is_empty());
+        assert!(delete(&fake, "datos").is_err());
+    }
+
+    #[test]
+    fn list_fails_without_base_directory() {
+        assert!(list(&FakeBackend::new()).is_err());
+    }
+}
@@ -0,0 +1,1033 @@
+use clap::builder::PossibleValue;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+
+/// Gestión de clústeres de almacenamiento GlusterFS.
+///
+/// Sin subcomando se abre el menú interactivo.
+#[derive(Parser)]
+#[command(name = "cluster_almacenamiento", version)]
+pub struct Cli {
+    /// Mostrar los comandos y cambios de ficheros sin aplicarlos
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Editar las entradas de /etc/hosts
+    #[command(subcommand)]
+    Hosts(HostsCommand),
+    /// Gestionar los bricks locales en /gluster
+    #[command(subcommand)]
+    Brick(BrickCommand),
+    /// Gestionar los peers del clúster
+    #[command(subcommand)]
+    Peer(PeerCommand),
+    /// Gestionar volúmenes
+    #[command(subcommand)]
+    Volume(VolumeCommand),
+    /// Ver el estado del clúster
+    Status,
+    /// Montar un volumen bajo /media
+    Mount(MountArgs),
+    /// Desmontar un punto de montaje
+    Umount {
+        /// Punto de montaje (ej. /media/vol_personal)
+        path: String,
+        /// Eliminar el directorio después de desmontar
+        #[arg(long)]
+        remove_dir: bool,
+    },
+    /// Listar los montajes activos en /media
+    Mounts,
+    /// Consultar el registro de auditoría
+    Audit {
+        /// Solo las operaciones de este usuario
+        #[arg(long)]
+        user: Option<String>,
+        /// Solo las operaciones cuyo comando o ruta contiene este texto
+        #[arg(long)]
+        grep: Option<String>,
+        /// Solo las operaciones fallidas
+        #[arg(long)]
+        failed: bool,
+        /// Mostrar como máximo las N entradas más recientes
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Snapshots de volúmenes (requieren bricks en LVM thin)
+    #[command(subcommand)]
+    Snapshot(SnapshotCommand),
+    /// Cuotas de espacio y de objetos por directorio
+    #[command(subcommand)]
+    Quota(QuotaCommand),
+    /// Perfiles de opciones (grupos de gluster y perfiles propios)
+    #[command(subcommand)]
+    Profile(ProfileCommand),
+    /// Self-heal de volúmenes replicados y dispersos
+    #[command(subcommand)]
+    Heal(HealCommand),
+    /// Rebalanceo de los datos entre los bricks de un volumen distribuido
+    #[command(subcommand)]
+    Rebalance(RebalanceCommand),
+    /// Comparar una especificación TOML del clúster con el estado real
+    Plan {
+        /// Fichero de especificación (ej. cluster.toml)
+        file: String,
+    },
+    /// Aplicar las operaciones necesarias para alcanzar una especificación TOML
+    Apply {
+        /// Fichero de especificación (ej. cluster.toml)
+        file: String,
+        /// Aplicar sin pedir confirmación
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotCommand {
+    /// Crear un snapshot de un volumen
+    Create {
+        volume: String,
+        name: String,
+        /// Descripción guardada con el snapshot
+        #[arg(long)]
+        description: Option<String>,
+        /// No añadir la fecha al nombre
+        #[arg(long)]
+        no_timestamp: bool,
+    },
+    /// Listar los snapshots (de un volumen o de todos)
+    List { volume: Option<String> },
+    /// Detalles de un snapshot
+    Info { name: String },
+    /// Estado de los bricks de un snapshot (o de todos)
+    Status { name: Option<String> },
+    /// Activar un snapshot (necesario para montarlo o clonarlo)
+    Activate { name: String },
+    /// Desactivar un snapshot
+    Deactivate { name: String },
+    /// Restaurar el volumen de origen (se detiene y se vuelve a iniciar)
+    Restore {
+        name: String,
+        /// Confirmar la restauración
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Crear un volumen nuevo a partir de un snapshot activado
+    Clone {
+        name: String,
+        volume: String,
+        /// No iniciar el volumen clonado
+        #[arg(long)]
+        no_start: bool,
+    },
+    /// Borrar un snapshot
+    Delete {
+        name: String,
+        /// Confirmar el borrado
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Comprobar si los bricks de un volumen admiten snapshots (LVM thin)
+    Check { volume: String },
+    /// Ver las políticas de snapshots programados y sus ejecuciones
+    Schedule,
+    /// Programar snapshots de un volumen conservando los KEEP más recientes
+    ScheduleSet { volume: String, frequency: crate::schedule::Frequency, keep: usize },
+    /// Quitar una política de snapshots programados
+    ScheduleRemove { volume: String, frequency: crate::schedule::Frequency },
+    /// Crear y purgar los snapshots programados que tocan (lo lanza cron cada hora)
+    RunSchedule,
+}
+
+#[derive(Subcommand)]
+pub enum QuotaCommand {
+    /// Activar la cuota en un volumen
+    Enable { volume: String },
+    /// Desactivar la cuota (gluster borra todos los límites)
+    Disable {
+        volume: String,
+        /// Confirmar la desactivación
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Uso y límites de cada directorio
+    List {
+        volume: String,
+        /// Límites de objetos en lugar de espacio
+        #[arg(long)]
+        objects: bool,
+    },
+    /// Limitar el espacio de un directorio (ej. /ventas 10GB)
+    Limit {
+        volume: String,
+        path: String,
+        size: String,
+        /// Límite blando en porcentaje del duro
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=100))]
+        soft: Option<u32>,
+    },
+    /// Limitar el número de ficheros y directorios de un directorio
+    LimitObjects {
+        volume: String,
+        path: String,
+        count: String,
+        /// Límite blando en porcentaje del duro
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=100))]
+        soft: Option<u32>,
+    },
+    /// Quitar el límite de un directorio
+    Remove {
+        volume: String,
+        path: String,
+        /// Quitar el límite de objetos en lugar del de espacio
+        #[arg(long)]
+        objects: bool,
+    },
+    /// Límite blando por defecto, en porcentaje
+    DefaultSoftLimit {
+        volume: String,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=100))]
+        percent: u32,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommand {
+    /// Listar los grupos de gluster y los perfiles propios
+    List,
+    /// Mostrar qué opciones de un volumen cambiaría un perfil
+    Diff { volume: String, profile: String },
+    /// Aplicar un perfil a un volumen
+    Apply {
+        volume: String,
+        profile: String,
+        /// Confirmar la aplicación
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Guardar las opciones cambiadas de un volumen como perfil propio
+    Save { volume: String, profile: String },
+}
+
+#[derive(Subcommand)]
+pub enum HealCommand {
+    /// Entradas pendientes de reparar por brick
+    Info {
+        volume: String,
+        /// Listar los ficheros pendientes, no solo los contadores
+        #[arg(long)]
+        entries: bool,
+    },
+    /// Lanzar la reparación de las entradas pendientes
+    Start {
+        volume: String,
+        /// Recorrer todo el volumen en lugar de solo las entradas marcadas
+        #[arg(long)]
+        full: bool,
+    },
+    /// Activar el demonio de self-heal
+    Enable { volume: String },
+    /// Desactivar el demonio de self-heal
+    Disable { volume: String },
+    /// Consultar las entradas pendientes hasta que lleguen a cero
+    Watch {
+        volume: String,
+        /// Segundos entre consultas
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+        /// Segundos hasta darse por vencido
+        #[arg(long, default_value_t = 3600)]
+        timeout: u64,
+    },
+    /// Listar los ficheros y gfid en split-brain
+    SplitBrain { volume: String },
+    /// Tamaño y fecha de cada copia de un fichero en split-brain (bricks de este nodo)
+    Preview { volume: String, file: String },
+    /// Resolver un fichero en split-brain (ruta desde la raíz del volumen o gfid:UUID)
+    Resolve {
+        volume: String,
+        file: String,
+        #[arg(long, value_enum)]
+        policy: ResolvePolicy,
+        /// Brick cuya copia es la buena (con --policy source-brick)
+        #[arg(long, required_if_eq("policy", "source-brick"))]
+        brick: Option<String>,
+        /// Confirmar la resolución
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Resolver todo el split-brain de un brick tomando sus copias como buenas
+    ResolveBrick {
+        volume: String,
+        brick: String,
+        /// Confirmar la resolución
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RebalanceCommand {
+    /// Iniciar el rebalanceo
+    Start {
+        volume: String,
+        /// Solo recalcular el reparto, sin mover los ficheros existentes
+        #[arg(long, conflicts_with = "force")]
+        fix_layout: bool,
+        /// Mover ficheros aunque el brick de destino tenga menos espacio libre
+        #[arg(long)]
+        force: bool,
+    },
+    /// Progreso por nodo
+    Status { volume: String },
+    /// Detener el rebalanceo (los ficheros ya movidos se quedan donde están)
+    Stop { volume: String },
+    /// Mostrar el progreso hasta que termine
+    Watch {
+        volume: String,
+        /// Segundos entre consultas
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        /// Segundos hasta darse por vencido
+        #[arg(long, default_value_t = 43200)]
+        timeout: u64,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub enum ResolvePolicy {
+    /// La copia más grande
+    BiggerFile,
+    /// La copia modificada más recientemente
+    LatestMtime,
+    /// La copia de --brick
+    SourceBrick,
+}
+
+// A mano y no derivado para que la ayuda de cada valor salga del catálogo.
+impl ValueEnum for ResolvePolicy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[ResolvePolicy::BiggerFile, ResolvePolicy::LatestMtime, ResolvePolicy::SourceBrick]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            ResolvePolicy::BiggerFile => PossibleValue::new("bigger-file").help(t!("cli_help.resolve_policy.bigger_file")),
+            ResolvePolicy::LatestMtime => PossibleValue::new("latest-mtime").help(t!("cli_help.resolve_policy.latest_mtime")),
+            ResolvePolicy::SourceBrick => PossibleValue::new("source-brick").help(t!("cli_help.resolve_policy.source_brick")),
+        })
+    }
+}
+
+#[derive(Subcommand)]
+pub enum HostsCommand {
+    /// Mostrar las entradas actuales
+    List,
+    /// Agregar una entrada
+    Add { ip: String, hostname: String },
+    /// Cambiar la IP de un nombre de nodo existente
+    Update { hostname: String, ip: String },
+    /// Eliminar las entradas con esa IP o nombre
+    Remove { host: String },
+}
+
+#[derive(Subcommand)]
+pub enum BrickCommand {
+    /// Crear /gluster/NOMBRE
+    Create { name: String },
+    /// Listar bricks existentes
+    List,
+    /// Eliminar /gluster/NOMBRE y su contenido
+    Delete {
+        name: String,
+        /// Confirmar la eliminación
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PeerCommand {
+    /// Añadir un nodo al clúster
+    Probe { host: String },
+    /// Listar el pool con UUID, estado y conexión de cada peer
+    List,
+    /// Quitar un nodo del clúster (se niega si todavía aloja bricks)
+    Detach {
+        host: String,
+        /// Quitarlo aunque no esté conectado; si vuelve, habrá que limpiar su configuración
+        #[arg(long)]
+        force: bool,
+        /// Confirmar que se quita el nodo
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VolumeCommand {
+    /// Crear (e iniciar) un volumen
+    Create {
+        name: String,
+        /// Brick con formato host:/ruta (repetible)
+        #[arg(long = "brick", required = true)]
+        bricks: Vec<String>,
+        /// Copias por conjunto de réplica (2 o 3); sin él, volumen distribuido
+        #[arg(long, value_parser = clap::value_parser!(u32).range(2..=3))]
+        replica: Option<u32>,
+        /// Último brick de cada conjunto como árbitro (solo con --replica 3)
+        #[arg(long, requires = "replica")]
+        arbiter: bool,
+        /// Bricks por conjunto disperso (erasure coding, mínimo 3)
+        #[arg(long, conflicts_with = "replica", requires = "redundancy")]
+        disperse: Option<u32>,
+        /// Bricks de cada conjunto disperso que pueden fallar sin perder datos
+        #[arg(long, requires = "disperse")]
+        redundancy: Option<u32>,
+        /// Reordenar los bricks para que cada conjunto quede en nodos distintos
+        #[arg(long)]
+        reorder: bool,
+        /// Pasar `force` a gluster (bricks en la partición raíz o conjuntos en un mismo nodo)
+        #[arg(long)]
+        force: bool,
+        /// No iniciar el volumen tras crearlo
+        #[arg(long)]
+        no_start: bool,
+    },
+    /// Listar nombres de volúmenes
+    List,
+    /// Mostrar detalles de los volúmenes
+    Info { name: Option<String> },
+    /// Tabla de salud de los bricks de un volumen iniciado
+    Status { name: Option<String> },
+    /// Iniciar un volumen
+    Start { name: String },
+    /// Detener un volumen
+    Stop { name: String },
+    /// Eliminar un volumen
+    Delete {
+        name: String,
+        /// Confirmar la eliminación
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Agregar bricks a un volumen
+    AddBrick {
+        name: String,
+        /// Brick con formato host:/ruta (repetible)
+        #[arg(long = "brick", required = true)]
+        bricks: Vec<String>,
+        /// Pasar `force` a gluster (bricks en la partición raíz o conjuntos en un mismo nodo)
+        #[arg(long)]
+        force: bool,
+        /// Rebalancear después para repartir los datos existentes en los bricks nuevos
+        #[arg(long)]
+        rebalance: bool,
+    },
+    /// Quitar bricks: conjuntos completos (migra sus datos; después `remove-brick-commit`)
+    /// o, con `--replica`, un brick de cada conjunto para reducir la réplica
+    RemoveBrick {
+        name: String,
+        #[arg(required = true)]
+        bricks: Vec<String>,
+        /// Nueva réplica; los bricks se quitan al momento, sin migración
+        #[arg(long)]
+        replica: Option<u32>,
+        /// Confirmar la eliminación (también si cluster.force-migration está activo)
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Progreso de la eliminación en curso
+    RemoveBrickStatus { name: String },
+    /// Seguir la eliminación en curso hasta que termine la migración
+    RemoveBrickWatch {
+        name: String,
+        /// Segundos entre consultas
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        /// Segundos hasta darse por vencido
+        #[arg(long, default_value_t = 43200)]
+        timeout: u64,
+    },
+    /// Detener la eliminación en curso (los bricks se quedan en el volumen)
+    RemoveBrickStop { name: String },
+    /// Quitar los bricks una vez migrados sus datos sin fallos
+    RemoveBrickCommit {
+        name: String,
+        /// Confirmar la eliminación definitiva
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Sustituir un brick averiado por otro (otra ruta u otro nodo) y seguir la reparación
+    ReplaceBrick {
+        name: String,
+        old: String,
+        new: String,
+        /// Confirmar la sustitución
+        #[arg(long)]
+        yes: bool,
+        /// Segundos hasta dejar de seguir la reparación (0 para no seguirla)
+        #[arg(long, default_value_t = 86400)]
+        timeout: u64,
+    },
+    /// Detener un brick para cambiar su disco (después `reset-brick`)
+    ResetBrickStart { name: String, brick: String },
+    /// Reincorporar un brick con el disco nuevo ya montado en su ruta y seguir la reparación
+    ResetBrick {
+        name: String,
+        brick: String,
+        /// Confirmar la reincorporación
+        #[arg(long)]
+        yes: bool,
+        /// Segundos hasta dejar de seguir la reparación (0 para no seguirla)
+        #[arg(long, default_value_t = 86400)]
+        timeout: u64,
+    },
+    /// Mostrar las opciones cambiadas de un volumen
+    Options {
+        name: String,
+        /// Mostrar todas las opciones, no solo las cambiadas
+        #[arg(long)]
+        all: bool,
+    },
+    /// Cambiar una opción de un volumen (se valida antes de llamar a gluster)
+    Set { name: String, key: String, value: String },
+    /// Devolver una opción a su valor por defecto
+    Reset { name: String, key: String },
+}
+
+#[derive(Args)]
+pub struct MountArgs {
+    /// Nombre del volumen
+    pub volume: String,
+    /// Servidor desde el que montar (ej. vm1)
+    #[arg(long)]
+    pub server: String,
+    /// Directorio bajo /media (ej. vol_personal)
+    #[arg(long)]
+    pub dir: String,
+    /// Usuario al que asignar el punto de montaje
+    #[arg(long)]
+    pub owner: Option<String>,
+}
+
+/// Definición de la línea de órdenes con la ayuda de `--help` en el idioma de la sesión.
+pub fn command() -> clap::Command {
+    localize(Cli::command(), "cli_help").long_about(t!("cli_help.long_about"))
+}
+
+/// Toma la ayuda de `command`, de sus argumentos y de sus subcomandos del catálogo:
+/// `<prefix>.about`, `<prefix>.<argumento>` y `<prefix>.<subcomando>.about`.
+fn localize(command: clap::Command, prefix: &str) -> clap::Command {
+    let mut command = command.about(t!(&format!("{}.about", prefix))).long_about(None);
+
+    let args: Vec<String> = command
+        .get_arguments()
+        .filter(|a| a.get_help().is_some())
+        .map(|a| a.get_id().to_string())
+        .collect();
+    for id in args {
+        let help = t!(&format!("{}.{}", prefix, id));
+        command = command.mut_arg(id, |arg| arg.help(help).long_help(None));
+    }
+
+    let subcommands: Vec<String> = command.get_subcommands().map(|s| s.get_name().to_string()).collect();
+    for name in subcommands {
+        let prefix = format!("{}.{}", prefix, name.replace('-', "_"));
+        command = command.mut_subcommand(name, |sub| localize(sub, &prefix));
+    }
+    command
+}
+
+/// Ejecuta un subcomando y devuelve el código de salida del proceso.
+pub fn run(backend: &dyn GlusterBackend, command: Commands) -> i32 {
+    let result = execute(backend, command);
+    if backend.is_dry_run() {
+        println!("{}", t!("cli.dry_run_done"));
+    }
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+fn require_yes(yes: bool, what: &str) -> Result<()> {
+    if yes {
+        Ok(())
+    } else {
+        Err(Error::Message(t!("cli.requires_yes", what = what)))
+    }
+}
+
+/// Sigue la reparación tras sustituir un brick; con `timeout` 0 solo recuerda cómo seguirla.
+fn follow_heal(backend: &dyn GlusterBackend, volume: &str, timeout: u64) -> Result<()> {
+    if timeout == 0 {
+        println!("{}", t!("replace.watch_hint", name = volume));
+        return Ok(());
+    }
+    crate::replace::watch_heal(backend, volume, std::time::Duration::from_secs(10), timeout / 10 + 1)
+}
+
+fn execute(backend: &dyn GlusterBackend, command: Commands) -> Result<()> {
+    match command {
+        Commands::Hosts(cmd) => match cmd {
+            HostsCommand::List => {
+                for (i, line) in crate::hosts::read_entries(backend)?.iter().enumerate() {
+                    println!("{}: {}", i + 1, line);
+                }
+                Ok(())
+            }
+            HostsCommand::Add { ip, hostname } => crate::hosts::add_entry(backend, &ip, &hostname),
+            HostsCommand::Update { hostname, ip } => crate::hosts::update_entry(backend, &hostname, &ip),
+            HostsCommand::Remove { host } => crate::hosts::remove_entry(backend, &host),
+        },
+        Commands::Brick(cmd) => match cmd {
+            BrickCommand::Create { name } => crate::bricks::create(backend, &name),
+            BrickCommand::List => {
+                for brick in crate::bricks::list(backend)? {
+                    println!("{}", brick);
+                }
+                Ok(())
+            }
+            BrickCommand::Delete { name, yes } => {
+                require_yes(yes, &t!("cli.delete_brick"))?;
+                crate::bricks::delete(backend, &name)
+            }
+        },
+        Commands::Peer(cmd) => match cmd {
+            PeerCommand::Probe { host } => crate::peers::probe(backend, &host),
+            PeerCommand::Detach { host, force, yes } => {
+                if force {
+                    println!("{}", t!("peers.force_warning", host = host));
+                }
+                require_yes(yes, &t!("cli.detach_peer"))?;
+                crate::peers::detach(backend, &host, force)
+            }
+            PeerCommand::List => {
+                crate::pool::print_table(&crate::pool::pool(backend)?);
+                Ok(())
+            }
+        },
+        Commands::Volume(cmd) => match cmd {
+            VolumeCommand::Create { name, bricks, replica, arbiter, disperse, redundancy, reorder, force, no_start } => {
+                let layout = match (replica, disperse, redundancy) {
+                    (_, Some(disperse), Some(redundancy)) => crate::layout::VolumeLayout::Disperse { disperse, redundancy },
+                    (Some(3), _, _) if arbiter => crate::layout::VolumeLayout::Replicate { replica: 3, arbiter: 1 },
+                    (Some(_), _, _) if arbiter => return Err(t!("cli.arbiter_needs_replica3").into()),
+                    (Some(replica), _, _) => crate::layout::VolumeLayout::Replicate { replica, arbiter: 0 },
+                    _ => crate::layout::VolumeLayout::Distribute,
+                };
+                layout.check_brick_count(bricks.len()).map_err(|e| e.context("❌"))?;
+                let bricks = if reorder { crate::placement::arrange(&layout, &bricks).bricks } else { bricks };
+                crate::volume::print_sets(&layout, &bricks);
+                crate::volume::print_capacity(&layout, bricks.len());
+                if !force {
+                    for warning in crate::placement::check_spread(&layout, &bricks) {
+                        println!("⚠️ {}", warning);
+                    }
+                }
+                crate::volume::create(backend, &name, &layout, &bricks, !no_start, force)
+            }
+            VolumeCommand::List => {
+                for name in crate::volume::get_volume_names(backend)? {
+                    println!("{}", name);
+                }
+                Ok(())
+            }
+            VolumeCommand::Info { name } => {
+                for volume in crate::volume::info(backend, name.as_deref())? {
+                    println!("{}", volume);
+                }
+                Ok(())
+            }
+            VolumeCommand::Status { name } => {
+                for status in crate::volstatus::fetch(backend, name.as_deref())? {
+                    crate::volstatus::print_table(&status);
+                }
+                Ok(())
+            }
+            VolumeCommand::Start { name } => crate::volume::start(backend, &name),
+            VolumeCommand::Stop { name } => crate::volume::stop(backend, &name),
+            VolumeCommand::Delete { name, yes } => {
+                require_yes(yes, &t!("cli.delete_volume"))?;
+                crate::volume::delete(backend, &name)
+            }
+            VolumeCommand::AddBrick { name, bricks, force, rebalance } => {
+                crate::volume::add(backend, &name, &bricks, force)?;
+                if rebalance {
+                    crate::rebalance::start(backend, &name, crate::rebalance::Kind::Data)
+                } else {
+                    println!("{}", t!("rebalance.later_hint", name = name));
+                    Ok(())
+                }
+            }
+            VolumeCommand::RemoveBrick { name, bricks, replica: Some(replica), yes } => {
+                require_yes(yes, &t!("cli.reduce_replica"))?;
+                crate::removal::reduce_replica(backend, &name, replica, &bricks)
+            }
+            VolumeCommand::RemoveBrick { name, bricks, replica: None, yes } => {
+                if let Ok(true) = crate::volume::check_force_migration(backend, &name) {
+                    println!("{}", t!("volume.force_migration_on", name = name));
+                }
+                require_yes(yes, &t!("cli.remove_brick"))?;
+                crate::removal::start(backend, &name, &bricks).map(|_| println!("{}", t!("removal.status_hint", name = name)))
+            }
+            VolumeCommand::RemoveBrickStatus { name } => {
+                let removal = crate::removal::pending(backend, &name)?;
+                let status = crate::removal::status(backend, &removal)?;
+                println!("{}", t!("removal.title", name = name, bricks = removal.bricks.join(", ")));
+                crate::rebalance::print_progress(&name, &status);
+                match crate::removal::commit_blocker(&status) {
+                    Some(reason) => println!("{}", t!("removal.not_ready", reason = reason)),
+                    None => println!("{}", t!("removal.ready", name = name)),
+                }
+                Ok(())
+            }
+            VolumeCommand::RemoveBrickWatch { name, interval, timeout } => {
+                let rounds = timeout / interval.max(1) + 1;
+                crate::removal::watch(backend, &name, std::time::Duration::from_secs(interval), rounds)
+            }
+            VolumeCommand::RemoveBrickStop { name } => crate::removal::stop(backend, &name),
+            VolumeCommand::RemoveBrickCommit { name, yes } => {
+                require_yes(yes, &t!("cli.commit_remove_brick"))?;
+                crate::removal::commit(backend, &name)
+            }
+            VolumeCommand::ReplaceBrick { name, old, new, yes, timeout } => {
+                require_yes(yes, &t!("cli.replace_brick"))?;
+                crate::replace::replace(backend, &name, &old, &new)?;
+                follow_heal(backend, &name, timeout)
+            }
+            VolumeCommand::ResetBrickStart { name, brick } => crate::replace::reset_start(backend, &name, &brick)
+                .map(|_| println!("{}", t!("replace.reset_commit_hint", name = name, brick = brick))),
+            VolumeCommand::ResetBrick { name, brick, yes, timeout } => {
+                require_yes(yes, &t!("cli.reset_brick"))?;
+                crate::replace::reset_commit(backend, &name, &brick)?;
+                follow_heal(backend, &name, timeout)
+            }
+            VolumeCommand::Options { name, all } => {
+                let options = crate::options::list(backend, &crate::options::catalog(backend), &name)?;
+                let options: Vec<_> = options.into_iter().filter(|o| all || o.changed).collect();
+                crate::options::print_table(&name, &options);
+                Ok(())
+            }
+            VolumeCommand::Set { name, key, value } => {
+                crate::options::set(backend, &crate::options::catalog(backend), &name, &key, &value)
+            }
+            VolumeCommand::Reset { name, key } => crate::options::reset(backend, &name, &key),
+        },
+        Commands::Status => crate::cluster::status(backend),
+        Commands::Mount(args) => {
+            let mount_point = crate::mount::mount(backend, &args.server, &args.volume, &args.dir)?;
+            match args.owner {
+                Some(owner) => crate::mount::set_owner(backend, &mount_point, &owner),
+                None => Ok(()),
+            }
+        }
+        Commands::Umount { path, remove_dir } => crate::mount::unmount(backend, &path, remove_dir),
+        Commands::Mounts => {
+            for (source, target) in crate::mount::media_mounts(backend)? {
+                println!("{}", t!("mount.item", source = source, target = target));
+            }
+            Ok(())
+        }
+        Commands::Audit { user, grep, failed, limit } => {
+            let entries = crate::audit::read(backend, crate::audit::AUDIT_PATH)?;
+            let filter = crate::audit::AuditFilter {
+                user,
+                text: grep,
+                failed_only: failed,
+                limit: Some(limit),
+            };
+            crate::audit::print_entries(&filter.apply(&entries));
+            Ok(())
+        }
+        Commands::Snapshot(cmd) => match cmd {
+            SnapshotCommand::Create { volume, name, description, no_timestamp } => {
+                crate::snapshot::create(backend, &volume, &name, description.as_deref(), no_timestamp).map(|_| ())
+            }
+            SnapshotCommand::List { volume } => {
+                crate::snapshot::print_list(&crate::snapshot::list(backend, volume.as_deref())?);
+                Ok(())
+            }
+            SnapshotCommand::Info { name } => {
+                println!("{}", crate::snapshot::find(backend, &name)?);
+                Ok(())
+            }
+            SnapshotCommand::Status { name } => {
+                println!("{}", crate::snapshot::status(backend, name.as_deref())?);
+                Ok(())
+            }
+            SnapshotCommand::Activate { name } => crate::snapshot::activate(backend, &name),
+            SnapshotCommand::Deactivate { name } => crate::snapshot::deactivate(backend, &name),
+            SnapshotCommand::Restore { name, yes } => {
+                require_yes(yes, &t!("cli.restore_snapshot"))?;
+                crate::snapshot::restore(backend, &name)
+            }
+            SnapshotCommand::Clone { name, volume, no_start } => crate::snapshot::clone(backend, &name, &volume, !no_start),
+            SnapshotCommand::Delete { name, yes } => {
+                require_yes(yes, &t!("cli.delete_snapshot"))?;
+                crate::snapshot::delete(backend, &name)
+            }
+            SnapshotCommand::Check { volume } => {
+                let check = crate::snapshot::preflight(backend, &volume)?;
+                check.print();
+                if check.ok() {
+                    Ok(())
+                } else {
+                    Err(Error::Message(t!("snapshot.preflight_failed", name = volume)))
+                }
+            }
+            SnapshotCommand::Schedule => {
+                crate::schedule::print_status(backend, &crate::schedule::Schedule::load(backend)?, crate::schedule::now());
+                Ok(())
+            }
+            SnapshotCommand::ScheduleSet { volume, frequency, keep } => {
+                let mut schedule = crate::schedule::Schedule::load(backend)?;
+                schedule.set(&volume, frequency, keep)?;
+                crate::schedule::save_and_install(backend, &schedule)?;
+                println!("{}", t!("schedule.saved", name = volume, frequency = frequency, keep = keep));
+                Ok(())
+            }
+            SnapshotCommand::ScheduleRemove { volume, frequency } => {
+                let mut schedule = crate::schedule::Schedule::load(backend)?;
+                if !schedule.remove(&volume, frequency) {
+                    return Err(Error::Message(t!("schedule.not_found", name = volume, frequency = frequency)));
+                }
+                crate::schedule::save_and_install(backend, &schedule)?;
+                println!("{}", t!("schedule.removed", name = volume, frequency = frequency));
+                Ok(())
+            }
+            SnapshotCommand::RunSchedule => crate::schedule::run(backend, crate::schedule::now()).map(|report| {
+                println!("{}", t!("schedule.run_summary", created = report.created.len(), pruned = report.pruned.len()))
+            }),
+        },
+        Commands::Quota(cmd) => {
+            use crate::quota::QuotaKind;
+            let kind = |objects: bool| if objects { QuotaKind::Objects } else { QuotaKind::Usage };
+            match cmd {
+                QuotaCommand::Enable { volume } => crate::quota::enable(backend, &volume),
+                QuotaCommand::Disable { volume, yes } => {
+                    require_yes(yes, &t!("cli.disable_quota"))?;
+                    crate::quota::disable(backend, &volume)
+                }
+                QuotaCommand::List { volume, objects } => {
+                    let kind = kind(objects);
+                    crate::quota::print_table(&volume, kind, &crate::quota::list(backend, &volume, kind)?);
+                    Ok(())
+                }
+                QuotaCommand::Limit { volume, path, size, soft } => {
+                    crate::quota::set_limit(backend, &volume, QuotaKind::Usage, &path, &size, soft)
+                }
+                QuotaCommand::LimitObjects { volume, path, count, soft } => {
+                    crate::quota::set_limit(backend, &volume, QuotaKind::Objects, &path, &count, soft)
+                }
+                QuotaCommand::Remove { volume, path, objects } => {
+                    crate::quota::remove_limit(backend, &volume, kind(objects), &path)
+                }
+                QuotaCommand::DefaultSoftLimit { volume, percent } => {
+                    crate::quota::set_default_soft_limit(backend, &volume, percent)
+                }
+            }
+        }
+        Commands::Profile(cmd) => match cmd {
+            ProfileCommand::List => {
+                for profile in crate::profiles::list(backend) {
+                    println!("{}", profile);
+                }
+                Ok(())
+            }
+            ProfileCommand::Diff { volume, profile } => {
+                let profile = crate::profiles::find(backend, &profile)?;
+                let options = crate::options::list(backend, &crate::options::catalog(backend), &volume)?;
+                let diffs = crate::profiles::diff(&profile, &options);
+                crate::profiles::print_diff(&volume, &profile, &diffs);
+                Ok(())
+            }
+            ProfileCommand::Apply { volume, profile, yes } => {
+                let profile = crate::profiles::find(backend, &profile)?;
+                let docs = crate::options::catalog(backend);
+                let diffs = crate::profiles::diff(&profile, &crate::options::list(backend, &docs, &volume)?);
+                crate::profiles::print_diff(&volume, &profile, &diffs);
+                if !diffs.iter().any(crate::profiles::OptionDiff::differs) {
+                    return Ok(());
+                }
+                require_yes(yes, &t!("cli.apply_profile"))?;
+                crate::profiles::apply(backend, &docs, &volume, &profile, &diffs)
+            }
+            ProfileCommand::Save { volume, profile } => {
+                let options = crate::options::list(backend, &crate::options::catalog(backend), &volume)?;
+                crate::profiles::save(backend, &profile, &volume, &options)
+            }
+        },
+        Commands::Heal(cmd) => match cmd {
+            HealCommand::Info { volume, entries } => {
+                if entries {
+                    crate::heal::print_entries(&crate::heal::info(backend, &volume)?);
+                } else {
+                    crate::heal::print_summary(&volume, &crate::heal::summary(backend, &volume)?);
+                }
+                Ok(())
+            }
+            HealCommand::Start { volume, full } => crate::heal::trigger(backend, &volume, full),
+            HealCommand::Enable { volume } => crate::heal::set_daemon(backend, &volume, true),
+            HealCommand::Disable { volume } => crate::heal::set_daemon(backend, &volume, false),
+            HealCommand::Watch { volume, interval, timeout } => {
+                let rounds = timeout / interval.max(1) + 1;
+                crate::heal::watch(backend, &volume, std::time::Duration::from_secs(interval), rounds)
+            }
+            HealCommand::SplitBrain { volume } => {
+                crate::splitbrain::print_list(&volume, &crate::splitbrain::list(backend, &volume)?);
+                Ok(())
+            }
+            HealCommand::Preview { volume, file } => {
+                let entry = crate::splitbrain::list(backend, &volume)?
+                    .into_iter()
+                    .find(|e| e.path == file)
+                    .ok_or_else(|| Error::Message(t!("splitbrain.not_in_split_brain", path = file)))?;
+                crate::splitbrain::print_preview(&entry, &crate::splitbrain::preview(backend, &entry));
+                Ok(())
+            }
+            HealCommand::Resolve { volume, file, policy, brick, yes } => {
+                use crate::splitbrain::Policy;
+                let policy = match policy {
+                    ResolvePolicy::BiggerFile => Policy::BiggerFile,
+                    ResolvePolicy::LatestMtime => Policy::LatestMtime,
+                    ResolvePolicy::SourceBrick => Policy::SourceBrick(brick.unwrap_or_default()),
+                };
+                require_yes(yes, &t!("cli.resolve_split_brain"))?;
+                crate::splitbrain::resolve_file(backend, &volume, &file, &policy)
+            }
+            HealCommand::ResolveBrick { volume, brick, yes } => {
+                require_yes(yes, &t!("cli.resolve_split_brain"))?;
+                crate::splitbrain::resolve_brick(backend, &volume, &brick)
+            }
+        },
+        Commands::Rebalance(cmd) => match cmd {
+            RebalanceCommand::Start { volume, fix_layout, force } => {
+                use crate::rebalance::Kind;
+                let kind = match (fix_layout, force) {
+                    (true, _) => Kind::FixLayout,
+                    (_, true) => Kind::Force,
+                    _ => Kind::Data,
+                };
+                crate::rebalance::start(backend, &volume, kind)
+            }
+            RebalanceCommand::Status { volume } => {
+                crate::rebalance::print_status(&volume, &crate::rebalance::status(backend, &volume)?);
+                Ok(())
+            }
+            RebalanceCommand::Stop { volume } => crate::rebalance::stop(backend, &volume),
+            RebalanceCommand::Watch { volume, interval, timeout } => {
+                let rounds = timeout / interval.max(1) + 1;
+                crate::rebalance::watch(backend, &volume, std::time::Duration::from_secs(interval), rounds)
+            }
+        },
+        Commands::Plan { file } => {
+            let spec = crate::spec::ClusterSpec::load(backend, &file)?;
+            crate::spec::plan(backend, &spec)?.print();
+            Ok(())
+        }
+        Commands::Apply { file, yes } => {
+            let spec = crate::spec::ClusterSpec::load(backend, &file)?;
+            let plan = crate::spec::plan(backend, &spec)?;
+            plan.print();
+            if plan.actions.is_empty() {
+                return Ok(());
+            }
+            require_yes(yes, &t!("cli.apply"))?;
+            crate::spec::apply(backend, &plan)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::FromArgMatches;
+
+    fn parse(args: &[&str]) -> clap::error::Result<Cli> {
+        Cli::try_parse_from(std::iter::once("cluster_almacenamiento").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn without_subcommand_opens_the_menu() {
+        let cli = parse(&[]).unwrap();
+        assert!(cli.command.is_none() && !cli.dry_run);
+    }
+
+    #[test]
+    fn create_and_add_take_force_and_repeated_bricks() {
+        let cli = parse(&[
+            "volume", "create", "datos", "--brick", "vm1:/gluster/a", "--brick", "vm2:/gluster/a", "--replica", "2", "--force",
+            "--dry-run",
+        ])
+        .unwrap();
+        assert!(cli.dry_run);
+        match cli.command {
+            Some(Commands::Volume(VolumeCommand::Create { name, bricks, replica, force, no_start, .. })) => {
+                assert_eq!((name.as_str(), bricks.len(), replica, force, no_start), ("datos", 2, Some(2), true, false));
+            }
+            _ => panic!("se esperaba volume create"),
+        }
+
+        let cli = parse(&["--dry-run", "volume", "add-brick", "datos", "--brick", "vm3:/gluster/a", "--rebalance"]).unwrap();
+        assert!(cli.dry_run);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Volume(VolumeCommand::AddBrick { force: false, rebalance: true, ref bricks, .. })) if bricks == &["vm3:/gluster/a"]
+        ));
+    }
+
+    #[test]
+    fn invalid_combinations_are_rejected_by_the_parser() {
+        // Sin bricks, réplica fuera de rango, árbitro sin réplica y disperso sin redundancia.
+        assert!(parse(&["volume", "create", "datos"]).is_err());
+        assert!(parse(&["volume", "create", "datos", "--brick", "vm1:/gluster/a", "--replica", "4"]).is_err());
+        assert!(parse(&["volume", "create", "datos", "--brick", "vm1:/gluster/a", "--arbiter"]).is_err());
+        assert!(parse(&["volume", "create", "datos", "--brick", "vm1:/gluster/a", "--disperse", "3"]).is_err());
+        assert!(parse(&["rebalance", "start", "datos", "--fix-layout", "--force"]).is_err());
+        assert!(parse(&["heal", "resolve", "datos", "/f", "--policy", "source-brick"]).is_err());
+        assert!(parse(&["quota", "default-soft-limit", "datos", "0"]).is_err());
+    }
+
+    #[test]
+    fn subcommands_and_value_enums_parse() {
+        assert!(matches!(
+            parse(&["heal", "resolve", "datos", "/f", "--policy", "latest-mtime"]).unwrap().command,
+            Some(Commands::Heal(HealCommand::Resolve { policy: ResolvePolicy::LatestMtime, brick: None, yes: false, .. }))
+        ));
+        assert!(matches!(
+            parse(&["snapshot", "schedule-set", "datos", "daily", "7"]).unwrap().command,
+            Some(Commands::Snapshot(SnapshotCommand::ScheduleSet { frequency: crate::schedule::Frequency::Daily, keep: 7, .. }))
+        ));
+        assert!(matches!(
+            parse(&["audit", "--failed"]).unwrap().command,
+            Some(Commands::Audit { failed: true, limit: 50, user: None, grep: None })
+        ));
+        assert!(matches!(
+            parse(&["umount", "/media/datos", "--remove-dir"]).unwrap().command,
+            Some(Commands::Umount { remove_dir: true, .. })
+        ));
+    }
+
+    #[test]
+    fn localized_command_parses_like_the_derived_one() {
+        let matches = command().try_get_matches_from(["cluster_almacenamiento", "peer", "detach", "vm3", "--force", "--yes"]).unwrap();
+        assert!(matches!(
+            Cli::from_arg_matches(&matches).unwrap().command,
+            Some(Commands::Peer(PeerCommand::Detach { force: true, yes: true, .. }))
+        ));
+    }
+}
@@ -1,25 +1,34 @@
-use std::process::Command;
+use crate::backend::GlusterBackend;
+use crate::error::Result;
+use crate::t;
 
-pub fn check_status() {
-    println!("📡 Verificando estado del clúster...\n");
+/// Muestra el estado de los peers y de los volúmenes; falla si algún comando no termina bien.
+pub fn status(backend: &dyn GlusterBackend) -> Result<()> {
+    println!("{}\n", t!("cluster.checking"));
 
-    println!("🔗 Estado de los peers:");
-    let _ = Command::new("gluster")
-        .args(["peer", "status"])
-        .status()
-        .expect("❌ Fallo al ejecutar 'gluster peer status'");
+    println!("{}", t!("cluster.peers_title"));
+    let peers = crate::pool::pool(backend)
+        .map_err(|e| e.context(&t!("cluster.command_failed", command = "gluster pool list")))?;
+    crate::pool::print_table(&peers);
 
-    println!("\n📦 Información del volumen:");
-    let _ = Command::new("gluster")
-        .args(["volume", "info"])
-        .status()
-        .expect("❌ Fallo al ejecutar 'gluster volume info'");
+    println!("\n{}", t!("cluster.info_title"));
+    let volumes = crate::volinfo::fetch(backend, None)
+        .map_err(|e| e.context(&t!("cluster.command_failed", command = "gluster volume info")))?;
+    for volume in &volumes {
+        println!("{}", volume);
+    }
 
-    println!("\n📈 Estado del volumen:");
-    let _ = Command::new("gluster")
-        .args(["volume", "status"])
-        .status()
-        .expect("❌ Fallo al ejecutar 'gluster volume status'");
+    println!("\n{}", t!("cluster.status_title"));
+    let statuses = crate::volstatus::fetch(backend, None)
+        .map_err(|e| e.context(&t!("cluster.command_failed", command = "gluster volume status")))?;
+    for volume in &volumes {
+        match statuses.iter().find(|s| s.name == volume.name) {
+            Some(status) => crate::volstatus::print_table(status),
+            None => println!("{}", t!("cluster.no_processes", name = volume.name, status = volume.status)),
+        }
+        println!();
+    }
 
-    println!("\n✅ Consulta completada.\n");
+    println!("\n{}\n", t!("cluster.done"));
+    Ok(())
 }
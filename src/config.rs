@@ -0,0 +1,95 @@
+//! Configuración opcional de la herramienta en `/etc/cluster_almacenamiento/config.toml`.
+//!
+//! ```toml
+//! language = "en"   # es | en; si falta se usa LC_ALL / LC_MESSAGES / LANG
+//! executable = "/opt/cluster/cluster_almacenamiento"   # el que lanza cron; por defecto INSTALL_PATH
+//! ```
+
+use serde::Deserialize;
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::i18n::Lang;
+use crate::t;
+
+pub const CONFIG_PATH: &str = "/etc/cluster_almacenamiento/config.toml";
+/// Donde se instala el binario; es el que ejecuta cron para los snapshots programados.
+pub const INSTALL_PATH: &str = "/usr/local/bin/cluster_almacenamiento";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub executable: Option<String>,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self> {
+        let config: Config = toml::from_str(text).map_err(|e| Error::Parse(t!("config.invalid", path = CONFIG_PATH, error = e)))?;
+        if let Some(language) = &config.language
+            && Lang::parse(language).is_none()
+        {
+            return Err(Error::Parse(t!("config.unknown_language", path = CONFIG_PATH, language = language)));
+        }
+        Ok(config)
+    }
+
+    /// Lee la configuración; si el fichero no existe se usan los valores por defecto.
+    pub fn load(backend: &dyn GlusterBackend) -> Result<Self> {
+        if !backend.exists(CONFIG_PATH) {
+            return Ok(Config::default());
+        }
+        let text = backend
+            .read_file(CONFIG_PATH)
+            .map_err(|e| t!("config.read_failed", path = CONFIG_PATH, error = e))?;
+        Self::parse(&text)
+    }
+
+    /// Idioma de la sesión: el de la configuración, si no el del entorno y por defecto español.
+    pub fn language(&self, env: impl Fn(&str) -> Option<String>) -> Lang {
+        self.language
+            .as_deref()
+            .and_then(Lang::parse)
+            .or_else(|| Lang::from_env(env))
+            .unwrap_or(Lang::Es)
+    }
+
+    /// Ruta estable del binario para cron (nunca la del ejecutable en curso, que puede ser una compilación).
+    pub fn executable(&self) -> &str {
+        self.executable.as_deref().unwrap_or(INSTALL_PATH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::FakeBackend;
+
+    #[test]
+    fn config_language_wins_over_environment() {
+        let env = |name: &str| (name == "LANG").then(|| "es_ES.UTF-8".to_string());
+
+        let config = Config::parse("language = \"en\"\n").unwrap();
+        assert_eq!(config.language(env), Lang::En);
+        assert_eq!(Config::default().language(env), Lang::Es);
+        assert_eq!(Config::default().language(|_| None), Lang::Es);
+    }
+
+    #[test]
+    fn missing_file_uses_defaults_and_bad_values_fail() {
+        assert!(Config::load(&FakeBackend::new()).unwrap().language.is_none());
+
+        let fake = FakeBackend::new().with_file(CONFIG_PATH, "language = \"klingon\"\n");
+        assert!(Config::load(&fake).is_err());
+        assert!(Config::parse("idioma = \"en\"\n").is_err());
+    }
+
+    #[test]
+    fn executable_defaults_to_the_install_path() {
+        assert_eq!(Config::default().executable(), INSTALL_PATH);
+        let config = Config::parse("executable = \"/opt/cluster/cluster_almacenamiento\"\n").unwrap();
+        assert_eq!(config.executable(), "/opt/cluster/cluster_almacenamiento");
+    }
+}
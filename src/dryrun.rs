@@ -0,0 +1,182 @@
+//! Modo simulación: envuelve otro backend, deja pasar las consultas y solo muestra
+//! los comandos privilegiados y los cambios de ficheros que se harían.
+
+use std::cell::Cell;
+use std::io;
+
+use crate::backend::{CommandOutput, GlusterBackend};
+use crate::t;
+
+pub struct DryRunBackend<'a> {
+    inner: &'a dyn GlusterBackend,
+    enabled: Cell<bool>,
+}
+
+impl<'a> DryRunBackend<'a> {
+    pub fn new(inner: &'a dyn GlusterBackend, enabled: bool) -> Self {
+        Self {
+            inner,
+            enabled: Cell::new(enabled),
+        }
+    }
+
+    /// Activa o desactiva la simulación para el resto de la sesión.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+}
+
+impl GlusterBackend for DryRunBackend<'_> {
+    fn exec(&self, program: &str, args: &[&str], privileged: bool) -> io::Result<CommandOutput> {
+        if self.enabled.get() && privileged {
+            // `sudo` ya mostró la línea exacta; aquí solo se evita ejecutarla.
+            return Ok(CommandOutput {
+                success: true,
+                code: Some(0),
+                stdout: String::new(),
+                stderr: String::new(),
+            });
+        }
+        self.inner.exec(program, args, privileged)
+    }
+
+    fn exec_query(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        // Una consulta no cambia nada: se ejecuta también en simulación.
+        self.inner.exec_query(program, args)
+    }
+
+    fn read_file(&self, path: &str) -> io::Result<String> {
+        self.inner.read_file(path)
+    }
+
+    fn write_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        if !self.enabled.get() {
+            return self.inner.write_file(path, contents);
+        }
+
+        println!("{}", t!("dryrun.would_write", path = path));
+        let current = self.inner.read_file(path).unwrap_or_default();
+        let changes = line_changes(&current, contents);
+        if changes.is_empty() {
+            println!("{}", t!("dryrun.no_changes"));
+        }
+        for change in changes {
+            println!("   {}", change);
+        }
+        Ok(())
+    }
+
+    fn append_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        if !self.enabled.get() {
+            return self.inner.append_file(path, contents);
+        }
+        println!("{}", t!("dryrun.would_append", count = contents.lines().count(), path = path));
+        Ok(())
+    }
+
+    fn create_dir_all(&self, path: &str) -> io::Result<()> {
+        if !self.enabled.get() {
+            return self.inner.create_dir_all(path);
+        }
+        println!("{}", t!("dryrun.would_create_dir", path = path));
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &str) -> io::Result<()> {
+        if !self.enabled.get() {
+            return self.inner.remove_dir_all(path);
+        }
+        println!("{}", t!("dryrun.would_remove_dir", path = path));
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &str) -> io::Result<()> {
+        if !self.enabled.get() {
+            return self.inner.remove_file(path);
+        }
+        println!("{}", t!("dryrun.would_remove_file", path = path));
+        Ok(())
+    }
+
+    fn list_dirs(&self, path: &str) -> io::Result<Vec<String>> {
+        self.inner.list_dirs(path)
+    }
+
+    fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        self.inner.list_files(path)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.inner.exists(path)
+    }
+
+    fn user_exists(&self, name: &str) -> bool {
+        self.inner.user_exists(name)
+    }
+
+    fn current_user(&self) -> String {
+        self.inner.current_user()
+    }
+
+    fn local_hostname(&self) -> String {
+        self.inner.local_hostname()
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.enabled.get()
+    }
+}
+
+/// Líneas eliminadas (`- `) y agregadas (`+ `) entre dos versiones de un fichero.
+pub fn line_changes(old: &str, new: &str) -> Vec<String> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let removed = old_lines.iter().filter(|l| !new_lines.contains(l)).map(|l| format!("- {}", l));
+    let added = new_lines.iter().filter(|l| !old_lines.contains(l)).map(|l| format!("+ {}", l));
+    removed.chain(added).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    #[test]
+    fn mutations_are_shown_but_not_applied() {
+        let fake = FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_file("/etc/hosts", "127.0.0.1 localhost\n")
+            .with_dir("/gluster")
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        let backend = DryRunBackend::new(&fake, true);
+
+        crate::hosts::add_entry(&backend, "192.168.1.11", "vm2").unwrap();
+        crate::bricks::create(&backend, "nuevo").unwrap();
+        crate::volume::stop(&backend, "datos").unwrap();
+        crate::volume::delete(&backend, "datos").unwrap();
+        crate::mount::mount(&backend, "vm1", "datos", "datos").unwrap();
+
+        assert!(fake.commands().is_empty());
+        assert_eq!(fake.read_file("/etc/hosts").unwrap(), "127.0.0.1 localhost\n");
+        assert!(!fake.exists("/gluster/nuevo"));
+        assert_eq!(fake.volume("datos").unwrap().state, VolumeState::Started);
+    }
+
+    #[test]
+    fn disabling_applies_changes_again() {
+        let fake = FakeBackend::new().with_dir("/gluster");
+        let backend = DryRunBackend::new(&fake, true);
+
+        backend.set_enabled(false);
+        crate::bricks::create(&backend, "a").unwrap();
+        assert!(fake.exists("/gluster/a"));
+        assert!(!backend.is_dry_run());
+    }
+
+    #[test]
+    fn diff_shows_removed_and_added_lines() {
+        let changes = line_changes("127.0.0.1 localhost\n10.0.0.1 vm1\n", "127.0.0.1 localhost\n10.0.0.2 vm1\n");
+        assert_eq!(changes, vec!["- 10.0.0.1 vm1", "+ 10.0.0.2 vm1"]);
+    }
+}
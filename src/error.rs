@@ -0,0 +1,136 @@
+//! Error común de la herramienta: todos los módulos devuelven `error::Result`.
+
+use std::fmt;
+use std::io;
+
+use crate::t;
+
+#[derive(Debug)]
+pub enum Error {
+    /// El usuario canceló un prompt (Esc o Ctrl-C).
+    Cancelled,
+    /// El programa no está instalado o no está en el PATH (ej. falta `gluster`).
+    CommandNotFound(String),
+    /// El comando terminó con error; `stderr` trae su salida de error (o stdout si vino vacía).
+    CommandFailed { command: String, stderr: String },
+    /// Salida de gluster o fichero con un formato inesperado.
+    Parse(String),
+    /// Falta de permisos sobre un fichero o comando.
+    PermissionDenied(String),
+    Io(io::Error),
+    /// Validación u operación fallida con un mensaje ya preparado para el usuario.
+    Message(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Antepone contexto a los fallos de comandos y mensajes; el resto se propaga tal cual.
+    pub fn context(self, context: &str) -> Error {
+        match self {
+            Error::CommandFailed { command, stderr } => Error::CommandFailed {
+                command,
+                stderr: format!("{} {}", context, stderr.trim()),
+            },
+            Error::Message(message) => Error::Message(format!("{} {}", context, message)),
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Cancelled => write!(f, "{}", t!("error.cancelled")),
+            Error::CommandNotFound(program) => write!(f, "{}", t!("error.command_not_found", program = program)),
+            Error::CommandFailed { stderr, .. } => write!(f, "{}", stderr.trim()),
+            Error::Parse(message) | Error::Message(message) => write!(f, "{}", message),
+            Error::PermissionDenied(what) => write!(f, "{}", t!("error.permission_denied", what = what)),
+            Error::Io(e) => write!(f, "{}", t!("error.io", error = e)),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::PermissionDenied => Error::PermissionDenied(e.to_string()),
+            io::ErrorKind::Interrupted => Error::Cancelled,
+            _ => Error::Io(e),
+        }
+    }
+}
+
+/// Ctrl-C en un prompt llega como una lectura interrumpida.
+impl From<dialoguer::Error> for Error {
+    fn from(e: dialoguer::Error) -> Self {
+        let dialoguer::Error::IO(e) = e;
+        e.into()
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Message(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Message(message.to_string())
+    }
+}
+
+/// Informa el resultado de una acción del menú sin salir de él.
+pub fn report(result: Result<()>) {
+    match result {
+        Ok(()) => {}
+        Err(Error::Cancelled) => {
+            // Ctrl-C en medio de un prompt puede dejar el cursor oculto.
+            let _ = console::Term::stdout().show_cursor();
+            println!("{}", Error::Cancelled);
+        }
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::GlusterBackend;
+    use crate::fake::FakeBackend;
+
+    #[test]
+    fn command_errors_keep_their_kind() {
+        let fake = FakeBackend::new();
+        let backend: &dyn GlusterBackend = &fake;
+
+        assert!(matches!(backend.command("gluster-no-existe", &[]), Err(Error::CommandNotFound(p)) if p == "gluster-no-existe"));
+        match backend.sudo_gluster(&["volume", "start", "nada"]) {
+            Err(Error::CommandFailed { command, stderr }) => {
+                assert_eq!(command, "sudo gluster --mode=script volume start nada");
+                assert!(stderr.contains("does not exist"));
+            }
+            other => panic!("se esperaba CommandFailed: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn ctrl_c_and_permissions_are_recognized() {
+        let interrupted = dialoguer::Error::IO(io::Error::from(io::ErrorKind::Interrupted));
+        assert!(matches!(Error::from(interrupted), Error::Cancelled));
+        assert!(matches!(
+            Error::from(io::Error::from(io::ErrorKind::PermissionDenied)),
+            Error::PermissionDenied(_)
+        ));
+    }
+
+    #[test]
+    fn context_is_added_only_to_failures() {
+        let failed = Error::CommandFailed { command: "gluster".into(), stderr: "boom\n".into() };
+        assert_eq!(failed.context("❌ Falló:").to_string(), "❌ Falló: boom");
+        assert!(matches!(Error::Cancelled.context("❌ Falló:"), Error::Cancelled));
+    }
+}
@@ -0,0 +1,1392 @@
+//! Backend en memoria para los tests: modela peers, volúmenes, bricks, montajes y ficheros
+//! respondiendo a los mismos comandos `gluster` que usa la herramienta.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+
+use crate::backend::{CommandOutput, GlusterBackend};
+use crate::xml;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VolumeState {
+    Created,
+    Started,
+    Stopped,
+}
+
+#[derive(Clone, Debug)]
+pub struct FakePeer {
+    pub hostname: String,
+    pub connected: bool,
+    pub state: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct FakeVolume {
+    pub name: String,
+    pub state: VolumeState,
+    pub bricks: Vec<String>,
+    /// Bricks con un `remove-brick ... start` en curso.
+    pub removing: Vec<String>,
+    /// Consultas de estado de la eliminación: la primera la muestra en curso y las siguientes terminada.
+    pub removal_polls: u32,
+    /// Ficheros que la migración no pudo mover.
+    pub removal_failures: u64,
+    pub options: BTreeMap<String, String>,
+    /// Bricks cuyo proceso está caído (`volume status` los informa offline).
+    pub offline: Vec<String>,
+    pub replica: u32,
+    pub arbiter: u32,
+    pub disperse: u32,
+    pub redundancy: u32,
+    pub quotas: Vec<FakeQuota>,
+    /// Entradas por reparar de cada brick (`heal info`).
+    pub heal_pending: BTreeMap<String, Vec<String>>,
+    /// Se lanzó `volume heal`: cada consulta de `heal info summary` repara una entrada por brick.
+    pub healing: bool,
+    /// Ficheros (ruta o `gfid:UUID`) en split-brain y los bricks que los informan.
+    pub split_brain: BTreeMap<String, Vec<String>>,
+    pub rebalance: Option<FakeRebalance>,
+}
+
+/// Rebalanceo lanzado: la primera consulta de estado lo muestra en curso y la siguiente terminado.
+#[derive(Clone, Debug)]
+pub struct FakeRebalance {
+    pub fix_layout: bool,
+    pub polls: u32,
+    pub stopped: bool,
+}
+
+impl FakeRebalance {
+    fn running(&self) -> bool {
+        !self.stopped && self.polls == 0
+    }
+}
+
+/// Sistema de ficheros montado, para `findmnt` y `lvs`.
+#[derive(Clone, Debug)]
+pub struct FakeFilesystem {
+    pub target: String,
+    pub source: String,
+    /// Atributos de `lvs` si el dispositivo es un volumen lógico (`Vwi-aotz--` para thin).
+    pub lv_attr: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FakeSnapshot {
+    pub name: String,
+    pub volume: String,
+    pub description: Option<String>,
+    pub active: bool,
+}
+
+/// Límite de cuota de un directorio del volumen.
+#[derive(Clone, Debug)]
+pub struct FakeQuota {
+    pub path: String,
+    /// Límite de objetos (`limit-objects`) en lugar de espacio.
+    pub objects: bool,
+    pub hard: u64,
+    pub soft: Option<u32>,
+    pub used: u64,
+}
+
+impl FakeVolume {
+    pub fn new(name: &str, bricks: Vec<String>, state: VolumeState) -> Self {
+        Self {
+            name: name.to_string(),
+            state,
+            bricks,
+            removing: vec![],
+            removal_polls: 0,
+            removal_failures: 0,
+            options: BTreeMap::new(),
+            offline: vec![],
+            replica: 1,
+            arbiter: 0,
+            disperse: 0,
+            redundancy: 0,
+            quotas: vec![],
+            heal_pending: BTreeMap::new(),
+            healing: false,
+            split_brain: BTreeMap::new(),
+            rebalance: None,
+        }
+    }
+
+    /// Bricks por subvolumen.
+    fn set_size(&self) -> usize {
+        if self.disperse > 0 {
+            self.disperse as usize
+        } else {
+            self.replica as usize
+        }
+    }
+
+    fn type_str(&self) -> &'static str {
+        let distributed = self.bricks.len() > self.set_size();
+        match (self.disperse > 0, self.replica > 1, distributed) {
+            (true, _, false) => "Disperse",
+            (true, _, true) => "Distributed-Disperse",
+            (false, true, false) => "Replicate",
+            (false, true, true) => "Distributed-Replicate",
+            _ => "Distribute",
+        }
+    }
+}
+
+/// Opciones que conoce el backend de pruebas: (nombre, valor por defecto, descripción).
+const OPTIONS: &[(&str, &str, &str)] = &[
+    ("cluster.force-migration", "off", "Migrate files even if they are open during rebalance."),
+    ("cluster.quorum-type", "none", "If value is \"fixed\" only allow writes if quorum-count bricks are present."),
+    ("network.ping-timeout", "42", "Time duration for which the client waits to check if the server is responsive."),
+    ("performance.cache-size", "32MB", "Size of the read cache."),
+    ("performance.readdir-ahead", "on", "enable/disable readdir-ahead translator in the volume."),
+    ("auth.allow", "*", "Allow a comma separated list of addresses and/or hostnames to connect to the server."),
+];
+
+#[derive(Default)]
+pub struct FakeState {
+    /// Nombre del nodo en el que "corre" la herramienta.
+    pub local_host: String,
+    pub peers: Vec<FakePeer>,
+    pub volumes: Vec<FakeVolume>,
+    pub files: BTreeMap<String, String>,
+    pub dirs: BTreeSet<String>,
+    /// (origen, punto de montaje)
+    pub mounts: Vec<(String, String)>,
+    pub users: Vec<String>,
+    /// Comandos privilegiados ejecutados, en orden.
+    pub commands: Vec<String>,
+    /// Montajes además de la raíz, que está en `/dev/sda1` sin LVM.
+    pub filesystems: Vec<FakeFilesystem>,
+    pub snapshots: Vec<FakeSnapshot>,
+    /// Tamaño y mtime de ficheros locales, para `stat`.
+    pub stats: BTreeMap<String, (u64, i64)>,
+    /// Programas que `sudo` se niega a ejecutar (sin permiso en sudoers).
+    pub sudo_denied: BTreeSet<String>,
+    /// Comandos que fallan con el stderr indicado, por prefijo de la línea (`gluster --mode=script volume start`).
+    pub failures: BTreeMap<String, String>,
+}
+
+pub struct FakeBackend {
+    pub state: RefCell<FakeState>,
+}
+
+impl Default for FakeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeBackend {
+    pub fn new() -> Self {
+        let state = FakeState {
+            local_host: "vm1".to_string(),
+            users: vec!["root".to_string(), "operador".to_string()],
+            ..FakeState::default()
+        };
+        Self { state: RefCell::new(state) }
+    }
+
+    pub fn with_peer(self, hostname: &str, connected: bool) -> Self {
+        self.state.borrow_mut().peers.push(FakePeer {
+            hostname: hostname.to_string(),
+            connected,
+            state: "Peer in Cluster".to_string(),
+        });
+        self
+    }
+
+    pub fn with_volume(self, name: &str, bricks: &[&str], state: VolumeState) -> Self {
+        let bricks = bricks.iter().map(|b| b.to_string()).collect();
+        self.state.borrow_mut().volumes.push(FakeVolume::new(name, bricks, state));
+        self
+    }
+
+    pub fn with_file(self, path: &str, contents: &str) -> Self {
+        self.state.borrow_mut().files.insert(path.to_string(), contents.to_string());
+        self
+    }
+
+    pub fn with_filesystem(self, target: &str, source: &str, lv_attr: Option<&str>) -> Self {
+        self.state.borrow_mut().filesystems.push(FakeFilesystem {
+            target: target.to_string(),
+            source: source.to_string(),
+            lv_attr: lv_attr.map(str::to_string),
+        });
+        self
+    }
+
+    pub fn with_replica(self, volume: &str, replica: u32) -> Self {
+        self.update_volume(volume, |v| v.replica = replica)
+    }
+
+    pub fn with_state(self, volume: &str, state: VolumeState) -> Self {
+        self.update_volume(volume, |v| v.state = state)
+    }
+
+    pub fn with_option(self, volume: &str, key: &str, value: &str) -> Self {
+        self.update_volume(volume, |v| {
+            v.options.insert(key.to_string(), value.to_string());
+        })
+    }
+
+    /// Brick con el proceso caído: `volume status` lo informa offline.
+    pub fn with_offline_brick(self, volume: &str, brick: &str) -> Self {
+        self.update_volume(volume, |v| v.offline.push(brick.to_string()))
+    }
+
+    /// Ficheros que la migración de `remove-brick` no podrá mover.
+    pub fn with_removal_failures(self, volume: &str, failures: u64) -> Self {
+        self.update_volume(volume, |v| v.removal_failures = failures)
+    }
+
+    fn update_volume(self, volume: &str, update: impl FnOnce(&mut FakeVolume)) -> Self {
+        if let Some(v) = self.state.borrow_mut().volumes.iter_mut().find(|v| v.name == volume) {
+            update(v);
+        }
+        self
+    }
+
+    pub fn with_pending_heal(self, volume: &str, brick: &str, entries: &[&str]) -> Self {
+        self.update_volume(volume, |v| {
+            v.heal_pending.insert(brick.to_string(), entries.iter().map(|e| e.to_string()).collect());
+        })
+    }
+
+    pub fn with_split_brain(self, volume: &str, path: &str, bricks: &[&str]) -> Self {
+        self.update_volume(volume, |v| {
+            v.split_brain.insert(path.to_string(), bricks.iter().map(|b| b.to_string()).collect());
+        })
+    }
+
+    pub fn with_stat(self, path: &str, size: u64, mtime: i64) -> Self {
+        self.state.borrow_mut().stats.insert(path.to_string(), (size, mtime));
+        self
+    }
+
+    /// Copia en el brick de otro nodo, que se consulta por ssh.
+    pub fn with_remote_stat(self, host: &str, path: &str, size: u64, mtime: i64) -> Self {
+        self.with_stat(&format!("{}:{}", host, path), size, mtime)
+    }
+
+    pub fn with_sudo_denied(self, program: &str) -> Self {
+        self.state.borrow_mut().sudo_denied.insert(program.to_string());
+        self
+    }
+
+    pub fn with_command_failure(self, prefix: &str, stderr: &str) -> Self {
+        self.state.borrow_mut().failures.insert(prefix.to_string(), stderr.to_string());
+        self
+    }
+
+    pub fn with_dir(self, path: &str) -> Self {
+        self.state.borrow_mut().dirs.insert(path.to_string());
+        self
+    }
+
+    pub fn volume(&self, name: &str) -> Option<FakeVolume> {
+        self.state.borrow().volumes.iter().find(|v| v.name == name).cloned()
+    }
+
+    pub fn commands(&self) -> Vec<String> {
+        self.state.borrow().commands.clone()
+    }
+
+    fn gluster(&self, args: &[&str]) -> Result<String, String> {
+        // `--mode=script` solo evita prompts; no cambia el resultado.
+        let args: Vec<&str> = args.iter().copied().filter(|a| *a != "--mode=script").collect();
+        let mut state = self.state.borrow_mut();
+
+        match args.as_slice() {
+            ["peer", "probe", host] => {
+                if state.peers.iter().any(|p| p.hostname == *host) {
+                    return Ok(format!("peer probe: Host {} port 24007 already in peer list\n", host));
+                }
+                state.peers.push(FakePeer {
+                    hostname: host.to_string(),
+                    connected: true,
+                    state: "Peer in Cluster".to_string(),
+                });
+                Ok("peer probe: success\n".to_string())
+            }
+            ["peer", "detach", host, rest @ ..] => {
+                let Some(index) = state.peers.iter().position(|p| p.hostname == *host) else {
+                    return Err(format!("peer detach: failed: {} is not part of cluster\n", host));
+                };
+                if state.volumes.iter().any(|v| v.bricks.iter().any(|b| crate::placement::brick_host(b) == *host)) {
+                    return Err(format!("peer detach: failed: Brick(s) with the peer {} exist in cluster\n", host));
+                }
+                if !state.peers[index].connected && rest != ["force"] {
+                    return Err(format!("peer detach: failed: {} is not connected. Use the force option\n", host));
+                }
+                state.peers.remove(index);
+                Ok("peer detach: success\n".to_string())
+            }
+            ["peer", "status", "--xml"] => Ok(peer_status_xml(&state.peers, false)),
+            ["pool", "list", "--xml"] => Ok(peer_status_xml(&state.peers, true)),
+            ["volume", "list"] => Ok(state.volumes.iter().map(|v| format!("{}\n", v.name)).collect()),
+            ["volume", "info", "--xml"] => Ok(volume_info_xml(state.volumes.iter())),
+            ["volume", "info", name, "--xml"] => {
+                if !state.volumes.iter().any(|v| v.name == *name) {
+                    return Err(xml_error(&format!("Volume {} does not exist", name)));
+                }
+                Ok(volume_info_xml(state.volumes.iter().filter(|v| v.name == *name)))
+            }
+            ["volume", "status", target, extra @ .., "--xml"] => {
+                let started = state.volumes.iter().filter(|v| v.state == VolumeState::Started);
+                if *target == "all" {
+                    return Ok(volume_status_xml(started, extra));
+                }
+                let volume = find_volume(&mut state, target)?;
+                if volume.state != VolumeState::Started {
+                    return Err(xml_error(&format!("Volume {} is not started", target)));
+                }
+                let volume = volume.clone();
+                Ok(volume_status_xml(std::iter::once(&volume), extra))
+            }
+            ["volume", "create", name, rest @ ..] => {
+                if state.volumes.iter().any(|v| v.name == *name) {
+                    return Err(format!("volume create: {}: failed: Volume {} already exists\n", name, name));
+                }
+                let bricks = brick_args(rest);
+                if bricks.is_empty() {
+                    return Err("volume create: failed: no bricks\n".to_string());
+                }
+                check_brick_hosts(&state, &bricks)?;
+                let mut volume = FakeVolume::new(name, bricks, VolumeState::Created);
+                volume.replica = keyword_value(rest, "replica").unwrap_or(1);
+                volume.arbiter = keyword_value(rest, "arbiter").unwrap_or(0);
+                volume.disperse = keyword_value(rest, "disperse").unwrap_or(0);
+                volume.redundancy = keyword_value(rest, "redundancy").unwrap_or(0);
+                if volume.disperse > 0 && (volume.disperse < 3 || volume.redundancy == 0 || 2 * volume.redundancy >= volume.disperse) {
+                    return Err(format!("volume create: {}: failed: Invalid redundancy value\n", name));
+                }
+
+                if !volume.bricks.len().is_multiple_of(volume.set_size()) {
+                    return Err(format!(
+                        "volume create: {}: failed: number of bricks is not a multiple of {} count\n",
+                        name,
+                        if volume.disperse > 0 { "disperse" } else { "replica" }
+                    ));
+                }
+                if !rest.contains(&"force") {
+                    check_same_server(&volume.bricks, volume.set_size()).map_err(|e| format!("volume create: {}: failed: {}", name, e))?;
+                }
+                state.volumes.push(volume);
+                Ok(format!("volume create: {}: success\n", name))
+            }
+            ["volume", "start", name] => {
+                let volume = find_volume(&mut state, name)?;
+                if volume.state == VolumeState::Started {
+                    return Err(format!("volume start: {}: failed: Volume {} already started\n", name, name));
+                }
+                volume.state = VolumeState::Started;
+                Ok(format!("volume start: {}: success\n", name))
+            }
+            ["volume", "stop", name, ..] => {
+                let volume = find_volume(&mut state, name)?;
+                if volume.state != VolumeState::Started {
+                    return Err(format!("volume stop: {}: failed: Volume {} is not in the started state\n", name, name));
+                }
+                volume.state = VolumeState::Stopped;
+                Ok(format!("volume stop: {}: success\n", name))
+            }
+            ["volume", "delete", name] => {
+                let volume = find_volume(&mut state, name)?;
+                if volume.state == VolumeState::Started {
+                    return Err(format!("volume delete: {}: failed: Volume {} has been started\n", name, name));
+                }
+                state.volumes.retain(|v| v.name != *name);
+                Ok(format!("volume delete: {}: success\n", name))
+            }
+            ["volume", "add-brick", name, rest @ ..] => {
+                let bricks = brick_args(rest);
+                check_brick_hosts(&state, &bricks)?;
+                let volume = find_volume(&mut state, name)?;
+                if let Some(dup) = bricks.iter().find(|b| volume.bricks.contains(b)) {
+                    return Err(format!("volume add-brick: failed: Brick: {} not available\n", dup));
+                }
+                if !rest.contains(&"force") {
+                    check_same_server(&bricks, volume.set_size()).map_err(|e| format!("volume add-brick: failed: {}", e))?;
+                }
+                volume.bricks.extend(bricks);
+                Ok("volume add-brick: success\n".to_string())
+            }
+            ["volume", "remove-brick", name, rest @ ..] => {
+                let local = state.local_host.clone();
+                let bricks = brick_args(rest);
+                let action = rest.iter().rev().find(|a| !a.contains(":/") && **a != "--xml").copied().unwrap_or_default();
+                let replica = keyword_value(rest, "replica");
+                let as_xml = rest.last() == Some(&"--xml");
+                let volume = find_volume(&mut state, name)?;
+                if let Some(missing) = bricks.iter().find(|b| !volume.bricks.contains(b)) {
+                    return Err(format!("volume remove-brick {}: failed: Incorrect brick {} for volume {}\n", action, missing, name));
+                }
+                let in_progress = !volume.removing.is_empty() && volume.removing == bricks;
+                // Sin `replica N` solo se quitan subvolúmenes completos; con ella, los mismos bricks de cada uno.
+                let per_set = match replica {
+                    Some(n) if n >= volume.replica => {
+                        return Err(format!("volume remove-brick {}: failed: Changing the replica count of volume {} from {} to {} is not allowed\n", action, name, volume.replica, n));
+                    }
+                    Some(n) => (volume.replica - n) as usize,
+                    None => volume.set_size(),
+                };
+                let sets = volume.bricks.len() / volume.set_size();
+                if (replica.is_some() && bricks.len() != per_set * sets) || !bricks.len().is_multiple_of(per_set) {
+                    return Err(format!("volume remove-brick {}: failed: Remove brick incorrect brick count of {} for {} {}\n", action, bricks.len(), if volume.disperse > 0 { "disperse" } else { "replica" }, volume.set_size()));
+                }
+                if let (Some(n), "force") = (replica, action) {
+                    volume.bricks.retain(|b| !bricks.contains(b));
+                    volume.replica = n;
+                    volume.arbiter = 0;
+                    return Ok("volume remove-brick commit force: success\n".to_string());
+                }
+                match action {
+                    "start" => {
+                        if !volume.removing.is_empty() {
+                            return Err("volume remove-brick start: failed: An earlier remove-brick task exists for volume. Either commit it or stop it before starting a new task.\n".to_string());
+                        }
+                        volume.removing = bricks;
+                        volume.removal_polls = 0;
+                        Ok("volume remove-brick start: success\n".to_string())
+                    }
+                    "status" | "stop" if !in_progress => {
+                        let message = format!("Remove brick not started for volume {}", name);
+                        Err(if as_xml { xml_error(&message) } else { format!("volume remove-brick {}: failed: {}\n", action, message) })
+                    }
+                    "status" => {
+                        let output = remove_brick_status(volume, &local);
+                        // Como en el rebalanceo, cada consulta hace avanzar la migración.
+                        volume.removal_polls += 1;
+                        Ok(output)
+                    }
+                    "stop" => {
+                        volume.removing.clear();
+                        Ok("volume remove-brick stop: success\n".to_string())
+                    }
+                    "commit" if !in_progress || volume.removal_polls == 0 => {
+                        Err("volume remove-brick commit: failed: use 'force' option as migration is in progress\n".to_string())
+                    }
+                    "commit" | "force" => {
+                        volume.bricks.retain(|b| !bricks.contains(b));
+                        volume.removing.clear();
+                        Ok("volume remove-brick commit: success\n".to_string())
+                    }
+                    _ => Err(format!("volume remove-brick: unknown action {}\n", action)),
+                }
+            }
+            ["volume", "replace-brick", name, old, new, "commit", "force"] => {
+                check_brick_hosts(&state, &[new.to_string()])?;
+                if state.volumes.iter().any(|v| v.bricks.iter().any(|b| b == new)) {
+                    return Err(format!("volume replace-brick: failed: Brick: {} not available. Brick may be containing or be contained by an existing brick.\n", new));
+                }
+                let volume = find_volume(&mut state, name)?;
+                let Some(position) = volume.bricks.iter().position(|b| b == old) else {
+                    return Err(format!("volume replace-brick: failed: Incorrect source brick {} for volume {}\n", old, name));
+                };
+                volume.bricks[position] = new.to_string();
+                volume.offline.retain(|b| b != old);
+                volume.heal_pending.remove(*old);
+                mark_for_heal(volume, position);
+                Ok("volume replace-brick: success: replace-brick commit force operation successful\n".to_string())
+            }
+            ["volume", "reset-brick", name, brick, rest @ ..] => {
+                let volume = find_volume(&mut state, name)?;
+                let Some(position) = volume.bricks.iter().position(|b| b == brick) else {
+                    return Err(format!("volume reset-brick: failed: Incorrect source brick {} for volume {}\n", brick, name));
+                };
+                match rest {
+                    ["start"] => {
+                        volume.offline.push(brick.to_string());
+                        Ok("volume reset-brick: success: reset-brick start operation successful\n".to_string())
+                    }
+                    [same, "commit", "force"] if same == brick => {
+                        if !volume.offline.iter().any(|b| b == brick) {
+                            return Err("volume reset-brick: failed: Brick is online. Run reset-brick start first.\n".to_string());
+                        }
+                        volume.offline.retain(|b| b != brick);
+                        mark_for_heal(volume, position);
+                        Ok("volume reset-brick: success: reset-brick commit force operation successful\n".to_string())
+                    }
+                    _ => Err("volume reset-brick: failed: source and destination brick must be the same\n".to_string()),
+                }
+            }
+            ["volume", "set", "help"] => Ok(OPTIONS
+                .iter()
+                .map(|(name, default, description)| {
+                    format!("Option: {}\nDefault Value: {}\nDescription: {}\n\n", name, default, description)
+                })
+                .collect()),
+            ["volume", "quota", name, "list" | "list-objects", "--xml"] => {
+                let objects = args[3] == "list-objects";
+                let volume = find_volume(&mut state, name).map_err(|e| xml_error(e.trim()))?;
+                if !volume.options.contains_key("features.quota") {
+                    return Err(xml_error("Quota is disabled, please enable quota"));
+                }
+                Ok(quota_list_xml(volume, objects))
+            }
+            ["volume", "quota", name, action, rest @ ..] => {
+                let volume = find_volume(&mut state, name)?;
+                let enabled = volume.options.contains_key("features.quota");
+                match (*action, rest) {
+                    ("enable", []) if enabled => Err("volume quota : failed: Quota is already enabled\n".to_string()),
+                    ("enable", []) => {
+                        volume.options.insert("features.quota".to_string(), "on".to_string());
+                        volume.options.insert("features.inode-quota".to_string(), "on".to_string());
+                        Ok("volume quota : success\n".to_string())
+                    }
+                    _ if !enabled => Err("quota command failed : Quota is disabled, please enable quota\n".to_string()),
+                    ("disable", []) => {
+                        for key in ["features.quota", "features.inode-quota", "features.default-soft-limit"] {
+                            volume.options.remove(key);
+                        }
+                        volume.quotas.clear();
+                        Ok("volume quota : success\n".to_string())
+                    }
+                    ("limit-usage" | "limit-objects", [path, limit, soft @ ..]) => {
+                        let objects = *action == "limit-objects";
+                        let hard = if objects { limit.parse().ok() } else { crate::options::parse_size(limit) }
+                            .ok_or_else(|| format!("Please enter a correct value for {}\n", limit))?;
+                        let soft = match soft {
+                            [] => None,
+                            [percent] => Some(percent.trim_end_matches('%').parse().map_err(|_| "Invalid soft-limit\n".to_string())?),
+                            _ => return Err("Usage: volume quota limit-usage\n".to_string()),
+                        };
+                        volume.quotas.retain(|q| !(q.path == *path && q.objects == objects));
+                        volume.quotas.push(FakeQuota { path: path.to_string(), objects, hard, soft, used: 0 });
+                        Ok("volume quota : success\n".to_string())
+                    }
+                    ("remove" | "remove-objects", [path]) => {
+                        let objects = *action == "remove-objects";
+                        let before = volume.quotas.len();
+                        volume.quotas.retain(|q| !(q.path == *path && q.objects == objects));
+                        if volume.quotas.len() == before {
+                            return Err(format!("quota command failed : {} not in limit list\n", path));
+                        }
+                        Ok("volume quota : success\n".to_string())
+                    }
+                    ("default-soft-limit", [percent]) => {
+                        volume.options.insert("features.default-soft-limit".to_string(), percent.to_string());
+                        Ok("volume quota : success\n".to_string())
+                    }
+                    _ => Err(format!("unrecognized command: gluster {}\n", args.join(" "))),
+                }
+            }
+            ["snapshot", "create", snap, name, rest @ ..] => {
+                let volume = find_volume(&mut state, name)?.clone();
+                if volume.state != VolumeState::Started {
+                    return Err(format!("snapshot create: failed: volume {} is not started\n", name));
+                }
+                let thin = volume.bricks.iter().all(|brick| {
+                    let path = brick.split_once(':').map_or(brick.as_str(), |(_, p)| p);
+                    filesystem_of(&state.filesystems, path).and_then(|f| f.lv_attr.as_deref()).is_some_and(|a| a.starts_with('V'))
+                });
+                if !thin {
+                    return Err(format!(
+                        "snapshot create: failed: Snapshot is supported only for thin provisioned LV. Ensure that all bricks of {} are thinly provisioned LV.\n",
+                        name
+                    ));
+                }
+                let snap_name = if rest.contains(&"no-timestamp") {
+                    snap.to_string()
+                } else {
+                    format!("{}_GMT-2026.10.18-09.15.02", snap)
+                };
+                let description = rest.iter().position(|a| *a == "description").and_then(|i| rest.get(i + 1)).map(|d| d.to_string());
+                state.snapshots.push(FakeSnapshot { name: snap_name.clone(), volume: name.to_string(), description, active: false });
+                Ok(format!("snapshot create: success: Snap {} created successfully\n", snap_name))
+            }
+            ["snapshot", "info", rest @ .., "--xml"] => {
+                let (snapshots, origin): (Vec<&FakeSnapshot>, bool) = match rest {
+                    [] => (state.snapshots.iter().collect(), true),
+                    ["volume", name] => (state.snapshots.iter().filter(|s| s.volume == *name).collect(), false),
+                    [snap] => {
+                        let found: Vec<&FakeSnapshot> = state.snapshots.iter().filter(|s| s.name == *snap).collect();
+                        if found.is_empty() {
+                            return Err(xml_error(&format!("Snapshot ({}) does not exist", snap)));
+                        }
+                        (found, true)
+                    }
+                    _ => return Err(xml_error("Invalid Syntax")),
+                };
+                Ok(snapshot_info_xml(&snapshots, origin))
+            }
+            ["snapshot", "status", snap] => {
+                let snapshot = find_snapshot(&mut state, snap)?;
+                Ok(format!("\nSnap Name : {}\n\n\tBrick Running : {}\n", snapshot.name, if snapshot.active { "Yes" } else { "No" }))
+            }
+            ["snapshot", "activate" | "deactivate", snap] => {
+                let activate = args[1] == "activate";
+                let snapshot = find_snapshot(&mut state, snap)?;
+                if snapshot.active == activate {
+                    return Err(format!("snapshot {}: failed: Snapshot {} is already {}d.\n", args[1], snap, args[1]));
+                }
+                snapshot.active = activate;
+                Ok(format!("Snapshot {} : {} successful\n", args[1], snap))
+            }
+            ["snapshot", "restore", snap] => {
+                let volume = find_snapshot(&mut state, snap)?.volume.clone();
+                if find_volume(&mut state, &volume)?.state == VolumeState::Started {
+                    return Err(format!(
+                        "snapshot restore: failed: Volume ({}) has been started. Volume needs to be stopped before restoring a snapshot.\n",
+                        volume
+                    ));
+                }
+                state.snapshots.retain(|s| s.name != *snap);
+                Ok(format!("Snapshot restore: {}: Snap restored successfully\n", snap))
+            }
+            ["snapshot", "clone", clone, snap] => {
+                let snapshot = find_snapshot(&mut state, snap)?.clone();
+                if !snapshot.active {
+                    return Err(format!("snapshot clone: failed: Snapshot {} is not activated\n", snap));
+                }
+                let origin = find_volume(&mut state, &snapshot.volume)?.clone();
+                let bricks = origin
+                    .bricks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, brick)| format!("{}:/run/gluster/snaps/{}/brick{}", crate::placement::brick_host(brick), clone, i + 1))
+                    .collect();
+                state.volumes.push(FakeVolume::new(clone, bricks, VolumeState::Created));
+                Ok(format!("snapshot clone: success: Clone {} created successfully\n", clone))
+            }
+            ["snapshot", "delete", snap] => {
+                find_snapshot(&mut state, snap)?;
+                state.snapshots.retain(|s| s.name != *snap);
+                Ok(format!("snapshot delete: {}: snap removed successfully\n", snap))
+            }
+            ["volume", "heal", name, "info", rest @ .., "--xml"] => {
+                let volume = find_volume(&mut state, name).map_err(|e| xml_error(e.trim()))?;
+                if volume.replica < 2 && volume.disperse == 0 {
+                    return Err(xml_error(&format!("Volume {} is not of type replicate/disperse", name)));
+                }
+                let output = heal_info_xml(volume, rest);
+                if volume.healing && volume.options.get("cluster.self-heal-daemon").is_none_or(|v| v != "off") {
+                    for (brick, entries) in volume.heal_pending.iter_mut() {
+                        if !volume.offline.contains(brick) && !entries.is_empty() {
+                            entries.remove(0);
+                        }
+                    }
+                }
+                Ok(output)
+            }
+            ["volume", "heal", name, rest @ ..] => {
+                let volume = find_volume(&mut state, name)?;
+                if volume.replica < 2 && volume.disperse == 0 {
+                    return Err(format!("Volume {} is not of type replicate/disperse\nVolume heal failed.\n", name));
+                }
+                let daemon_off = volume.options.get("cluster.self-heal-daemon").is_some_and(|v| v == "off");
+                match rest {
+                    ["split-brain", "bigger-file" | "latest-mtime", file] => {
+                        if volume.split_brain.remove(*file).is_none() {
+                            return Err(format!("Lookup failed on {}:No such file or directory\nVolume heal failed.\n", file));
+                        }
+                        Ok(format!("Healed {}.\n", file))
+                    }
+                    ["split-brain", "source-brick", brick, file] => {
+                        if !volume.split_brain.get(*file).is_some_and(|b| b.iter().any(|b| b == brick)) {
+                            return Err(format!("Lookup failed on {}:No such file or directory\nVolume heal failed.\n", file));
+                        }
+                        volume.split_brain.remove(*file);
+                        Ok(format!("Healed {}.\n", file))
+                    }
+                    ["split-brain", "source-brick", brick] => {
+                        let healed: Vec<String> =
+                            volume.split_brain.iter().filter(|(_, b)| b.iter().any(|b| b == brick)).map(|(f, _)| f.clone()).collect();
+                        volume.split_brain.retain(|f, _| !healed.contains(f));
+                        Ok(healed.iter().map(|f| format!("Healed {}\n", f)).collect::<String>() + &format!("Number of healed entries: {}\n", healed.len()))
+                    }
+                    [] | ["full"] if daemon_off => Err("Self-heal-daemon is disabled. Heal will not be triggered on volume\n".to_string()),
+                    [] | ["full"] => {
+                        volume.healing = true;
+                        let kind = if rest.is_empty() { "index" } else { "full" };
+                        Ok(format!("Launching heal operation to perform {} self heal on volume {} has been successful\n", kind, name))
+                    }
+                    ["enable" | "disable"] => {
+                        let value = if rest[0] == "enable" { "on" } else { "off" };
+                        volume.options.insert("cluster.self-heal-daemon".to_string(), value.to_string());
+                        Ok(format!("{} heal on volume {} has been successful\n", if value == "on" { "Enable" } else { "Disable" }, name))
+                    }
+                    _ => Err(format!("unrecognized command: gluster {}\n", args.join(" "))),
+                }
+            }
+            ["volume", "rebalance", name, "status", rest @ ..] => {
+                let local = state.local_host.clone();
+                let volume = find_volume(&mut state, name)?;
+                let Some(rebalance) = volume.rebalance.clone() else {
+                    let message = format!("Rebalance not started for volume {}.", name);
+                    return Err(if rest.is_empty() { format!("volume rebalance: {}: failed: {}\n", name, message) } else { xml_error(&message) });
+                };
+                let output = rebalance_status(volume, &rebalance, &local);
+                // Cada consulta de estado hace avanzar el rebalanceo.
+                if let Some(rebalance) = volume.rebalance.as_mut() {
+                    rebalance.polls += 1;
+                }
+                Ok(output)
+            }
+            ["volume", "rebalance", name, rest @ ..] => {
+                let volume = find_volume(&mut state, name)?;
+                let running = volume.rebalance.as_ref().is_some_and(FakeRebalance::running);
+                match rest {
+                    ["stop"] if !running => Err(format!("volume rebalance: {}: failed: Rebalance not started for volume {}.\n", name, name)),
+                    ["stop"] => {
+                        if let Some(rebalance) = volume.rebalance.as_mut() {
+                            rebalance.stopped = true;
+                        }
+                        Ok(format!("volume rebalance: {}: success: Rebalance stopped\n", name))
+                    }
+                    ["start"] | ["start", "force"] | ["fix-layout", "start"] => {
+                        if volume.state != VolumeState::Started {
+                            return Err(format!("volume rebalance: {}: failed: Volume {} needs to be started to perform rebalance\n", name, name));
+                        }
+                        if volume.bricks.len() <= volume.set_size() {
+                            return Err(format!(
+                                "volume rebalance: {}: failed: Volume {} is not a distribute volume or contains only 1 brick.\nNot performing rebalance\n",
+                                name, name
+                            ));
+                        }
+                        if running {
+                            return Err(format!("volume rebalance: {}: failed: Rebalance on {} is already started\n", name, name));
+                        }
+                        volume.rebalance = Some(FakeRebalance { fix_layout: rest[0] == "fix-layout", polls: 0, stopped: false });
+                        Ok(format!("volume rebalance: {}: success: Rebalance on {} has been started successfully.\n", name, name))
+                    }
+                    _ => Err(format!("unrecognized command: gluster {}\n", args.join(" "))),
+                }
+            }
+            ["volume", "get", name, "all", "--xml"] => {
+                let volume = find_volume(&mut state, name).map_err(|e| xml_error(e.trim()))?;
+                let mut body = format!("  <volGetopts>\n    <count>{}</count>\n", OPTIONS.len());
+                for (option, default, _) in OPTIONS {
+                    let value = volume.options.get(*option).map(String::as_str).unwrap_or(default);
+                    body.push_str(&format!(
+                        "    <Opt>\n      <Option>{}</Option>\n      <Value>{}</Value>\n    </Opt>\n",
+                        option,
+                        xml::escape(value)
+                    ));
+                }
+                body.push_str("  </volGetopts>\n");
+                Ok(xml_envelope(&body))
+            }
+            ["volume", "get", name, option] => {
+                let volume = find_volume(&mut state, name)?;
+                let value = volume.options.get(*option).cloned().unwrap_or_else(|| "off".to_string());
+                Ok(format!("Option                                  Value\n------                                  -----\n{:<40}{}\n", option, value))
+            }
+            ["volume", "set", name, "group", group] => {
+                let path = format!("{}/{}", crate::profiles::GROUPS_DIR, group);
+                let text = state
+                    .files
+                    .get(&path)
+                    .cloned()
+                    .ok_or_else(|| format!("volume set: failed: Unable to open file {}. Error: No such file or directory\n", path))?;
+                let volume = find_volume(&mut state, name)?;
+                for (option, value) in crate::profiles::parse(&text) {
+                    if !OPTIONS.iter().any(|(known, _, _)| *known == option) {
+                        return Err(format!("volume set: failed: option : {} does not exist\n", option));
+                    }
+                    volume.options.insert(option, value);
+                }
+                Ok("volume set: success\n".to_string())
+            }
+            ["volume", "set", name, option, value] => {
+                let volume = find_volume(&mut state, name)?;
+                if !OPTIONS.iter().any(|(known, _, _)| known == option) {
+                    return Err(format!("volume set: failed: option : {} does not exist\n", option));
+                }
+                volume.options.insert(option.to_string(), value.to_string());
+                Ok("volume set: success\n".to_string())
+            }
+            ["volume", "reset", name, option] => {
+                let volume = find_volume(&mut state, name)?;
+                volume.options.remove(*option);
+                Ok("volume reset: success: reset volume successful\n".to_string())
+            }
+            _ => Err(format!("unrecognized command: gluster {}\n", args.join(" "))),
+        }
+    }
+
+    fn system(&self, program: &str, args: &[&str]) -> Result<String, String> {
+        let mut state = self.state.borrow_mut();
+
+        match (program, args) {
+            ("mount", []) => Ok(state
+                .mounts
+                .iter()
+                .map(|(source, target)| format!("{} on {} type fuse.glusterfs (rw,relatime)\n", source, target))
+                .collect()),
+            ("mount", ["-t", "glusterfs", source, target]) => {
+                let (_, volume) = source.split_once(":/").ok_or("mount: bad source\n")?;
+                let started = state
+                    .volumes
+                    .iter()
+                    .any(|v| v.name == volume && v.state == VolumeState::Started);
+                if !started || !state.dirs.contains(*target) {
+                    return Err("Mount failed. Check the log file for more details.\n".to_string());
+                }
+                state.mounts.push((source.to_string(), target.to_string()));
+                Ok(String::new())
+            }
+            ("umount", [target]) => {
+                let before = state.mounts.len();
+                state.mounts.retain(|(_, t)| t != target);
+                if state.mounts.len() == before {
+                    return Err(format!("umount: {}: not mounted.\n", target));
+                }
+                Ok(String::new())
+            }
+            ("chown", [_, path]) | ("chmod", [_, path]) => {
+                if state.dirs.contains(*path) {
+                    Ok(String::new())
+                } else {
+                    Err(format!("{}: cannot access '{}': No such file or directory\n", program, path))
+                }
+            }
+            ("stat", ["-c", "%s %Y", path]) => match state.stats.get(*path) {
+                Some((size, mtime)) => Ok(format!("{} {}\n", size, mtime)),
+                None => Err(format!("stat: cannot statx '{}': No such file or directory\n", path)),
+            },
+            ("ssh", ["-o", "BatchMode=yes", "-o", "ConnectTimeout=5", host, remote]) => {
+                let path = remote.strip_prefix("stat -c '%s %Y' ").unwrap_or(remote);
+                let path = path.trim_matches('\'').replace("'\\''", "'");
+                match state.stats.get(&format!("{}:{}", host, path)) {
+                    Some((size, mtime)) => Ok(format!("{} {}\n", size, mtime)),
+                    None => Err(format!("ssh: connect to host {} port 22: Connection refused\n", host)),
+                }
+            }
+            ("findmnt", ["-n", "-o", "SOURCE", "--target", path]) => {
+                Ok(format!("{}\n", filesystem_of(&state.filesystems, path).map_or("/dev/sda1", |f| f.source.as_str())))
+            }
+            ("lvs", ["--noheadings", "-o", "lv_attr", device]) => {
+                match state.filesystems.iter().find(|f| f.source == *device).and_then(|f| f.lv_attr.as_ref()) {
+                    Some(attr) => Ok(format!("  {}\n", attr)),
+                    None => Err(format!("  Failed to find logical volume \"{}\"\n", device)),
+                }
+            }
+            _ => Err(format!("{}: command not found\n", program)),
+        }
+    }
+}
+
+/// Montaje más específico que contiene `path`.
+fn filesystem_of<'a>(filesystems: &'a [FakeFilesystem], path: &str) -> Option<&'a FakeFilesystem> {
+    filesystems
+        .iter()
+        .filter(|f| path == f.target || path.starts_with(&format!("{}/", f.target)))
+        .max_by_key(|f| f.target.len())
+}
+
+impl FakeBackend {
+    fn respond(&self, program: &str, args: &[&str], sudo: bool) -> CommandOutput {
+        let line = format!("{} {}", program, args.join(" "));
+        let failure = self.state.borrow().failures.iter().find(|(prefix, _)| line.starts_with(*prefix)).map(|(_, e)| e.clone());
+        let result = match failure {
+            _ if sudo && self.state.borrow().sudo_denied.contains(program) => Err("sudo: a password is required\n".to_string()),
+            Some(stderr) => Err(stderr),
+            None if program == "gluster" => self.gluster(args),
+            None => self.system(program, args),
+        };
+
+        match result {
+            Ok(stdout) => CommandOutput {
+                success: true,
+                code: Some(0),
+                stdout,
+                stderr: String::new(),
+            },
+            Err(stderr) => CommandOutput {
+                success: false,
+                code: Some(1),
+                stdout: String::new(),
+                stderr,
+            },
+        }
+    }
+}
+
+impl GlusterBackend for FakeBackend {
+    fn exec(&self, program: &str, args: &[&str], privileged: bool) -> io::Result<CommandOutput> {
+        if privileged {
+            self.state
+                .borrow_mut()
+                .commands
+                .push(format!("{} {}", program, args.join(" ")));
+        }
+        Ok(self.respond(program, args, privileged))
+    }
+
+    /// Las consultas con sudo no se anotan en `commands`: no cambian nada.
+    fn exec_query(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        Ok(self.respond(program, args, true))
+    }
+
+    fn read_file(&self, path: &str) -> io::Result<String> {
+        self.state
+            .borrow()
+            .files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn write_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        self.state.borrow_mut().files.insert(path.to_string(), contents.to_string());
+        Ok(())
+    }
+
+    fn append_file(&self, path: &str, contents: &str) -> io::Result<()> {
+        self.state
+            .borrow_mut()
+            .files
+            .entry(path.to_string())
+            .or_default()
+            .push_str(contents);
+        Ok(())
+    }
+
+    fn create_dir_all(&self, path: &str) -> io::Result<()> {
+        self.state.borrow_mut().dirs.insert(path.to_string());
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &str) -> io::Result<()> {
+        let mut state = self.state.borrow_mut();
+        if !state.dirs.contains(path) {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        let prefix = format!("{}/", path);
+        state.dirs.retain(|d| d != path && !d.starts_with(&prefix));
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &str) -> io::Result<()> {
+        match self.state.borrow_mut().files.remove(path) {
+            Some(_) => Ok(()),
+            None => Err(io::Error::from(io::ErrorKind::NotFound)),
+        }
+    }
+
+    fn list_dirs(&self, path: &str) -> io::Result<Vec<String>> {
+        let state = self.state.borrow();
+        if !state.dirs.contains(path) {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        let prefix = format!("{}/", path);
+        Ok(state
+            .dirs
+            .iter()
+            .filter_map(|d| d.strip_prefix(&prefix))
+            .filter(|rest| !rest.contains('/'))
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        let state = self.state.borrow();
+        let prefix = format!("{}/", path);
+        let files: Vec<String> = state
+            .files
+            .keys()
+            .filter_map(|f| f.strip_prefix(&prefix))
+            .filter(|rest| !rest.contains('/'))
+            .map(str::to_string)
+            .collect();
+        if files.is_empty() && !state.dirs.contains(path) {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        Ok(files)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let state = self.state.borrow();
+        state.dirs.contains(path) || state.files.contains_key(path)
+    }
+
+    fn user_exists(&self, name: &str) -> bool {
+        self.state.borrow().users.iter().any(|u| u == name)
+    }
+
+    fn current_user(&self) -> String {
+        "root".to_string()
+    }
+
+    fn local_hostname(&self) -> String {
+        self.state.borrow().local_host.clone()
+    }
+}
+
+fn find_volume<'a>(state: &'a mut FakeState, name: &str) -> Result<&'a mut FakeVolume, String> {
+    state
+        .volumes
+        .iter_mut()
+        .find(|v| v.name == name)
+        .ok_or_else(|| format!("Volume {} does not exist\n", name))
+}
+
+/// Bricks de una línea de comando, sin las palabras clave finales (`force`, ...).
+fn brick_args(args: &[&str]) -> Vec<String> {
+    args.iter()
+        .filter(|a| a.contains(":/"))
+        .map(|a| a.to_string())
+        .collect()
+}
+
+/// Como gluster, rechaza sin `force` los conjuntos con dos bricks en el mismo servidor.
+fn check_same_server(bricks: &[String], set_size: usize) -> Result<(), String> {
+    if set_size == 1 {
+        return Ok(());
+    }
+    for set in bricks.chunks(set_size) {
+        let hosts: Vec<&str> = set.iter().map(|b| crate::placement::brick_host(b)).collect();
+        if hosts.iter().enumerate().any(|(i, h)| hosts[..i].contains(h)) {
+            return Err("Multiple bricks of a replicate volume are present on the same server. This setup is not \
+                 optimal. Use 'force' at the end of the command if you want to override this behavior.\n"
+                .to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Tras sustituir un brick vacío, el resto de su conjunto marca la raíz como pendiente y el
+/// demonio de self-heal empieza a copiarla.
+fn mark_for_heal(volume: &mut FakeVolume, position: usize) {
+    let size = volume.set_size();
+    let set = position / size * size;
+    let siblings: Vec<String> = (set..set + size).filter(|&i| i != position).map(|i| volume.bricks[i].clone()).collect();
+    for sibling in siblings {
+        volume.heal_pending.entry(sibling).or_default().push("/".to_string());
+    }
+    volume.healing = true;
+}
+
+/// Valor numérico que sigue a una palabra clave (`replica 3`, `redundancy 1`, ...).
+fn keyword_value(args: &[&str], keyword: &str) -> Option<u32> {
+    let pos = args.iter().position(|a| *a == keyword)?;
+    args.get(pos + 1)?.parse().ok()
+}
+
+fn check_brick_hosts(state: &FakeState, bricks: &[String]) -> Result<(), String> {
+    for brick in bricks {
+        let host = brick.split(':').next().unwrap_or_default();
+        let known = host == state.local_host || state.peers.iter().any(|p| p.hostname == host && p.connected);
+        if !known {
+            return Err(format!("Host {} is not in 'Peer in Cluster' state\n", host));
+        }
+    }
+    Ok(())
+}
+
+fn peer_status_xml(peers: &[FakePeer], include_local: bool) -> String {
+    let mut body = String::from("  <peerStatus>\n");
+    for (i, peer) in peers.iter().enumerate() {
+        body.push_str(&format!(
+            "    <peer>\n      <uuid>00000000-0000-0000-0000-{:012}</uuid>\n      <hostname>{1}</hostname>\n      <hostnames><hostname>{1}</hostname></hostnames>\n      <connected>{2}</connected>\n      <stateStr>{3}</stateStr>\n    </peer>\n",
+            i + 2,
+            xml::escape(&peer.hostname),
+            u8::from(peer.connected),
+            xml::escape(&peer.state)
+        ));
+    }
+    if include_local {
+        body.push_str(
+            "    <peer>\n      <uuid>00000000-0000-0000-0000-000000000001</uuid>\n      <hostname>localhost</hostname>\n      <connected>1</connected>\n    </peer>\n",
+        );
+    }
+    body.push_str("  </peerStatus>\n");
+    xml_envelope(&body)
+}
+
+fn volume_status_xml<'a>(volumes: impl Iterator<Item = &'a FakeVolume>, extra: &[&str]) -> String {
+    let mut body = String::from("  <volStatus>\n    <volumes>\n");
+
+    for volume in volumes {
+        body.push_str(&format!("      <volume>\n        <volName>{}</volName>\n", xml::escape(&volume.name)));
+        for (i, brick) in volume.bricks.iter().enumerate() {
+            let (host, path) = brick.split_once(':').unwrap_or_default();
+            let online = !volume.offline.contains(brick);
+            let (status, port, pid) = if online {
+                (1, (49152 + i).to_string(), (1000 + i).to_string())
+            } else {
+                (0, "N/A".to_string(), "-1".to_string())
+            };
+            body.push_str(&format!(
+                "        <node>\n          <hostname>{}</hostname>\n          <path>{}</path>\n          <peerid>u</peerid>\n          <status>{}</status>\n          <port>{}</port>\n          <ports><tcp>{}</tcp><rdma>N/A</rdma></ports>\n          <pid>{}</pid>\n",
+                host, path, status, port, port, pid
+            ));
+            match extra {
+                ["detail"] => body.push_str(
+                    "          <sizeTotal>10737418240</sizeTotal><sizeFree>5368709120</sizeFree><inodesTotal>5242880</inodesTotal><inodesFree>5242000</inodesFree>\n",
+                ),
+                ["clients"] if online => body.push_str("          <clientsStatus><clientCount>1</clientCount></clientsStatus>\n"),
+                _ => {}
+            }
+            body.push_str("        </node>\n");
+        }
+        if extra.is_empty() {
+            body.push_str(
+                "        <node>\n          <hostname>Self-heal Daemon</hostname>\n          <path>localhost</path>\n          <status>1</status>\n          <pid>900</pid>\n        </node>\n",
+            );
+        }
+        body.push_str("      </volume>\n");
+    }
+
+    body.push_str("    </volumes>\n  </volStatus>\n");
+    xml_envelope(&body)
+}
+
+/// Respuesta XML de error como la que devuelve gluster con `--xml`.
+fn xml_error(message: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<cliOutput><opRet>-1</opRet><opErrno>30800</opErrno><opErrstr>{}</opErrstr></cliOutput>\n",
+        xml::escape(message)
+    )
+}
+
+fn xml_envelope(body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<cliOutput>\n  <opRet>0</opRet>\n  <opErrno>0</opErrno>\n  <opErrstr/>\n{}</cliOutput>\n",
+        body
+    )
+}
+
+fn find_snapshot<'a>(state: &'a mut FakeState, name: &str) -> Result<&'a mut FakeSnapshot, String> {
+    state
+        .snapshots
+        .iter_mut()
+        .find(|s| s.name == name)
+        .ok_or_else(|| format!("Snapshot ({}) does not exist\n", name))
+}
+
+/// `snapshot info`; con `info volume NOMBRE` gluster no repite el volumen de origen.
+fn snapshot_info_xml(snapshots: &[&FakeSnapshot], with_origin: bool) -> String {
+    let mut body = format!("  <snapInfo>\n    <count>{}</count>\n    <snapshots>\n", snapshots.len());
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let description = snapshot
+            .description
+            .as_ref()
+            .map(|d| format!("<description>{}</description>", xml::escape(d)))
+            .unwrap_or_default();
+        let origin = if with_origin {
+            format!("<originVolume><name>{}</name></originVolume>", snapshot.volume)
+        } else {
+            String::new()
+        };
+        body.push_str(&format!(
+            "      <snapshot><name>{}</name><uuid>00000000-0000-0000-0001-{:012}</uuid>{}<createTime>2026-10-18 09:15:02</createTime><volCount>1</volCount><snapVolume><name>{:032x}</name><status>{}</status>{}</snapVolume></snapshot>\n",
+            snapshot.name,
+            i + 1,
+            description,
+            i + 1,
+            if snapshot.active { "Started" } else { "Stopped" },
+            origin,
+        ));
+    }
+    body.push_str("    </snapshots>\n  </snapInfo>\n");
+    xml_envelope(&body)
+}
+
+/// `heal info` (con los ficheros), `heal info split-brain` o `heal info summary` (solo contadores).
+fn heal_info_xml(volume: &FakeVolume, mode: &[&str]) -> String {
+    let summary = mode == ["summary"];
+    let mut body = String::from("  <healInfo>\n    <bricks>\n");
+    for brick in &volume.bricks {
+        body.push_str(&format!("      <brick hostUuid=\"u\">\n        <name>{}</name>\n", xml::escape(brick)));
+        let split_brain: Vec<String> = volume
+            .split_brain
+            .iter()
+            .filter(|(_, bricks)| bricks.contains(brick))
+            .map(|(path, _)| path.clone())
+            .collect();
+        let entries = if mode == ["split-brain"] {
+            // gluster muestra los gfid sin ruta conocida como `<gfid:UUID>`.
+            split_brain.iter().map(|p| if p.starts_with("gfid:") { format!("<{}>", p) } else { p.clone() }).collect()
+        } else {
+            volume.heal_pending.get(brick).cloned().unwrap_or_default()
+        };
+        if volume.offline.contains(brick) {
+            let counts = if summary {
+                "<totalNumberOfEntries>-</totalNumberOfEntries><numberOfEntriesInHealPending>-</numberOfEntriesInHealPending><numberOfEntriesInSplitBrain>-</numberOfEntriesInSplitBrain><numberOfEntriesPossiblyHealing>-</numberOfEntriesPossiblyHealing>"
+            } else {
+                "<numberOfEntries>-</numberOfEntries>"
+            };
+            body.push_str(&format!("        <status>Transport endpoint is not connected</status>{}\n      </brick>\n", counts));
+            continue;
+        }
+        if summary {
+            body.push_str(&format!(
+                "        <status>Connected</status>\n        <totalNumberOfEntries>{}</totalNumberOfEntries>\n        <numberOfEntriesInHealPending>{}</numberOfEntriesInHealPending>\n        <numberOfEntriesInSplitBrain>{}</numberOfEntriesInSplitBrain>\n        <numberOfEntriesPossiblyHealing>0</numberOfEntriesPossiblyHealing>\n",
+                entries.len() + split_brain.len(),
+                entries.len(),
+                split_brain.len()
+            ));
+        } else {
+            for (i, entry) in entries.iter().enumerate() {
+                body.push_str(&format!("        <file gfid=\"00000000-0000-0000-0002-{:012}\">{}</file>\n", i + 1, xml::escape(entry)));
+            }
+            body.push_str(&format!("        <status>Connected</status>\n        <numberOfEntries>{}</numberOfEntries>\n", entries.len()));
+        }
+        body.push_str("      </brick>\n");
+    }
+    body.push_str("    </bricks>\n  </healInfo>\n");
+    xml_envelope(&body)
+}
+
+/// `rebalance status`, un nodo por cada host con bricks (`localhost` para este).
+fn rebalance_status(volume: &FakeVolume, rebalance: &FakeRebalance, local: &str) -> String {
+    let mut hosts: Vec<&str> = vec![];
+    for brick in &volume.bricks {
+        let host = crate::placement::brick_host(brick);
+        if !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+    let running = rebalance.running();
+    let status = match (rebalance.fix_layout, rebalance.stopped, running) {
+        (_, true, _) => "stopped",
+        (true, _, true) => "fix-layout in progress",
+        (true, _, false) => "fix-layout completed",
+        (false, _, true) => "in progress",
+        (false, _, false) => "completed",
+    };
+
+    let mut text = String::new();
+    if rebalance.fix_layout {
+        text.push_str("Node                                    status           run time in h:m:s\n");
+        text.push_str("---------                          -----------           ------------\n");
+        for host in &hosts {
+            let node = if *host == local { "localhost" } else { host };
+            text.push_str(&format!("{:>9}  {:>24}  {:>14}\n", node, status, "0:0:12"));
+        }
+    } else {
+        let (scanned, moved, runtime) = if running { (60, 20, "0:00:45") } else { (120, 40, "0:01:35") };
+        text.push_str(REBALANCE_HEADER);
+        for host in &hosts {
+            let node = if *host == local { "localhost" } else { host };
+            text.push_str(&rebalance_row(node, moved, scanned, 0, 2, status, runtime));
+        }
+        if running {
+            text.push_str("Estimated time left for rebalance to complete :        0:01:30\n");
+        }
+    }
+    text + &format!("volume rebalance: {}: success\n", volume.name)
+}
+
+const REBALANCE_HEADER: &str = "\
+                                    Node Rebalanced-files          size       scanned      failures       skipped               status  run time in h:m:s
+                               ---------      -----------   -----------   -----------   -----------   -----------         ------------     --------------
+";
+
+/// Fila de la tabla; cada fichero movido ocupa 1 MB.
+fn rebalance_row(node: &str, moved: u64, scanned: u64, failures: u64, skipped: u64, status: &str, runtime: &str) -> String {
+    let size = format!("{}.0MB", moved);
+    format!("{:>40} {:>16} {:>13} {:>13} {:>13} {:>13} {:>20} {:>18}\n", node, moved, size, scanned, failures, skipped, status, runtime)
+}
+
+/// `remove-brick status`: una fila por cada nodo con bricks que se eliminan.
+fn remove_brick_status(volume: &FakeVolume, local: &str) -> String {
+    let mut hosts: Vec<&str> = volume.removing.iter().map(|b| crate::placement::brick_host(b)).collect();
+    hosts.dedup();
+    let running = volume.removal_polls == 0;
+    let status = match (running, volume.removal_failures) {
+        (true, _) => "in progress",
+        (false, 0) => "completed",
+        (false, _) => "failed",
+    };
+
+    let mut text = String::from(REBALANCE_HEADER);
+    for host in hosts {
+        let node = if host == local { "localhost" } else { host };
+        text.push_str(&rebalance_row(node, 10, 30, volume.removal_failures, 0, status, "0:00:20"));
+    }
+    if running {
+        text.push_str("Estimated time left for rebalance to complete :        0:00:40\n");
+    }
+    text
+}
+
+fn quota_list_xml(volume: &FakeVolume, objects: bool) -> String {
+    let default_soft: u32 = volume
+        .options
+        .get("features.default-soft-limit")
+        .and_then(|p| p.trim_end_matches('%').parse().ok())
+        .unwrap_or(80);
+    let limits: Vec<&FakeQuota> = volume.quotas.iter().filter(|q| q.objects == objects).collect();
+    if limits.is_empty() {
+        return xml_envelope("");
+    }
+
+    let mut body = String::from("  <volQuota>\n");
+    for quota in limits {
+        let soft = quota.soft.unwrap_or(default_soft);
+        let soft_value = quota.hard * u64::from(soft) / 100;
+        let usage = if objects {
+            format!("<file_count>{}</file_count><dir_count>0</dir_count><available>{}</available>", quota.used, quota.hard.saturating_sub(quota.used))
+        } else {
+            format!("<used_space>{}</used_space><avail_space>{}</avail_space>", quota.used, quota.hard.saturating_sub(quota.used))
+        };
+        body.push_str(&format!(
+            "    <limit><path>{}</path><hard_limit>{}</hard_limit><soft_limit_percent>{}%</soft_limit_percent><soft_limit_value>{}</soft_limit_value>{}<sl_exceeded>{}</sl_exceeded><hl_exceeded>{}</hl_exceeded></limit>\n",
+            xml::escape(&quota.path),
+            quota.hard,
+            soft,
+            soft_value,
+            usage,
+            if quota.used > soft_value { "Yes" } else { "No" },
+            if quota.used > quota.hard { "Yes" } else { "No" },
+        ));
+    }
+    body.push_str("  </volQuota>\n");
+    xml_envelope(&body)
+}
+
+fn volume_info_xml<'a>(volumes: impl Iterator<Item = &'a FakeVolume>) -> String {
+    let mut body = String::from("  <volInfo>\n    <volumes>\n");
+    let mut count = 0;
+
+    for (v, volume) in volumes.enumerate() {
+        count += 1;
+        let (status, status_str) = match volume.state {
+            VolumeState::Created => (0, "Created"),
+            VolumeState::Started => (1, "Started"),
+            VolumeState::Stopped => (2, "Stopped"),
+        };
+        body.push_str(&format!(
+            "      <volume>\n        <name>{}</name>\n        <id>00000000-0000-0000-0000-{:012}</id>\n        <status>{}</status>\n        <statusStr>{}</statusStr>\n        <brickCount>{}</brickCount>\n        <distCount>{}</distCount>\n        <replicaCount>{}</replicaCount>\n        <arbiterCount>{}</arbiterCount>\n        <disperseCount>{}</disperseCount>\n        <redundancyCount>{}</redundancyCount>\n        <typeStr>{}</typeStr>\n        <transport>0</transport>\n        <bricks>\n",
+            xml::escape(&volume.name),
+            v + 1,
+            status,
+            status_str,
+            volume.bricks.len(),
+            volume.set_size(),
+            volume.replica,
+            volume.arbiter,
+            volume.disperse,
+            volume.redundancy,
+            volume.type_str(),
+        ));
+        for (i, brick) in volume.bricks.iter().enumerate() {
+            // En `replica N arbiter 1` el último brick de cada conjunto es el árbitro
+            let arbiter = volume.arbiter > 0 && (i + 1) % volume.replica as usize == 0;
+            body.push_str(&format!(
+                "          <brick uuid=\"u\">{0}<name>{0}</name><hostUuid>u</hostUuid><isArbiter>{1}</isArbiter></brick>\n",
+                xml::escape(brick),
+                u8::from(arbiter)
+            ));
+        }
+        body.push_str(&format!("        </bricks>\n        <optCount>{}</optCount>\n        <options>\n", volume.options.len()));
+        for (key, value) in &volume.options {
+            body.push_str(&format!(
+                "          <option><name>{}</name><value>{}</value></option>\n",
+                xml::escape(key),
+                xml::escape(value)
+            ));
+        }
+        body.push_str("        </options>\n      </volume>\n");
+    }
+
+    body.push_str(&format!("      <count>{}</count>\n    </volumes>\n  </volInfo>\n", count));
+    xml_envelope(&body)
+}
@@ -0,0 +1,335 @@
+//! Self-heal de volúmenes replicados y dispersos: entradas pendientes por brick
+//! (`volume heal VOL info [summary] --xml`), lanzar la reparación y el demonio de self-heal.
+
+use std::time::Duration;
+
+use console::style;
+use dialoguer::{Confirm, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+use crate::xml;
+
+/// Entradas por reparar de un brick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BrickHeal {
+    /// `host:/ruta`.
+    pub brick: String,
+    /// `Connected` o el error con el que gluster no pudo consultar el brick.
+    pub status: String,
+    /// Entradas pendientes; `None` si el brick no respondió.
+    pub pending: Option<u64>,
+    pub split_brain: Option<u64>,
+    pub possibly_healing: Option<u64>,
+    /// Rutas (o gfid) pendientes; solo con `heal info`.
+    pub entries: Vec<String>,
+}
+
+impl BrickHeal {
+    pub fn connected(&self) -> bool {
+        self.status == "Connected"
+    }
+}
+
+/// Total de entradas pendientes en los bricks que respondieron.
+pub fn total_pending(bricks: &[BrickHeal]) -> u64 {
+    bricks.iter().filter_map(|b| b.pending).sum()
+}
+
+/// Parsea `heal info` y `heal info summary`: la primera trae `<file>` y `numberOfEntries`,
+/// la segunda los contadores `totalNumberOfEntries`, `numberOfEntriesInSplitBrain`, ...
+pub fn parse_info(output: &str) -> Result<Vec<BrickHeal>> {
+    let doc = xml::parse_cli_output(output)?;
+    let Some(bricks) = xml::descendant(doc.root_element(), "bricks") else {
+        return Ok(vec![]);
+    };
+
+    Ok(xml::children(bricks, "brick")
+        .map(|node| BrickHeal {
+            brick: xml::child_text(node, "name").unwrap_or_default().to_string(),
+            status: xml::child_text(node, "status").unwrap_or_default().to_string(),
+            // Los bricks caídos informan `-` en lugar de un número.
+            pending: xml::child_parse(node, "totalNumberOfEntries").or_else(|| xml::child_parse(node, "numberOfEntries")),
+            split_brain: xml::child_parse(node, "numberOfEntriesInSplitBrain"),
+            possibly_healing: xml::child_parse(node, "numberOfEntriesPossiblyHealing"),
+            entries: xml::children(node, "file")
+                .filter_map(|f| f.text())
+                .map(|f| f.trim().to_string())
+                .collect(),
+        })
+        .collect())
+}
+
+/// Solo los volúmenes con réplica o dispersión tienen self-heal.
+pub fn ensure_healable(backend: &dyn GlusterBackend, volume: &str) -> Result<crate::volinfo::VolumeInfo> {
+    let info = crate::volinfo::fetch_one(backend, volume)?;
+    if info.replica_count <= 1 && info.disperse_count == 0 {
+        return Err(Error::Message(t!("heal.not_replicated", name = volume, kind = info.vol_type)));
+    }
+    if info.status != "Started" {
+        return Err(Error::Message(t!("heal.not_started", name = volume)));
+    }
+    Ok(info)
+}
+
+/// Entradas pendientes de cada brick, con la lista de ficheros.
+pub fn info(backend: &dyn GlusterBackend, volume: &str) -> Result<Vec<BrickHeal>> {
+    ensure_healable(backend, volume)?;
+    let output = backend
+        .gluster_xml(&["volume", "heal", volume, "info"])
+        .map_err(|e| e.context(&t!("heal.info_failed", name = volume)))?;
+    parse_info(&output)
+}
+
+/// Contadores por brick (pendientes, split-brain, posiblemente reparándose).
+pub fn summary(backend: &dyn GlusterBackend, volume: &str) -> Result<Vec<BrickHeal>> {
+    ensure_healable(backend, volume)?;
+    let output = backend
+        .gluster_xml(&["volume", "heal", volume, "info", "summary"])
+        .map_err(|e| e.context(&t!("heal.info_failed", name = volume)))?;
+    parse_info(&output)
+}
+
+/// Lanza la reparación: la de índice solo repasa las entradas marcadas; la completa recorre todo el volumen.
+pub fn trigger(backend: &dyn GlusterBackend, volume: &str, full: bool) -> Result<()> {
+    let info = ensure_healable(backend, volume)?;
+    if !daemon_enabled(&info) {
+        return Err(Error::Message(t!("heal.daemon_off", name = volume)));
+    }
+    let mut args = vec!["volume", "heal", volume];
+    if full {
+        args.push("full");
+    }
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("heal.trigger_failed", name = volume)))?;
+    if full {
+        println!("{}", t!("heal.full_started", name = volume));
+    } else {
+        println!("{}", t!("heal.index_started", name = volume));
+    }
+    Ok(())
+}
+
+/// El demonio de self-heal está activo salvo que `cluster.self-heal-daemon` sea `off`.
+pub fn daemon_enabled(info: &crate::volinfo::VolumeInfo) -> bool {
+    info.options.get("cluster.self-heal-daemon").is_none_or(|v| v != "off" && v != "disable")
+}
+
+/// Activa o desactiva el demonio de self-heal del volumen.
+pub fn set_daemon(backend: &dyn GlusterBackend, volume: &str, enabled: bool) -> Result<()> {
+    ensure_healable(backend, volume)?;
+    backend
+        .sudo_gluster(&["volume", "heal", volume, if enabled { "enable" } else { "disable" }])
+        .map_err(|e| e.context(&t!("heal.daemon_failed", name = volume)))?;
+    if enabled {
+        println!("{}", t!("heal.daemon_enabled", name = volume));
+    } else {
+        println!("{}", t!("heal.daemon_disabled", name = volume));
+    }
+    Ok(())
+}
+
+pub fn print_summary(volume: &str, bricks: &[BrickHeal]) {
+    println!("{}", t!("heal.title", name = volume));
+    let width = bricks.iter().map(|b| b.brick.len()).max().unwrap_or(5).max(5);
+    println!(
+        "   {:<width$}  {:>10}  {:>11}  {:>10}  {}",
+        t!("heal.column_brick"),
+        t!("heal.column_pending"),
+        t!("heal.column_split_brain"),
+        t!("heal.column_healing"),
+        t!("heal.column_status"),
+    );
+    let count = |n: Option<u64>| n.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string());
+    for brick in bricks {
+        let line = format!(
+            "   {:<width$}  {:>10}  {:>11}  {:>10}  {}",
+            brick.brick,
+            count(brick.pending),
+            count(brick.split_brain),
+            count(brick.possibly_healing),
+            brick.status,
+        );
+        if !brick.connected() || brick.split_brain.is_some_and(|n| n > 0) {
+            println!("{}", style(line).red().bold());
+        } else if brick.pending.is_some_and(|n| n > 0) {
+            println!("{}", style(line).yellow());
+        } else {
+            println!("{}", line);
+        }
+    }
+    let total = total_pending(bricks);
+    if total == 0 {
+        println!("{}", t!("heal.all_healed"));
+    } else {
+        println!("{}", t!("heal.total_pending", count = total));
+    }
+}
+
+/// Lista de entradas pendientes por brick.
+pub fn print_entries(bricks: &[BrickHeal]) {
+    for brick in bricks {
+        let pending = brick.pending.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string());
+        println!("{}", t!("heal.entries_title", brick = brick.brick, count = pending, status = brick.status));
+        for entry in &brick.entries {
+            println!("      {}", entry);
+        }
+    }
+}
+
+/// Consulta el resumen cada `interval` hasta que no quede nada pendiente; falla si se agotan `rounds`.
+pub fn watch(backend: &dyn GlusterBackend, volume: &str, interval: Duration, rounds: u64) -> Result<()> {
+    for round in 0..rounds {
+        if round > 0 {
+            std::thread::sleep(interval);
+        }
+        let bricks = summary(backend, volume)?;
+        let offline = bricks.iter().filter(|b| !b.connected()).count();
+        let total = total_pending(&bricks);
+        let time = chrono::Local::now().format("%H:%M:%S");
+        if offline > 0 {
+            println!("{}", t!("heal.watch_line_offline", time = time, count = total, offline = offline));
+        } else {
+            println!("{}", t!("heal.watch_line", time = time, count = total));
+        }
+        // Con bricks caídos el 0 no es fiable: sus entradas no se cuentan.
+        if total == 0 && offline == 0 {
+            println!("{}", t!("heal.all_healed"));
+            return Ok(());
+        }
+    }
+    Err(Error::Message(t!("heal.watch_timeout", name = volume)))
+}
+
+/// Pantalla de self-heal de un volumen.
+pub fn manage_heal(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+    let mut enabled = daemon_enabled(&ensure_healable(backend, &volume)?);
+    print_summary(&volume, &summary(backend, &volume)?);
+
+    loop {
+        let items = [
+            t!("heal.menu_summary"),
+            t!("heal.menu_entries"),
+            t!("heal.menu_index"),
+            t!("heal.menu_full"),
+            if enabled { t!("heal.menu_disable") } else { t!("heal.menu_enable") },
+            t!("heal.menu_watch"),
+            t!("heal.menu_split_brain"),
+            t!("heal.menu_resolve"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("heal.menu_title", name = volume))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => summary(backend, &volume).map(|bricks| print_summary(&volume, &bricks)),
+            1 => info(backend, &volume).map(|bricks| print_entries(&bricks)),
+            2 => trigger(backend, &volume, false),
+            3 => trigger(backend, &volume, true),
+            4 if enabled => {
+                // Sin el demonio no se repara nada hasta que un cliente acceda a cada fichero.
+                if Confirm::with_theme(&theme)
+                    .with_prompt(t!("heal.disable_confirm", name = volume))
+                    .default(false)
+                    .interact_opt()?
+                    .ok_or(Error::Cancelled)?
+                {
+                    set_daemon(backend, &volume, false).map(|_| enabled = false)
+                } else {
+                    Ok(())
+                }
+            }
+            4 => set_daemon(backend, &volume, true).map(|_| enabled = true),
+            5 => watch(backend, &volume, Duration::from_secs(10), 360),
+            6 => crate::splitbrain::list(backend, &volume).map(|entries| crate::splitbrain::print_list(&volume, &entries)),
+            7 => crate::splitbrain::resolve_interactive(backend, &theme, &volume),
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn replicated() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started)
+            .with_replica("datos", 2)
+            .with_pending_heal("datos", "vm1:/gluster/a", &["/docs/informe.odt", "/docs"])
+    }
+
+    #[test]
+    fn parses_info_and_summary() {
+        let fake = replicated();
+        let bricks = info(&fake, "datos").unwrap();
+        assert_eq!(bricks[0].brick, "vm1:/gluster/a");
+        assert_eq!(bricks[0].pending, Some(2));
+        assert_eq!(bricks[0].entries, vec!["/docs/informe.odt", "/docs"]);
+        assert_eq!(bricks[1].pending, Some(0));
+
+        let bricks = summary(&fake, "datos").unwrap();
+        assert_eq!(bricks[0].split_brain, Some(0));
+        assert_eq!(total_pending(&bricks), 2);
+    }
+
+    #[test]
+    fn offline_bricks_have_no_count() {
+        let fake = replicated().with_offline_brick("datos", "vm2:/gluster/a");
+        let bricks = summary(&fake, "datos").unwrap();
+        assert!(!bricks[1].connected());
+        assert_eq!(bricks[1].pending, None);
+    }
+
+    #[test]
+    fn distributed_volumes_are_rejected() {
+        let fake = FakeBackend::new().with_volume("plano", &["vm1:/gluster/a"], VolumeState::Started);
+        assert!(summary(&fake, "plano").is_err());
+        assert!(trigger(&fake, "plano", false).is_err());
+        assert!(fake.commands().is_empty());
+    }
+
+    #[test]
+    fn heal_needs_the_daemon() {
+        let fake = replicated();
+        set_daemon(&fake, "datos", false).unwrap();
+        assert!(trigger(&fake, "datos", false).is_err());
+        set_daemon(&fake, "datos", true).unwrap();
+        trigger(&fake, "datos", true).unwrap();
+        assert_eq!(
+            fake.commands(),
+            vec![
+                "gluster --mode=script volume heal datos disable",
+                "gluster --mode=script volume heal datos enable",
+                "gluster --mode=script volume heal datos full",
+            ]
+        );
+    }
+
+    #[test]
+    fn watch_stops_when_nothing_is_pending() {
+        let fake = replicated();
+        trigger(&fake, "datos", false).unwrap();
+        assert!(watch(&fake, "datos", Duration::ZERO, 10).is_ok());
+
+        let fake = replicated();
+        // Sin lanzar la reparación nada avanza.
+        assert!(watch(&fake, "datos", Duration::ZERO, 3).is_err());
+    }
+}
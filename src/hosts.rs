@@ -1,92 +1,170 @@
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader, Write};
 use dialoguer::{Input, Select, Confirm, theme::ColorfulTheme};
 
-pub fn edit_hosts() {
-    let theme = ColorfulTheme::default();
-    let path = "/etc/hosts";
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
 
-    // Leer contenido actual
-    let file = match std::fs::File::open(path) {
-        Ok(file) => file,
-        Err(_) => {
-            eprintln!("❌ No se pudo abrir /etc/hosts. ¿Tienes permisos de sudo?");
-            return;
+const HOSTS_PATH: &str = "/etc/hosts";
+
+/// Lee todas las líneas de /etc/hosts.
+pub fn read_entries(backend: &dyn GlusterBackend) -> Result<Vec<String>> {
+    let contents = backend.read_file(HOSTS_PATH).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => Error::PermissionDenied(t!("hosts.open_failed", path = HOSTS_PATH)),
+        _ => Error::Io(e),
+    })?;
+
+    Ok(contents.lines().map(str::to_string).collect())
+}
+
+/// Sobrescribe /etc/hosts con las líneas dadas.
+pub fn write_entries(backend: &dyn GlusterBackend, entries: &[String]) -> Result<()> {
+    let mut contents = String::new();
+    for line in entries {
+        contents.push_str(line);
+        contents.push('\n');
+    }
+
+    backend.write_file(HOSTS_PATH, &contents).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            Error::PermissionDenied(t!("hosts.save_open_failed", path = HOSTS_PATH))
         }
-    };
+        _ => Error::Message(t!("hosts.write_failed", error = e)),
+    })
+}
 
-    let reader = BufReader::new(file);
-    let mut entries: Vec<String> = reader.lines().filter_map(Result::ok).collect();
+/// Indica si una línea de /etc/hosts corresponde a la IP o al nombre indicado.
+fn entry_matches(line: &str, host: &str) -> bool {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return false;
+    }
+    line.split_whitespace().any(|field| field == host)
+}
+
+pub fn add_entry(backend: &dyn GlusterBackend, ip: &str, hostname: &str) -> Result<()> {
+    let mut entries = read_entries(backend)?;
+    let new_entry = format!("{} {}", ip.trim(), hostname.trim());
+
+    if entries.iter().any(|line| line.trim() == new_entry) {
+        println!("{}", t!("hosts.already_exists"));
+        return Ok(());
+    }
+
+    entries.push(new_entry);
+    write_entries(backend, &entries)?;
+    println!("{}", t!("hosts.added"));
+    Ok(())
+}
+
+/// Cambia la IP de todas las entradas que contienen `hostname`.
+pub fn update_entry(backend: &dyn GlusterBackend, hostname: &str, ip: &str) -> Result<()> {
+    let mut entries = read_entries(backend)?;
+    let mut changed = 0;
+
+    for line in entries.iter_mut() {
+        if entry_matches(line, hostname) {
+            let names: Vec<&str> = line.split_whitespace().skip(1).collect();
+            *line = format!("{} {}", ip.trim(), names.join(" "));
+            changed += 1;
+        }
+    }
+
+    if changed == 0 {
+        return Err(Error::Message(t!("hosts.no_entries_for", host = hostname)));
+    }
+
+    write_entries(backend, &entries)?;
+    println!("{}", t!("hosts.updated"));
+    Ok(())
+}
+
+/// Elimina las entradas que contienen la IP o el nombre indicado.
+pub fn remove_entry(backend: &dyn GlusterBackend, host: &str) -> Result<()> {
+    let mut entries = read_entries(backend)?;
+    let before = entries.len();
+    entries.retain(|line| !entry_matches(line, host));
+
+    if entries.len() == before {
+        return Err(Error::Message(t!("hosts.no_entries_for", host = host)));
+    }
+
+    write_entries(backend, &entries)?;
+    println!("{}", t!("hosts.removed"));
+    Ok(())
+}
+
+pub fn edit_hosts(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+
+    // Leer contenido actual
+    let mut entries = read_entries(backend)?;
 
     loop {
-        println!("\n📄 Entradas actuales en /etc/hosts:");
+        println!("\n{}", t!("hosts.current_entries", path = HOSTS_PATH));
 
         for (i, line) in entries.iter().enumerate() {
             println!("{}: {}", i + 1, line);
         }
 
         let options = vec![
-            "➕ Agregar nueva entrada",
-            "✏️ Modificar entrada existente",
-            "🗑️ Eliminar entrada existente",
-            "💾 Guardar y salir",
-            "❌ Salir sin guardar",
+            t!("hosts.menu_add"),
+            t!("hosts.menu_edit"),
+            t!("hosts.menu_remove"),
+            t!("hosts.menu_save"),
+            t!("hosts.menu_discard"),
         ];
 
         let choice = Select::with_theme(&theme)
-            .with_prompt("¿Qué quieres hacer?")
+            .with_prompt(t!("hosts.prompt"))
             .items(&options)
             .default(0)
-            .interact()
-            .unwrap();
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?;
 
         match choice {
             0 => { // Agregar
                 let ip: String = Input::with_theme(&theme)
-                    .with_prompt("Ingresa la IP del nodo")
-                    .interact_text()
-                    .unwrap();
+                    .with_prompt(t!("hosts.ip_prompt"))
+                    .interact_text()?;
 
                 let hostname: String = Input::with_theme(&theme)
-                    .with_prompt("Ingresa el nombre del nodo (ej: vm1)")
-                    .interact_text()
-                    .unwrap();
+                    .with_prompt(t!("hosts.name_prompt"))
+                    .interact_text()?;
 
                 let new_entry = format!("{} {}", ip.trim(), hostname.trim());
 
                 if entries.iter().any(|line| line.trim() == new_entry) {
-                    println!("⚠️  Esa entrada ya existe.");
+                    println!("{}", t!("hosts.already_exists"));
                 } else {
                     entries.push(new_entry);
-                    println!("✅ Entrada agregada.");
+                    println!("{}", t!("hosts.added"));
                 }
             }
             1 => { // Modificar
                 if entries.is_empty() {
-                    println!("⚠️ No hay entradas para modificar.");
+                    println!("{}", t!("hosts.nothing_to_edit"));
                     continue;
                 }
 
                 let selection = Select::with_theme(&theme)
-                    .with_prompt("Selecciona la entrada a modificar")
+                    .with_prompt(t!("hosts.edit_prompt"))
                     .items(&entries)
                     .default(0)
-                    .interact()
-                    .unwrap();
+                    .interact_opt()?
+                    .ok_or(Error::Cancelled)?;
 
                 let ip: String = Input::with_theme(&theme)
-                    .with_prompt("Nueva IP")
+                    .with_prompt(t!("hosts.new_ip_prompt"))
                     .with_initial_text(
                         entries[selection]
                             .split_whitespace()
                             .next()
                             .unwrap_or_default(),
                     )
-                    .interact_text()
-                    .unwrap();
+                    .interact_text()?;
 
                 let hostname: String = Input::with_theme(&theme)
-                    .with_prompt("Nuevo nombre de nodo")
+                    .with_prompt(t!("hosts.new_name_prompt"))
                     .with_initial_text(
                         entries[selection]
                             .split_whitespace()
@@ -94,69 +172,90 @@ pub fn edit_hosts() {
                             .collect::<Vec<_>>()
                             .join(" "),
                     )
-                    .interact_text()
-                    .unwrap();
+                    .interact_text()?;
 
                 entries[selection] = format!("{} {}", ip.trim(), hostname.trim());
-                println!("✅ Entrada modificada.");
+                println!("{}", t!("hosts.updated"));
             }
             2 => { // Eliminar
                 if entries.is_empty() {
-                    println!("⚠️ No hay entradas para eliminar.");
+                    println!("{}", t!("hosts.nothing_to_remove"));
                     continue;
                 }
 
                 let selection = Select::with_theme(&theme)
-                    .with_prompt("Selecciona la entrada a eliminar")
+                    .with_prompt(t!("hosts.remove_prompt"))
                     .items(&entries)
                     .default(0)
-                    .interact()
-                    .unwrap();
+                    .interact_opt()?
+                    .ok_or(Error::Cancelled)?;
 
                 if Confirm::with_theme(&theme)
-                    .with_prompt(format!("¿Eliminar '{}'? Esta acción es irreversible.", entries[selection]))
+                    .with_prompt(t!("hosts.remove_confirm", entry = entries[selection]))
                     .default(false)
-                    .interact()
-                    .unwrap()
+                    .interact_opt()?
+                    .ok_or(Error::Cancelled)?
                 {
                     entries.remove(selection);
-                    println!("✅ Entrada eliminada.");
+                    println!("{}", t!("hosts.removed"));
                 }
             }
             3 => { // Guardar y salir
-                let mut file = match OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .open(path)
-                {
-                    Ok(f) => f,
-                    Err(_) => {
-                        eprintln!("❌ No se pudo abrir /etc/hosts para guardar. ¿Tienes permisos de sudo?");
-                        return;
-                    }
-                };
-
-                for line in &entries {
-                    if let Err(e) = writeln!(file, "{}", line) {
-                        eprintln!("❌ Error escribiendo en archivo: {}", e);
-                        return;
-                    }
-                }
-                println!("💾 Cambios guardados correctamente.");
+                write_entries(backend, &entries)?;
+                println!("{}", t!("hosts.saved"));
                 break;
             }
-            4 => { // Salir sin guardar
-                if Confirm::with_theme(&theme)
-                    .with_prompt("¿Seguro que quieres salir sin guardar?")
-                    .default(false)
-                    .interact()
-                    .unwrap()
-                {
-                    println!("❌ Cambios descartados.");
-                    break;
-                }
+            // Salir sin guardar
+            4 if Confirm::with_theme(&theme)
+                .with_prompt(t!("hosts.discard_confirm"))
+                .default(false)
+                .interact_opt()?
+                .ok_or(Error::Cancelled)? =>
+            {
+                println!("{}", t!("hosts.discarded"));
+                break;
             }
             _ => {}
         }
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::FakeBackend;
+
+    const HOSTS: &str = "127.0.0.1 localhost\n# nodos\n192.168.1.10 vm1\n192.168.1.11 vm2 vm2.local\n";
+
+    #[test]
+    fn add_update_and_remove_entries() {
+        let fake = FakeBackend::new().with_file(HOSTS_PATH, HOSTS);
+
+        add_entry(&fake, "192.168.1.12", "vm3").unwrap();
+        update_entry(&fake, "vm2", "10.0.0.2").unwrap();
+        remove_entry(&fake, "vm1").unwrap();
+
+        assert_eq!(
+            fake.read_file(HOSTS_PATH).unwrap(),
+            "127.0.0.1 localhost\n# nodos\n10.0.0.2 vm2 vm2.local\n192.168.1.12 vm3\n"
+        );
+    }
+
+    #[test]
+    fn duplicate_entry_is_not_added_twice() {
+        let fake = FakeBackend::new().with_file(HOSTS_PATH, HOSTS);
+
+        add_entry(&fake, "192.168.1.10", "vm1").unwrap();
+        assert_eq!(fake.read_file(HOSTS_PATH).unwrap(), HOSTS);
+    }
+
+    #[test]
+    fn unknown_host_is_an_error_and_comments_are_kept() {
+        let fake = FakeBackend::new().with_file(HOSTS_PATH, HOSTS);
+
+        assert!(remove_entry(&fake, "vm9").is_err());
+        assert!(remove_entry(&fake, "nodos").is_err());
+        assert_eq!(fake.read_file(HOSTS_PATH).unwrap(), HOSTS);
+    }
 }
@@ -0,0 +1,230 @@
+//! Catálogos de mensajes (español e inglés) y selección de idioma.
+//!
+//! Cada mensaje vive en `locales/<idioma>.toml` bajo una clave con puntos (`volume.created`).
+//! Los argumentos se escriben `{nombre}`; los mensajes con plural son tablas `one`/`other`
+//! y eligen la forma según el argumento `count`.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lang {
+    Es,
+    En,
+}
+
+impl Lang {
+    /// Interpreta `es`, `en_US.UTF-8`, `English`, ...; `None` si no es un idioma soportado.
+    pub fn parse(value: &str) -> Option<Lang> {
+        let value = value.trim().to_lowercase();
+        if value.starts_with("es") || value == "spanish" || value == "español" {
+            Some(Lang::Es)
+        } else if value.starts_with("en") {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+
+    /// Idioma según el entorno: `LC_ALL`, luego `LC_MESSAGES` y por último `LANG`.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Lang> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| var(name))
+            .find(|value| !value.is_empty())
+            .and_then(|value| Lang::parse(&value))
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Lang::Es => include_str!("../locales/es.toml"),
+            Lang::En => include_str!("../locales/en.toml"),
+        }
+    }
+}
+
+static CURRENT: OnceLock<Lang> = OnceLock::new();
+
+/// Fija el idioma de la sesión; sin llamarla (como en los tests) se usa español.
+pub fn init(lang: Lang) {
+    let _ = CURRENT.set(lang);
+}
+
+pub fn current() -> Lang {
+    CURRENT.get().copied().unwrap_or(Lang::Es)
+}
+
+enum Entry {
+    Text(String),
+    Plural { one: String, other: String },
+}
+
+type Catalog = HashMap<String, Entry>;
+
+fn catalog(lang: Lang) -> &'static Catalog {
+    static ES: OnceLock<Catalog> = OnceLock::new();
+    static EN: OnceLock<Catalog> = OnceLock::new();
+
+    let cell = match lang {
+        Lang::Es => &ES,
+        Lang::En => &EN,
+    };
+    cell.get_or_init(|| {
+        // Los catálogos van embebidos en el binario: un error aquí es un fallo de compilación del catálogo.
+        let table: toml::Table = lang.source().parse().unwrap_or_default();
+        let mut catalog = HashMap::new();
+        flatten("", &table, &mut catalog);
+        catalog
+    })
+}
+
+fn flatten(prefix: &str, table: &toml::Table, catalog: &mut Catalog) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::String(text) => {
+                catalog.insert(key, Entry::Text(text.clone()));
+            }
+            toml::Value::Table(inner) if inner.contains_key("other") => {
+                let form = |name: &str| inner.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                catalog.insert(
+                    key,
+                    Entry::Plural {
+                        one: form("one"),
+                        other: form("other"),
+                    },
+                );
+            }
+            toml::Value::Table(inner) => flatten(&key, inner, catalog),
+            _ => {}
+        }
+    }
+}
+
+/// Traduce `key` al idioma indicado sustituyendo los `{argumentos}`.
+pub fn translate_in(lang: Lang, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = match catalog(lang).get(key).or_else(|| catalog(Lang::Es).get(key)) {
+        Some(Entry::Text(text)) => text.as_str(),
+        Some(Entry::Plural { one, other }) => {
+            let count = args.iter().find(|(name, _)| *name == "count").map(|(_, v)| v.to_string());
+            if count.as_deref() == Some("1") { one.as_str() } else { other.as_str() }
+        }
+        // Una clave sin traducir se muestra tal cual para que sea fácil de detectar.
+        None => return key.to_string(),
+    };
+
+    let mut message = template.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+pub fn translate(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate_in(current(), key, args)
+}
+
+/// Respuesta para cancelar un prompt de texto: `salir` o su traducción (`exit`).
+pub fn is_exit(answer: &str) -> bool {
+    is_word(answer, "salir", "common.exit_word")
+}
+
+/// Respuesta para terminar una lista de entradas: `fin` o su traducción (`done`).
+pub fn is_done(answer: &str) -> bool {
+    is_word(answer, "fin", "common.done_word")
+}
+
+fn is_word(answer: &str, spanish: &str, key: &str) -> bool {
+    let answer = answer.trim();
+    answer.eq_ignore_ascii_case(spanish) || answer.eq_ignore_ascii_case(&translate(key, &[]))
+}
+
+/// `t!("clave")` o `t!("clave", nombre = valor, ...)`: mensaje en el idioma de la sesión.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate($key, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(lang: Lang) -> Vec<&'static String> {
+        let mut keys: Vec<_> = catalog(lang).keys().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        assert!(!keys(Lang::Es).is_empty());
+        assert_eq!(keys(Lang::Es), keys(Lang::En));
+    }
+
+    #[test]
+    fn every_key_used_in_the_code_exists() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+        for file in std::fs::read_dir(dir).unwrap().flatten().filter(|f| f.file_name() != "i18n.rs") {
+            let source = std::fs::read_to_string(file.path()).unwrap();
+            let parts: Vec<&str> = source.split("t!(").collect();
+            for (before, part) in parts.iter().zip(&parts[1..]) {
+                // `format!(` también termina en `t!(`; la clave puede ir en la línea siguiente.
+                let Some(part) = part.trim_start().strip_prefix('"') else { continue };
+                if before.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let key = part.split('"').next().unwrap();
+                assert!(catalog(Lang::Es).contains_key(key), "falta '{}' ({:?})", key, file.path());
+            }
+        }
+    }
+
+    #[test]
+    fn every_cli_help_is_translated() {
+        fn check(command: &clap::Command) {
+            let untranslated = |text: Option<&clap::builder::StyledStr>| text.is_some_and(|t| t.to_string().starts_with("cli_help."));
+            assert!(command.get_about().is_some() && !untranslated(command.get_about()), "{}", command.get_name());
+            for arg in command.get_arguments() {
+                assert!(!untranslated(arg.get_help()), "{} {}", command.get_name(), arg.get_id());
+                for value in arg.get_possible_values() {
+                    assert!(!untranslated(value.get_help()), "{}", value.get_name());
+                }
+            }
+            command.get_subcommands().for_each(check);
+        }
+
+        let command = crate::cli::command();
+        command.clone().debug_assert();
+        check(&command);
+    }
+
+    #[test]
+    fn formats_arguments_and_plurals() {
+        assert_eq!(
+            translate_in(Lang::En, "pool.disconnected", &[("count", &1)]),
+            "   ⚠️ 1 peer disconnected"
+        );
+        assert_eq!(
+            translate_in(Lang::Es, "pool.disconnected", &[("count", &3)]),
+            "   ⚠️ 3 peers desconectados"
+        );
+        assert_eq!(translate_in(Lang::En, "no.existe", &[]), "no.existe");
+    }
+
+    #[test]
+    fn language_from_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        };
+        assert_eq!(Lang::from_env(env(&[("LANG", "en_US.UTF-8")])), Some(Lang::En));
+        assert_eq!(Lang::from_env(env(&[("LANG", "en_US.UTF-8"), ("LC_MESSAGES", "es_ES")])), Some(Lang::Es));
+        assert_eq!(Lang::from_env(env(&[("LC_ALL", ""), ("LANG", "es_CO.UTF-8")])), Some(Lang::Es));
+        assert_eq!(Lang::from_env(env(&[("LANG", "C")])), None);
+    }
+}
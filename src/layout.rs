@@ -0,0 +1,230 @@
+//! Disposición de un volumen: cómo agrupa gluster los bricks (distribuido, réplica, dispersión).
+
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::t;
+use crate::volinfo::VolumeInfo;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VolumeLayout {
+    Distribute,
+    /// `replica N [arbiter 1]`: cada conjunto de `replica` bricks guarda copias de los mismos ficheros.
+    Replicate { replica: u32, arbiter: u32 },
+    /// `disperse N redundancy R`: cada conjunto de `disperse` bricks tolera perder `redundancy`.
+    Disperse { disperse: u32, redundancy: u32 },
+}
+
+impl VolumeLayout {
+    /// Argumentos de `volume create` que van entre el nombre y los bricks.
+    pub fn create_args(&self) -> Vec<String> {
+        match *self {
+            VolumeLayout::Distribute => vec![],
+            VolumeLayout::Replicate { replica, arbiter } => {
+                let mut args = vec!["replica".to_string(), replica.to_string()];
+                if arbiter > 0 {
+                    args.extend(["arbiter".to_string(), arbiter.to_string()]);
+                }
+                args
+            }
+            VolumeLayout::Disperse { disperse, redundancy } => vec![
+                "disperse".to_string(),
+                disperse.to_string(),
+                "redundancy".to_string(),
+                redundancy.to_string(),
+            ],
+        }
+    }
+
+    /// Bricks por subvolumen (conjunto de réplica o dispersión); 1 en un volumen distribuido.
+    pub fn set_size(&self) -> usize {
+        match *self {
+            VolumeLayout::Distribute => 1,
+            VolumeLayout::Replicate { replica, .. } => replica as usize,
+            VolumeLayout::Disperse { disperse, .. } => disperse as usize,
+        }
+    }
+
+    /// Rechaza las combinaciones que `volume create` no acepta.
+    pub fn check(&self) -> Result<()> {
+        match *self {
+            VolumeLayout::Distribute => Ok(()),
+            VolumeLayout::Replicate { replica, arbiter } => {
+                if replica < 2 {
+                    Err(Error::Message(t!("layout.replica_too_small")))
+                } else if arbiter > 0 && (arbiter != 1 || replica != 3) {
+                    Err(Error::Message(t!("layout.arbiter_only_replica3")))
+                } else {
+                    Ok(())
+                }
+            }
+            VolumeLayout::Disperse { disperse, redundancy } => {
+                if disperse < 3 {
+                    Err(Error::Message(t!("layout.disperse_too_small")))
+                } else if redundancy == 0 || 2 * redundancy >= disperse {
+                    Err(Error::Message(t!("layout.bad_redundancy", disperse = disperse, max = (disperse - 1) / 2)))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Comprueba la disposición y que `count` bricks formen conjuntos completos, como exige `volume create`.
+    pub fn check_brick_count(&self, count: usize) -> Result<()> {
+        self.check()?;
+        if count == 0 || !count.is_multiple_of(self.set_size()) {
+            return Err(Error::Message(t!(
+                "layout.brick_count",
+                count = count,
+                size = self.set_size(),
+                layout = self
+            )));
+        }
+        Ok(())
+    }
+
+    /// Bricks agrupados en conjuntos, en el mismo orden en que gluster los agrupa.
+    pub fn sets<'a>(&self, bricks: &'a [String]) -> Vec<&'a [String]> {
+        bricks.chunks(self.set_size()).collect()
+    }
+
+    /// En `replica N arbiter 1` el último brick de cada conjunto es el árbitro.
+    pub fn is_arbiter(&self, position_in_set: usize) -> bool {
+        matches!(*self, VolumeLayout::Replicate { replica, arbiter } if arbiter > 0 && position_in_set + 1 == replica as usize)
+    }
+
+    /// Bricks que pueden perderse en cada conjunto sin perder datos (el árbitro no guarda datos).
+    pub fn fault_tolerance(&self) -> u32 {
+        match *self {
+            VolumeLayout::Distribute => 0,
+            VolumeLayout::Replicate { replica, arbiter } => replica - arbiter - 1,
+            VolumeLayout::Disperse { redundancy, .. } => redundancy,
+        }
+    }
+
+    /// Capacidad útil de `count` bricks del mismo tamaño, en bricks.
+    pub fn usable_bricks(&self, count: usize) -> usize {
+        let sets = count / self.set_size();
+        match *self {
+            VolumeLayout::Distribute => count,
+            VolumeLayout::Replicate { .. } => sets,
+            VolumeLayout::Disperse { disperse, redundancy } => sets * (disperse - redundancy) as usize,
+        }
+    }
+
+    /// Gluster recomienda que los bricks de datos (`disperse - redundancy`) sean potencia de 2.
+    pub fn is_optimal(&self) -> bool {
+        match *self {
+            VolumeLayout::Disperse { disperse, redundancy } => (disperse - redundancy).is_power_of_two(),
+            _ => true,
+        }
+    }
+
+    /// Combinaciones de dispersión válidas para `count` bricks (dispersas o distribuidas-dispersas),
+    /// primero las de conjuntos más grandes y, dentro de cada una, de menor a mayor redundancia.
+    pub fn disperse_options(count: usize) -> Vec<VolumeLayout> {
+        let mut options = vec![];
+        for disperse in (3..=count as u32).rev().filter(|d| count.is_multiple_of(*d as usize)) {
+            for redundancy in (1..).take_while(|r| 2 * r < disperse) {
+                options.push(VolumeLayout::Disperse { disperse, redundancy });
+            }
+        }
+        options
+    }
+
+    pub fn from_info(info: &VolumeInfo) -> Self {
+        if info.disperse_count > 0 {
+            VolumeLayout::Disperse {
+                disperse: info.disperse_count,
+                redundancy: info.redundancy_count,
+            }
+        } else if info.replica_count > 1 {
+            VolumeLayout::Replicate {
+                replica: info.replica_count,
+                arbiter: info.arbiter_count,
+            }
+        } else {
+            VolumeLayout::Distribute
+        }
+    }
+}
+
+impl fmt::Display for VolumeLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VolumeLayout::Distribute => write!(f, "{}", t!("layout.distribute")),
+            VolumeLayout::Replicate { replica, arbiter: 0 } => write!(f, "{}", t!("layout.replicate", replica = replica)),
+            VolumeLayout::Replicate { replica, arbiter } => {
+                write!(f, "{}", t!("layout.replicate_arbiter", replica = replica, arbiter = arbiter))
+            }
+            VolumeLayout::Disperse { disperse, redundancy } => {
+                write!(f, "{}", t!("layout.disperse", disperse = disperse, redundancy = redundancy))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bricks(n: usize) -> Vec<String> {
+        (1..=n).map(|i| format!("vm{}:/gluster/a", i)).collect()
+    }
+
+    #[test]
+    fn brick_count_must_fill_whole_sets() {
+        let replica3 = VolumeLayout::Replicate { replica: 3, arbiter: 0 };
+
+        assert!(replica3.check_brick_count(6).is_ok());
+        assert!(replica3.check_brick_count(4).is_err());
+        assert!(replica3.check_brick_count(0).is_err());
+        assert!(VolumeLayout::Distribute.check_brick_count(1).is_ok());
+    }
+
+    #[test]
+    fn sets_follow_brick_order_and_mark_arbiters() {
+        let layout = VolumeLayout::Replicate { replica: 3, arbiter: 1 };
+        let bricks = bricks(6);
+
+        let sets = layout.sets(&bricks);
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[1], &bricks[3..]);
+        assert!(layout.is_arbiter(2) && !layout.is_arbiter(1));
+        assert!(!VolumeLayout::Replicate { replica: 3, arbiter: 0 }.is_arbiter(2));
+        assert_eq!(layout.create_args(), vec!["replica", "3", "arbiter", "1"]);
+    }
+
+    #[test]
+    fn disperse_options_follow_gluster_rules() {
+        let options = VolumeLayout::disperse_options(6);
+        assert_eq!(
+            options,
+            vec![
+                VolumeLayout::Disperse { disperse: 6, redundancy: 1 },
+                VolumeLayout::Disperse { disperse: 6, redundancy: 2 },
+                VolumeLayout::Disperse { disperse: 3, redundancy: 1 },
+            ]
+        );
+        assert!(options.iter().all(|o| o.check_brick_count(6).is_ok()));
+        assert!(VolumeLayout::disperse_options(2).is_empty());
+
+        assert!(VolumeLayout::Disperse { disperse: 4, redundancy: 2 }.check().is_err());
+        assert!(VolumeLayout::Disperse { disperse: 2, redundancy: 0 }.check().is_err());
+        assert!(VolumeLayout::Replicate { replica: 2, arbiter: 1 }.check().is_err());
+    }
+
+    #[test]
+    fn capacity_and_fault_tolerance() {
+        let ec = VolumeLayout::Disperse { disperse: 6, redundancy: 2 };
+        assert_eq!(ec.usable_bricks(12), 8);
+        assert_eq!(ec.fault_tolerance(), 2);
+        assert!(ec.is_optimal());
+        assert!(!VolumeLayout::Disperse { disperse: 6, redundancy: 1 }.is_optimal());
+
+        let arbiter = VolumeLayout::Replicate { replica: 3, arbiter: 1 };
+        assert_eq!(arbiter.usable_bricks(6), 2);
+        assert_eq!(arbiter.fault_tolerance(), 1);
+    }
+}
@@ -6,8 +6,59 @@ mod volume;
 mod mount;
 mod peers;
 mod cluster;
+mod cli;
+mod backend;
+mod xml;
+mod volinfo;
+mod volstatus;
+mod pool;
+mod layout;
+mod placement;
+mod options;
+mod profiles;
+mod quota;
+mod snapshot;
+mod schedule;
+mod heal;
+mod splitbrain;
+mod rebalance;
+mod removal;
+mod replace;
+mod spec;
+mod dryrun;
+mod audit;
+mod error;
+mod i18n;
+mod config;
+#[cfg(test)]
+mod fake;
+
+use clap::FromArgMatches;
 
 fn main() {
-    let is_master = mode::ask_role();
-    menu::show_main_menu(is_master);
+    let system = backend::SystemBackend;
+
+    // El idioma se fija antes de cualquier mensaje; una configuración inválida se avisa y se usa el entorno.
+    let config = config::Config::load(&system).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        config::Config::default()
+    });
+    i18n::init(config.language(|name| std::env::var(name).ok()));
+
+    // Con el idioma ya fijado, `--help` sale traducido.
+    let cli = cli::Cli::from_arg_matches(&cli::command().get_matches()).unwrap_or_else(|e| e.exit());
+
+    // La simulación va por fuera: lo que no se ejecuta tampoco llega al registro de auditoría.
+    let audited = audit::AuditBackend::new(&system, audit::AUDIT_PATH, &audit::invoking_user(&system));
+    let backend = dryrun::DryRunBackend::new(&audited, cli.dry_run);
+
+    // Con un subcomando se ejecuta sin prompts; sin él, el menú interactivo de siempre.
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(&backend, command));
+    }
+
+    match mode::ask_role() {
+        Ok(is_master) => menu::show_main_menu(&backend, is_master),
+        Err(e) => error::report(Err(e)),
+    }
 }
@@ -1,49 +1,93 @@
 use dialoguer::{Select, theme::ColorfulTheme};
 
-pub fn show_main_menu(is_master: bool) {
+use crate::backend::GlusterBackend;
+use crate::dryrun::DryRunBackend;
+use crate::error::{self, Result};
+use crate::t;
+
+pub fn show_main_menu(session: &DryRunBackend, is_master: bool) {
+    let backend: &dyn GlusterBackend = session;
+
     loop {
+        let dry_run_toggle = if backend.is_dry_run() {
+            t!("menu.dry_run_off")
+        } else {
+            t!("menu.dry_run_on")
+        };
         let mut options = vec![
-            "Editar /etc/hosts",
-            "Gestionar bricks",
-            "Montar volumen",
-            "Gestionar montajes",
-            "Salir",
+            t!("menu.edit_hosts"),
+            t!("menu.manage_bricks"),
+            t!("menu.mount_volume"),
+            t!("menu.manage_mounts"),
+            t!("menu.audit"),
+            dry_run_toggle,
+            t!("menu.exit"),
         ];
 
         if is_master {
-            options.insert(2, "Agregar peer");
-            options.insert(3, "Crear e iniciar volumen");
-            options.insert(4, "Ver estado del clúster");
-            options.insert(5, "Gestionar volúmenes");
+            options.insert(2, t!("menu.manage_peers"));
+            options.insert(3, t!("menu.create_volume"));
+            options.insert(4, t!("menu.cluster_status"));
+            options.insert(5, t!("menu.manage_volumes"));
         }
 
         let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("¿Qué deseas hacer?")
+            .with_prompt(if backend.is_dry_run() {
+                t!("menu.prompt_dry_run")
+            } else {
+                t!("menu.prompt")
+            })
             .items(&options)
             .default(0)
-            .interact()
-            .unwrap();
+            .interact_opt();
 
-        if is_master {
+        // Esc o Ctrl-C en el menú principal equivalen a "Salir".
+        let selection = match selection {
+            Ok(Some(selection)) => selection,
+            Ok(None) => break,
+            Err(e) => {
+                error::report(Err(e.into()));
+                break;
+            }
+        };
+
+        let result = if is_master {
             match selection {
-                0 => crate::hosts::edit_hosts(),
-                1 => crate::bricks::manage_bricks(),
-                2 => crate::peers::add_peer(),
-                3 => crate::volume::create_volume(),
-                4 => crate::cluster::check_status(),
-                5 => crate::volume::manage_volumes(),
-                6 => crate::mount::mount_volume(),
-                7 => crate::mount::manage_mounts(),
+                0 => crate::hosts::edit_hosts(backend),
+                1 => crate::bricks::manage_bricks(backend),
+                2 => crate::peers::manage_peers(backend),
+                3 => crate::volume::create_volume(backend),
+                4 => crate::cluster::status(backend),
+                5 => crate::volume::manage_volumes(backend),
+                6 => crate::mount::mount_volume(backend),
+                7 => crate::mount::manage_mounts(backend),
+                8 => crate::audit::browse(backend),
+                9 => toggle_dry_run(session),
                 _ => break,
             }
         } else {
             match selection {
-                0 => crate::hosts::edit_hosts(),
-                1 => crate::bricks::manage_bricks(),
-                2 => crate::mount::mount_volume(),
-                3 => crate::mount::manage_mounts(),
+                0 => crate::hosts::edit_hosts(backend),
+                1 => crate::bricks::manage_bricks(backend),
+                2 => crate::mount::mount_volume(backend),
+                3 => crate::mount::manage_mounts(backend),
+                4 => crate::audit::browse(backend),
+                5 => toggle_dry_run(session),
                 _ => break,
             }
-        }
+        };
+        // Los errores se informan y se vuelve al menú en lugar de terminar el programa.
+        error::report(result);
+    }
+}
+
+fn toggle_dry_run(session: &DryRunBackend) -> Result<()> {
+    let enabled = !session.is_dry_run();
+    session.set_enabled(enabled);
+    if enabled {
+        println!("{}", t!("menu.dry_run_enabled"));
+    } else {
+        println!("{}", t!("menu.dry_run_disabled"));
     }
+    Ok(())
 }
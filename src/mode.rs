@@ -1,14 +1,17 @@
 use dialoguer::{Select, theme::ColorfulTheme};
 
-pub fn ask_role() -> bool {
-    let options = &["Nodo Maestro", "Nodo Cliente"];
+use crate::error::{Error, Result};
+use crate::t;
+
+pub fn ask_role() -> Result<bool> {
+    let options = &[t!("mode.master"), t!("mode.client")];
 
     let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Selecciona el tipo de nodo")
+        .with_prompt(t!("mode.prompt"))
         .items(options)
         .default(0)
-        .interact()
-        .unwrap();
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
-    selection == 0 // true si es maestro, false si cliente
+    Ok(selection == 0) // true si es maestro, false si cliente
 }
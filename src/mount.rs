@@ -1,111 +1,92 @@
-use std::fs;
-use std::path::Path;
-use std::process::Command;
 use std::collections::HashSet;
 use dialoguer::{theme::ColorfulTheme, Input, Select, Confirm};
-use users::get_user_by_name;
 
-pub fn mount_volume() {
-    println!("\n📂 Montar volumen GlusterFS");
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::i18n::is_exit;
+use crate::t;
+
+pub fn mount_volume(backend: &dyn GlusterBackend) -> Result<()> {
+    println!("\n{}", t!("mount.title"));
 
     let theme = ColorfulTheme::default();
 
     // 👉 Obtener nombre del servidor
     let server: String = Input::with_theme(&theme)
-        .with_prompt("Nombre del servidor (ej. vm1) o 'salir' para cancelar")
-        .interact_text()
-        .unwrap();
+        .with_prompt(t!("mount.server_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
 
-    if server.trim().eq_ignore_ascii_case("salir") {
-        println!("❎ Operación cancelada.");
-        return;
+    if is_exit(&server) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
     // 👉 Nombre del volumen
     let volume: String = Input::with_theme(&theme)
-        .with_prompt("Nombre del volumen o 'salir' para cancelar")
-        .interact_text()
-        .unwrap();
+        .with_prompt(t!("mount.volume_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
 
-    if volume.trim().eq_ignore_ascii_case("salir") {
-        println!("❎ Operación cancelada.");
-        return;
+    if is_exit(&volume) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
     // 👉 Nombre del directorio dentro de /media
     let dir_name: String = Input::with_theme(&theme)
-        .with_prompt("Nombre del directorio para montar bajo /media (ej. vol_personal) o 'salir' para cancelar")
-        .interact_text()
-        .unwrap();
+        .with_prompt(t!("mount.dir_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
+
+    if is_exit(&dir_name) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+
+    let mount_point = mount(backend, server.trim(), volume.trim(), dir_name.trim())?;
 
-    if dir_name.trim().eq_ignore_ascii_case("salir") {
-        println!("❎ Operación cancelada.");
-        return;
+    // 🔐 Solicitar nombre de usuario
+    let username: String = Input::with_theme(&theme)
+        .with_prompt(t!("mount.owner_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
+
+    if is_exit(&username) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
+    set_owner(backend, &mount_point, username.trim())
+}
+
+/// Monta `servidor:/volumen` en `/media/<dir_name>` y devuelve el punto de montaje.
+pub fn mount(backend: &dyn GlusterBackend, server: &str, volume: &str, dir_name: &str) -> Result<String> {
     let mount_point = format!("/media/{}", dir_name);
-    let path = Path::new(&mount_point);
 
     // ✅ Crear el directorio si no existe
-    if !path.exists() {
-        println!("📁 La ruta no existe. Creando...");
-        if let Err(e) = fs::create_dir_all(&path) {
-            eprintln!("❌ No se pudo crear la ruta de montaje: {}", e);
-            return;
-        }
+    if !backend.exists(&mount_point) {
+        println!("{}", t!("mount.creating_dir"));
+        backend.create_dir_all(&mount_point).map_err(|e| t!("mount.create_dir_failed", error = e))?;
     }
 
     // 🚀 Ejecutar el comando de montaje
-    println!("🚀 Ejecutando comando:");
-    println!("sudo mount -t glusterfs {}:/{} {}", server, volume, mount_point);
-
-    let status = Command::new("sudo")
-        .arg("mount")
-        .arg("-t")
-        .arg("glusterfs")
-        .arg(format!("{}:/{}", server.trim(), volume))
-        .arg(&mount_point)
-        .status();
-
-    if let Ok(s) = status {
-        if s.success() {
-            println!("✅ Volumen montado exitosamente.");
-
-            // 🔐 Solicitar nombre de usuario
-            let username: String = Input::with_theme(&theme)
-                .with_prompt("🔒 ¿A qué usuario quieres dar permisos del punto de montaje? o 'salir'")
-                .interact_text()
-                .unwrap();
-
-            if username.trim().eq_ignore_ascii_case("salir") {
-                println!("❎ Operación cancelada.");
-                return;
-            }
+    backend
+        .sudo("mount", &["-t", "glusterfs", &format!("{}:/{}", server, volume), &mount_point])
+        .map_err(|e| e.context(&t!("mount.mount_failed")))?;
 
-            if get_user_by_name(&username).is_none() {
-                println!("❌ El usuario '{}' no existe en el sistema.", username);
-                return;
-            }
+    println!("{}", t!("mount.mounted"));
+    Ok(mount_point)
+}
 
-            let chown_status = Command::new("sudo")
-                .arg("chown")
-                .arg(format!("{}:{}", username, username))
-                .arg(&mount_point)
-                .status();
-
-            if let Ok(cs) = chown_status {
-                if cs.success() {
-                    println!("✅ Permisos cambiados a usuario: {}", username);
-                } else {
-                    println!("⚠️ No se pudo cambiar la propiedad del punto de montaje.");
-                }
-            }
-        } else {
-            println!("❌ Falló el montaje. Verifica que el volumen esté iniciado y que tengas permisos.");
-        }
-    } else {
-        println!("❌ Error al ejecutar el comando de montaje.");
+/// Asigna el punto de montaje al usuario (y grupo homónimo) indicado.
+pub fn set_owner(backend: &dyn GlusterBackend, mount_point: &str, username: &str) -> Result<()> {
+    if !backend.user_exists(username) {
+        return Err(Error::Message(t!("mount.unknown_user", user = username)));
     }
+
+    backend
+        .sudo("chown", &[&format!("{}:{}", username, username), mount_point])
+        .map_err(|_| t!("mount.chown_failed"))?;
+
+    println!("{}", t!("mount.owner_set", user = username));
+    Ok(())
 }
 
 fn is_protected_path(path: &str) -> bool {
@@ -115,84 +96,131 @@ fn is_protected_path(path: &str) -> bool {
     protected.contains(path)
 }
 
-pub fn manage_mounts() {
-    println!("\n🧰 Gestión de puntos de montaje en /media/");
+/// Montajes activos bajo /media/ como pares (origen, punto de montaje).
+pub fn media_mounts(backend: &dyn GlusterBackend) -> Result<Vec<(String, String)>> {
+    let mount_output = backend.command("mount", &[])?;
 
-    let theme = ColorfulTheme::default();
+    Ok(mount_output
+        .lines()
+        .filter(|line| line.contains(" /media/"))
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 3 {
+                Some((parts[0].to_string(), parts[2].to_string()))
+            } else {
+                None
+            }
+        })
+        .collect())
+}
 
-    let output = Command::new("mount")
-        .output()
-        .expect("❌ No se pudo ejecutar el comando `mount`");
+/// Desmonta `mount_path` y, si se pide y no es una ruta protegida, elimina el directorio.
+pub fn unmount(backend: &dyn GlusterBackend, mount_path: &str, remove_dir: bool) -> Result<()> {
+    println!("{}", t!("mount.unmounting", path = mount_path));
+    backend
+        .sudo("umount", &[mount_path])
+        .map_err(|e| e.context(&t!("mount.unmount_failed")))?;
+    println!("{}", t!("mount.unmounted"));
+
+    if remove_dir {
+        if is_protected_path(mount_path) {
+            println!("{}", t!("mount.protected_path"));
+        } else {
+            backend.remove_dir_all(mount_path).map_err(|e| t!("mount.remove_failed", error = e))?;
+            println!("{}", t!("mount.dir_removed"));
+        }
+    }
 
-    let mount_output = String::from_utf8_lossy(&output.stdout);
+    Ok(())
+}
 
-    let media_mounts: Vec<&str> = mount_output
-        .lines()
-        .filter(|line| line.contains(" /media/"))
-        .collect();
+pub fn manage_mounts(backend: &dyn GlusterBackend) -> Result<()> {
+    println!("\n{}", t!("mount.manage_title"));
+
+    let theme = ColorfulTheme::default();
+
+    let media_mounts = media_mounts(backend)?;
 
     if media_mounts.is_empty() {
-        println!("⚠️ No hay montajes activos en /media/");
-        return;
+        println!("{}", t!("mount.none_active"));
+        return Ok(());
     }
 
     let items: Vec<String> = media_mounts
         .iter()
-        .map(|line| {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 3 {
-                format!("{} (en {})", parts[0], parts[2])
-            } else {
-                line.to_string()
-            }
-        })
+        .map(|(source, target)| t!("mount.item", source = source, target = target))
         .collect();
 
     let selection = Select::with_theme(&theme)
-        .with_prompt("Selecciona un volumen a desmontar")
+        .with_prompt(t!("mount.unmount_prompt"))
         .items(&items)
         .default(0)
-        .interact()
-        .unwrap();
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    let mount_path = &media_mounts[selection].1;
+
+    unmount(backend, mount_path, false)?;
+
+    if is_protected_path(mount_path) {
+        println!("{}", t!("mount.protected_path"));
+        return Ok(());
+    }
 
-    let line = media_mounts[selection];
-    let parts: Vec<&str> = line.split_whitespace().collect();
+    let remove = Confirm::with_theme(&theme)
+        .with_prompt(t!("mount.remove_confirm", path = mount_path))
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
-    if parts.len() < 3 {
-        println!("❌ No se pudo interpretar el punto de montaje.");
-        return;
+    if remove {
+        if let Err(e) = backend.remove_dir_all(mount_path) {
+            println!("{}", t!("mount.remove_failed", error = e));
+        } else {
+            println!("{}", t!("mount.dir_removed"));
+        }
     }
+    Ok(())
+}
 
-    let mount_path = parts[2];
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
 
-    println!("🔽 Desmontando: {}", mount_path);
-    let umount_status = Command::new("sudo")
-        .arg("umount")
-        .arg(mount_path)
-        .status();
+    #[test]
+    fn mount_set_owner_and_unmount() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
 
-    match umount_status {
-        Ok(status) if status.success() => {
-            println!("✅ Desmontado correctamente.");
+        let mount_point = mount(&fake, "vm1", "datos", "vol").unwrap();
+        assert_eq!(mount_point, "/media/vol");
+        assert_eq!(
+            media_mounts(&fake).unwrap(),
+            vec![("vm1:/datos".to_string(), "/media/vol".to_string())]
+        );
 
-            if is_protected_path(mount_path) {
-                println!("🛡️ Ruta protegida. No se puede eliminar.");
-            } else {
-                let remove = Confirm::with_theme(&theme)
-                    .with_prompt(format!("¿Deseas eliminar el directorio {}?", mount_path))
-                    .default(false)
-                    .interact()
-                    .unwrap();
-
-                if remove {
-                    if let Err(e) = fs::remove_dir_all(Path::new(mount_path)) {
-                        println!("⚠️ No se pudo eliminar: {}", e);
-                    } else {
-                        println!("🗑️ Directorio eliminado.");
-                    }
-                }
-            }
-        }
-        _ => println!("❌ Falló el desmontaje."),
+        set_owner(&fake, &mount_point, "operador").unwrap();
+        assert!(set_owner(&fake, &mount_point, "nadie").is_err());
+
+        unmount(&fake, &mount_point, true).unwrap();
+        assert!(media_mounts(&fake).unwrap().is_empty());
+        assert!(!fake.exists("/media/vol"));
+    }
+
+    #[test]
+    fn mount_fails_when_volume_is_stopped() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Stopped);
+
+        assert!(mount(&fake, "vm1", "datos", "vol").is_err());
+        assert!(media_mounts(&fake).unwrap().is_empty());
+    }
+
+    #[test]
+    fn protected_paths_are_never_removed() {
+        let fake = FakeBackend::new().with_dir("/mnt");
+        fake.state.borrow_mut().mounts.push(("vm1:/datos".into(), "/mnt".into()));
+
+        unmount(&fake, "/mnt", true).unwrap();
+        assert!(fake.exists("/mnt"));
     }
 }
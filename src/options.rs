@@ -0,0 +1,503 @@
+//! Opciones de volumen: valores actuales (`volume get NOMBRE all`), catálogo con valores por
+//! defecto y descripciones (`volume set help`) y validación antes de llamar a `volume set`.
+
+use std::fmt;
+
+use console::style;
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+use crate::volinfo;
+use crate::xml;
+
+/// Entrada de `gluster volume set help`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionDoc {
+    pub name: String,
+    pub default: String,
+    pub description: String,
+}
+
+/// Opción de un volumen con su valor actual.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VolumeOption {
+    pub name: String,
+    pub value: String,
+    /// Valor por defecto según el catálogo, si aparece en él.
+    pub default: Option<String>,
+    /// Se cambió con `volume set` (aparece entre las opciones reconfiguradas del volumen).
+    pub changed: bool,
+}
+
+/// Tipo de valor que admite una opción.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OptionKind {
+    Bool,
+    Integer { min: i64, max: i64 },
+    /// Tamaño con sufijo opcional: `4096`, `64KB`, `256MB`, `1GB`.
+    Size,
+    /// Porcentaje de 0 a 100, con o sin `%`.
+    Percent,
+    /// Porcentaje o tamaño absoluto (`10%`, `10GB`), como `cluster.min-free-disk`.
+    PercentOrSize,
+    Choice(&'static [&'static str]),
+    Text,
+}
+
+const LOG_LEVELS: &[&str] = &["DEBUG", "WARNING", "ERROR", "INFO", "CRITICAL", "NONE", "TRACE"];
+
+/// Opciones habituales cuyo tipo y rango no se deducen del valor por defecto.
+const KNOWN: &[(&str, OptionKind)] = &[
+    ("auth.allow", OptionKind::Text),
+    ("auth.reject", OptionKind::Text),
+    ("cluster.data-self-heal-algorithm", OptionKind::Choice(&["full", "diff"])),
+    ("cluster.favorite-child-policy", OptionKind::Choice(&["none", "size", "ctime", "mtime", "majority"])),
+    ("cluster.force-migration", OptionKind::Bool),
+    ("cluster.heal-timeout", OptionKind::Integer { min: 5, max: i32::MAX as i64 }),
+    ("cluster.min-free-disk", OptionKind::PercentOrSize),
+    ("cluster.quorum-count", OptionKind::Integer { min: 1, max: i32::MAX as i64 }),
+    ("cluster.quorum-type", OptionKind::Choice(&["none", "auto", "fixed"])),
+    ("cluster.self-heal-daemon", OptionKind::Bool),
+    ("cluster.server-quorum-type", OptionKind::Choice(&["none", "server"])),
+    ("diagnostics.brick-log-level", OptionKind::Choice(LOG_LEVELS)),
+    ("diagnostics.client-log-level", OptionKind::Choice(LOG_LEVELS)),
+    ("features.shard", OptionKind::Bool),
+    ("features.shard-block-size", OptionKind::Size),
+    ("network.ping-timeout", OptionKind::Integer { min: 0, max: 1013 }),
+    ("nfs.disable", OptionKind::Bool),
+    ("performance.cache-refresh-timeout", OptionKind::Integer { min: 0, max: 61 }),
+    ("performance.cache-size", OptionKind::Size),
+    ("performance.io-thread-count", OptionKind::Integer { min: 1, max: 64 }),
+    ("performance.write-behind-window-size", OptionKind::Size),
+    ("storage.owner-gid", OptionKind::Integer { min: -1, max: i32::MAX as i64 }),
+    ("storage.owner-uid", OptionKind::Integer { min: -1, max: i32::MAX as i64 }),
+    ("transport.address-family", OptionKind::Choice(&["inet", "inet6"])),
+];
+
+const BOOL_WORDS: &[(&str, &str)] = &[
+    ("on", "on"),
+    ("off", "off"),
+    ("yes", "on"),
+    ("no", "off"),
+    ("true", "on"),
+    ("false", "off"),
+    ("enable", "on"),
+    ("disable", "off"),
+    ("1", "on"),
+    ("0", "off"),
+];
+
+impl OptionKind {
+    /// Tipo de una opción: el de la tabla de conocidas o el que sugiere su valor por defecto.
+    pub fn of(name: &str, default: Option<&str>) -> OptionKind {
+        if let Some((_, kind)) = KNOWN.iter().find(|(known, _)| *known == name) {
+            return *kind;
+        }
+        let Some(default) = default.map(str::trim) else {
+            return OptionKind::Text;
+        };
+        if BOOL_WORDS.iter().any(|(word, _)| default.eq_ignore_ascii_case(word)) && default.parse::<i64>().is_err() {
+            OptionKind::Bool
+        } else if default.ends_with('%') && parse_percent(default).is_some() {
+            OptionKind::Percent
+        } else if default.parse::<u64>().is_err() && parse_size(default).is_some() {
+            OptionKind::Size
+        } else {
+            // Los valores numéricos por defecto no dicen el rango admitido: se deja validar a gluster.
+            OptionKind::Text
+        }
+    }
+
+    /// Comprueba el valor y lo devuelve normalizado (`yes` → `on`, `info` → `INFO`, ...).
+    pub fn validate(&self, value: &str) -> std::result::Result<String, String> {
+        let value = value.trim();
+        let valid = match self {
+            OptionKind::Bool => BOOL_WORDS
+                .iter()
+                .find(|(word, _)| value.eq_ignore_ascii_case(word))
+                .map(|(_, normalized)| normalized.to_string()),
+            OptionKind::Integer { min, max } => value
+                .parse::<i64>()
+                .ok()
+                .filter(|n| (min..=max).contains(&n))
+                .map(|n| n.to_string()),
+            OptionKind::Size => parse_size(value).map(|_| value.to_uppercase()),
+            OptionKind::Percent => parse_percent(value).map(|_| value.to_string()),
+            // Sin `%` un número se interpreta como tamaño, igual que hace gluster.
+            OptionKind::PercentOrSize if value.ends_with('%') => parse_percent(value).map(|_| value.to_string()),
+            OptionKind::PercentOrSize => parse_size(value).map(|_| value.to_uppercase()),
+            OptionKind::Choice(choices) => choices
+                .iter()
+                .find(|choice| value.eq_ignore_ascii_case(choice))
+                .map(|choice| choice.to_string()),
+            OptionKind::Text => Some(value.to_string()).filter(|v| !v.is_empty()),
+        };
+        valid.ok_or_else(|| t!("options.invalid_value", value = value, expected = self))
+    }
+}
+
+impl fmt::Display for OptionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            OptionKind::Bool => t!("options.kind.bool"),
+            OptionKind::Integer { min, max } => t!("options.kind.integer", min = min, max = max),
+            OptionKind::Size => t!("options.kind.size"),
+            OptionKind::Percent => t!("options.kind.percent"),
+            OptionKind::PercentOrSize => t!("options.kind.percent_or_size"),
+            OptionKind::Choice(choices) => t!("options.kind.choice", choices = choices.join(", ")),
+            OptionKind::Text => t!("options.kind.text"),
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Bytes de `4096`, `64KB`, `256MB`, `1GB`, `2TB` (sin distinguir mayúsculas).
+pub fn parse_size(value: &str) -> Option<u64> {
+    let upper = value.trim().to_uppercase();
+    let digits = upper.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit = &upper[digits.len()..];
+    let factor: u64 = match unit {
+        "" | "B" => 1,
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        "TB" => 1 << 40,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok()?.checked_mul(factor)
+}
+
+fn parse_percent(value: &str) -> Option<f64> {
+    let number: f64 = value.trim().trim_end_matches('%').trim().parse().ok()?;
+    (0.0..=100.0).contains(&number).then_some(number)
+}
+
+/// Interpreta la salida de texto de `gluster volume set help`:
+///
+/// ```text
+/// Option: cluster.lookup-unhashed
+/// Default Value: on
+/// Description: This option if set to ON, does a lookup ...
+/// ```
+pub fn parse_help(text: &str) -> Vec<OptionDoc> {
+    let mut docs: Vec<OptionDoc> = vec![];
+    let mut in_description = false;
+
+    for line in text.lines() {
+        if let Some(name) = line.strip_prefix("Option:") {
+            docs.push(OptionDoc {
+                name: name.trim().to_string(),
+                default: String::new(),
+                description: String::new(),
+            });
+            in_description = false;
+            continue;
+        }
+        let Some(doc) = docs.last_mut() else { continue };
+
+        if let Some(default) = line.strip_prefix("Default Value:") {
+            doc.default = default.trim().to_string();
+        } else if let Some(description) = line.strip_prefix("Description:") {
+            doc.description = description.trim().to_string();
+            in_description = true;
+        } else if line.trim().is_empty() {
+            in_description = false;
+        } else if in_description {
+            // Las descripciones largas continúan en las líneas siguientes.
+            doc.description.push(' ');
+            doc.description.push_str(line.trim());
+        }
+    }
+    docs
+}
+
+/// Catálogo de opciones documentadas por gluster.
+pub fn help(backend: &dyn GlusterBackend) -> Result<Vec<OptionDoc>> {
+    let output = backend
+        .gluster(&["volume", "set", "help"])
+        .map_err(|e| e.context(&t!("options.help_failed")))?;
+    Ok(parse_help(&output))
+}
+
+/// Catálogo para una orden completa; se consulta una vez y se pasa a `list` y `set`.
+/// Sin catálogo (versiones antiguas) se sigue adelante, solo que sin valores por defecto.
+pub fn catalog(backend: &dyn GlusterBackend) -> Vec<OptionDoc> {
+    help(backend).unwrap_or_default()
+}
+
+/// Valores actuales de `gluster volume get NOMBRE all --xml`, en el orden de gluster.
+pub fn parse_values(xml_text: &str) -> Result<Vec<(String, String)>> {
+    let doc = xml::parse_cli_output(xml_text)?;
+    let Some(opts) = xml::descendant(doc.root_element(), "volGetopts") else {
+        return Err(Error::Parse(t!("xml.missing", tag = "volGetopts")));
+    };
+
+    Ok(xml::children(opts, "Opt")
+        .filter_map(|opt| {
+            let name = xml::child_text(opt, "Option")?;
+            Some((name.to_string(), xml::child_text(opt, "Value").unwrap_or_default().to_string()))
+        })
+        .collect())
+}
+
+/// Opciones del volumen con su valor, su valor por defecto y si se cambiaron.
+pub fn list(backend: &dyn GlusterBackend, docs: &[OptionDoc], volume: &str) -> Result<Vec<VolumeOption>> {
+    let output = backend
+        .gluster_xml(&["volume", "get", volume, "all"])
+        .map_err(|e| e.context(&t!("options.get_failed", name = volume)))?;
+    let values = parse_values(&output)?;
+    let changed = volinfo::fetch_one(backend, volume)?.options;
+
+    Ok(values
+        .into_iter()
+        .map(|(name, value)| VolumeOption {
+            default: docs.iter().find(|d| d.name == name).map(|d| d.default.clone()),
+            changed: changed.contains_key(&name),
+            name,
+            value,
+        })
+        .collect())
+}
+
+/// Valida `value` para `key`; falla si la opción no existe en el catálogo.
+pub fn validate(docs: &[OptionDoc], key: &str, value: &str) -> Result<String> {
+    let doc = docs.iter().find(|d| d.name == key);
+    let known = KNOWN.iter().any(|(name, _)| *name == key);
+    // Un catálogo vacío significa que no se pudo consultar: no se bloquea por nombre.
+    if doc.is_none() && !known && !docs.is_empty() {
+        return Err(Error::Message(t!("options.unknown", key = key)));
+    }
+
+    OptionKind::of(key, doc.map(|d| d.default.as_str()))
+        .validate(value)
+        .map_err(|reason| Error::Message(t!("options.invalid", key = key, reason = reason)))
+}
+
+/// Valida y cambia una opción del volumen.
+pub fn set(backend: &dyn GlusterBackend, docs: &[OptionDoc], volume: &str, key: &str, value: &str) -> Result<()> {
+    let value = validate(docs, key, value)?;
+    crate::volume::set_option(backend, volume, key, &value)
+}
+
+/// Devuelve una opción a su valor por defecto (`gluster volume reset`).
+pub fn reset(backend: &dyn GlusterBackend, volume: &str, key: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["volume", "reset", volume, key])
+        .map_err(|e| e.context(&t!("options.reset_failed", key = key)))?;
+    println!("{}", t!("options.reset_done", key = key));
+    Ok(())
+}
+
+/// Tabla de opciones; las cambiadas respecto al valor por defecto se resaltan.
+pub fn print_table(volume: &str, options: &[VolumeOption]) {
+    println!("{}", t!("options.title", name = volume));
+    if options.is_empty() {
+        println!("{}", t!("options.none_changed"));
+        return;
+    }
+
+    let width = options.iter().map(|o| o.name.len()).max().unwrap_or(6).max(6);
+    println!("   {:<width$}  {}", t!("options.column_option"), t!("options.column_value"));
+    for option in options {
+        if option.changed {
+            let default = option.default.as_deref().unwrap_or("-");
+            let line = format!("   {:<width$}  {}", option.name, option.value);
+            println!("{}  {}", style(line).yellow().bold(), t!("options.default_was", default = default));
+        } else {
+            println!("   {:<width$}  {}", option.name, option.value);
+        }
+    }
+}
+
+/// Pantalla de opciones de un volumen: consultar, cambiar y restablecer.
+pub fn manage_options(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+
+    let docs = catalog(backend);
+    loop {
+        let items = [
+            t!("options.menu_changed"),
+            t!("options.menu_all"),
+            t!("options.menu_set"),
+            t!("options.menu_reset"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("options.menu_title", name = volume))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => list(backend, &docs, &volume).map(|options| {
+                let changed: Vec<VolumeOption> = options.into_iter().filter(|o| o.changed).collect();
+                print_table(&volume, &changed);
+            }),
+            1 => list(backend, &docs, &volume).map(|options| print_table(&volume, &options)),
+            2 => set_interactive(backend, &theme, &docs, &volume),
+            3 => reset_interactive(backend, &theme, &docs, &volume),
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+fn set_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, docs: &[OptionDoc], volume: &str) -> Result<()> {
+    let options = list(backend, docs, volume)?;
+
+    let search: String = Input::with_theme(theme)
+        .with_prompt(t!("options.search_prompt"))
+        .interact_text()?;
+    let search = search.trim().to_lowercase();
+    let matches: Vec<&VolumeOption> = options.iter().filter(|o| o.name.to_lowercase().contains(&search)).collect();
+
+    let option = match matches.as_slice() {
+        [] => {
+            println!("{}", t!("options.no_match", search = search));
+            return Ok(());
+        }
+        [only] => *only,
+        many => {
+            let names: Vec<&str> = many.iter().map(|o| o.name.as_str()).collect();
+            let index = Select::with_theme(theme)
+                .with_prompt(t!("options.pick_prompt", count = names.len()))
+                .items(&names)
+                .default(0)
+                .max_length(15)
+                .interact_opt()?
+                .ok_or(Error::Cancelled)?;
+            many[index]
+        }
+    };
+
+    let doc = docs.iter().find(|d| d.name == option.name);
+    let kind = OptionKind::of(&option.name, doc.map(|d| d.default.as_str()));
+    println!("\n⚙️ {}", style(&option.name).bold());
+    if let Some(doc) = doc.filter(|d| !d.description.is_empty()) {
+        println!("   {}", doc.description);
+    }
+    println!("{}", t!("options.current", value = option.value, default = option.default.as_deref().unwrap_or("-")));
+    println!("{}", t!("options.expected", kind = kind));
+
+    let value: String = Input::with_theme(theme)
+        .with_prompt(t!("options.value_prompt"))
+        .validate_with(|input: &String| kind.validate(input).map(|_| ()))
+        .interact_text()?;
+    let value = kind.validate(&value).map_err(Error::Message)?;
+
+    if !Confirm::with_theme(theme)
+        .with_prompt(t!("options.set_confirm", key = option.name, value = value, name = volume))
+        .default(true)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    crate::volume::set_option(backend, volume, &option.name, &value)
+}
+
+fn reset_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, docs: &[OptionDoc], volume: &str) -> Result<()> {
+    let changed: Vec<VolumeOption> = list(backend, docs, volume)?.into_iter().filter(|o| o.changed).collect();
+    if changed.is_empty() {
+        println!("{}", t!("options.none_changed"));
+        return Ok(());
+    }
+
+    let items: Vec<String> = changed.iter().map(|o| format!("{} = {}", o.name, o.value)).collect();
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("options.reset_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let option = &changed[index];
+
+    if !Confirm::with_theme(theme)
+        .with_prompt(t!(
+            "options.reset_confirm",
+            key = option.name,
+            default = option.default.as_deref().unwrap_or("-")
+        ))
+        .default(true)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    reset(backend, volume, &option.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    #[test]
+    fn parses_help_with_multiline_descriptions() {
+        let docs = parse_help(
+            "Option: cluster.lookup-unhashed\nDefault Value: on\nDescription: This option if set to ON,\ndoes a lookup.\n\n\
+             Option: performance.cache-size\nDefault Value: 32MB\nDescription: Size of the read cache.\n",
+        );
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].description, "This option if set to ON, does a lookup.");
+        assert_eq!(docs[1].default, "32MB");
+    }
+
+    #[test]
+    fn validates_by_kind() {
+        assert_eq!(OptionKind::Bool.validate("Yes"), Ok("on".to_string()));
+        assert!(OptionKind::Bool.validate("quizás").is_err());
+        assert_eq!(OptionKind::of("network.ping-timeout", Some("42")).validate("10"), Ok("10".to_string()));
+        assert!(OptionKind::of("network.ping-timeout", Some("42")).validate("2000").is_err());
+        assert_eq!(OptionKind::of("performance.cache-size", None).validate("256mb"), Ok("256MB".to_string()));
+        assert!(OptionKind::Size.validate("256 megas").is_err());
+        assert!(OptionKind::Percent.validate("120%").is_err());
+        let min_free = OptionKind::of("cluster.min-free-disk", Some("10%"));
+        assert_eq!(min_free.validate("10gb"), Ok("10GB".to_string()));
+        assert_eq!(min_free.validate("5%"), Ok("5%".to_string()));
+        assert!(min_free.validate("120%").is_err());
+        assert_eq!(OptionKind::of("diagnostics.brick-log-level", None).validate("info"), Ok("INFO".to_string()));
+
+        // Sin tabla, el tipo sale del valor por defecto.
+        assert_eq!(OptionKind::of("performance.readdir-ahead", Some("on")), OptionKind::Bool);
+        assert_eq!(OptionKind::of("cluster.min-free-inodes", Some("5%")), OptionKind::Percent);
+        assert_eq!(OptionKind::of("performance.rda-cache-limit", Some("10MB")), OptionKind::Size);
+        assert_eq!(OptionKind::of("features.read-only", Some("128")), OptionKind::Text);
+    }
+
+    #[test]
+    fn lists_values_and_marks_changed_options() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        let docs = catalog(&fake);
+
+        set(&fake, &docs, "datos", "performance.cache-size", "256mb").unwrap();
+        assert!(set(&fake, &docs, "datos", "network.ping-timeout", "-5").is_err());
+        assert!(set(&fake, &docs, "datos", "performance.cache-sise", "256MB").is_err());
+
+        let options = list(&fake, &docs, "datos").unwrap();
+        let cache = options.iter().find(|o| o.name == "performance.cache-size").unwrap();
+        assert_eq!((cache.value.as_str(), cache.default.as_deref(), cache.changed), ("256MB", Some("32MB"), true));
+        assert!(options.iter().filter(|o| o.name != "performance.cache-size").all(|o| !o.changed));
+
+        reset(&fake, "datos", "performance.cache-size").unwrap();
+        assert!(list(&fake, &docs, "datos").unwrap().iter().all(|o| !o.changed));
+        assert_eq!(
+            fake.commands(),
+            vec![
+                "gluster --mode=script volume set datos performance.cache-size 256MB",
+                "gluster --mode=script volume reset datos performance.cache-size",
+            ]
+        );
+    }
+}
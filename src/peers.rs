@@ -1,34 +1,243 @@
-use dialoguer::{Input, theme::ColorfulTheme};
-use std::process::Command;
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
 
-pub fn add_peer() {
-    println!("\n➕ Añadir nuevo peer (nodo) al cluster");
+use crate::backend::GlusterBackend;
+use crate::error::{self, Error, Result};
+use crate::i18n::is_exit;
+use crate::t;
+use crate::pool::{self, Peer};
+
+pub fn add_peer(backend: &dyn GlusterBackend) -> Result<()> {
+    println!("\n{}", t!("peers.add_title"));
 
     let theme = ColorfulTheme::default();
 
+    println!("{}", t!("peers.current"));
+    pool::print_table(&list(backend));
+
     let peer_host: String = Input::with_theme(&theme)
-        .with_prompt("Hostname o IP del nodo a añadir (o escribe 'salir' para cancelar)")
-        .interact_text()
-        .unwrap();
+        .with_prompt(t!("peers.host_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
+
+    if is_exit(&peer_host) {
+        println!("{}", t!("error.cancelled"));
+        return Ok(());
+    }
+
+    probe(backend, peer_host.trim())
+}
+
+/// Peers remotos conocidos; en caso de error lo informa y devuelve una lista vacía.
+pub fn list(backend: &dyn GlusterBackend) -> Vec<Peer> {
+    match pool::peers(backend) {
+        Ok(peers) => peers,
+        Err(e) => {
+            eprintln!("{}", t!("peers.list_failed", error = e));
+            vec![]
+        }
+    }
+}
+
+/// Añade un nodo al pool de confianza con `gluster peer probe`.
+pub fn probe(backend: &dyn GlusterBackend, peer_host: &str) -> Result<()> {
+    match backend.sudo_gluster(&["peer", "probe", peer_host]) {
+        Ok(_) => {
+            println!("{}", t!("peers.probed", host = peer_host));
+            if let Some(peer) = list(backend).into_iter().find(|p| p.matches(peer_host)) {
+                pool::print_table(&[peer]);
+            }
+            Ok(())
+        }
+        Err(e) => Err(e.context(&t!("peers.probe_failed", host = peer_host))),
+    }
+}
+
+/// Bricks que `peer` todavía aloja, agrupados por volumen.
+pub fn hosted_bricks(backend: &dyn GlusterBackend, peer: &Peer) -> Result<Vec<(String, Vec<String>)>> {
+    Ok(crate::volinfo::fetch(backend, None)?
+        .into_iter()
+        .filter_map(|volume| {
+            let bricks: Vec<String> = volume
+                .brick_names()
+                .into_iter()
+                .filter(|b| peer.matches(crate::placement::brick_host(b)))
+                .collect();
+            (!bricks.is_empty()).then_some((volume.name, bricks))
+        })
+        .collect())
+}
+
+fn print_hosted(host: &str, hosted: &[(String, Vec<String>)]) {
+    println!("{}", t!("peers.hosts_bricks", host = host));
+    for (volume, bricks) in hosted {
+        println!("{}", t!("peers.hosted_line", name = volume, bricks = bricks.join(", ")));
+    }
+}
+
+/// Peer del pool con ese nombre o IP.
+fn find(backend: &dyn GlusterBackend, host: &str) -> Result<Peer> {
+    pool::peers(backend)?
+        .into_iter()
+        .find(|p| p.matches(host))
+        .ok_or_else(|| Error::Message(t!("peers.unknown_host", host = host)))
+}
+
+/// Saca un nodo del pool con `gluster peer detach`. Se niega si aloja bricks (gluster también lo
+/// rechaza) y, si no está conectado, solo lo hace con `force`.
+pub fn detach(backend: &dyn GlusterBackend, host: &str, force: bool) -> Result<()> {
+    let peer = find(backend, host)?;
+    pool::print_table(std::slice::from_ref(&peer));
+
+    let hosted = hosted_bricks(backend, &peer)?;
+    if !hosted.is_empty() {
+        print_hosted(host, &hosted);
+        return Err(Error::Message(t!("peers.migrate_first", host = host)));
+    }
+    if !peer.connected && !force {
+        return Err(Error::Message(t!("peers.disconnected", host = host)));
+    }
 
-    if peer_host.trim().eq_ignore_ascii_case("salir") {
-        println!("❎ Operación cancelada por el usuario.");
-        return;
+    let mut args = vec!["peer", "detach", host];
+    if force {
+        args.push("force");
     }
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("peers.detach_failed", host = host)))?;
+    println!("{}", t!("peers.detached", host = host));
+    Ok(())
+}
+
+fn confirm(theme: &ColorfulTheme, prompt: &str) -> Result<bool> {
+    Confirm::with_theme(theme)
+        .with_prompt(prompt)
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)
+}
+
+/// Asistente para quitar un nodo: si aloja bricks ofrece moverlos antes de intentarlo.
+pub fn detach_peer(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let peers = list(backend);
+    if peers.is_empty() {
+        println!("{}", t!("pool.empty"));
+        return Ok(());
+    }
+    pool::print_table(&peers);
+
+    let items: Vec<String> = peers.iter().map(|p| p.hostname().to_string()).collect();
+    let index = Select::with_theme(&theme)
+        .with_prompt(t!("peers.detach_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let peer = &peers[index];
+    let host = peer.hostname();
+
+    loop {
+        let hosted = hosted_bricks(backend, peer)?;
+        if hosted.is_empty() {
+            break;
+        }
+        print_hosted(host, &hosted);
+        let options = [t!("peers.migrate_remove"), t!("peers.migrate_replace"), t!("menu.exit")];
+        let choice = Select::with_theme(&theme)
+            .with_prompt(t!("peers.migrate_prompt"))
+            .items(&options)
+            .default(0)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?;
+        match choice {
+            0 => error::report(crate::volume::remove_bricks(backend)),
+            1 => error::report(crate::replace::manage_replace(backend)),
+            _ => {
+                println!("{}", t!("common.cancelled"));
+                return Ok(());
+            }
+        }
+    }
+
+    // Un nodo caído solo se puede quitar con force, y eso merece su propio aviso.
+    let force = !peer.connected;
+    if force {
+        println!("{}", t!("peers.force_warning", host = host));
+        if !confirm(&theme, &t!("peers.force_confirm", host = host))? {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
+        }
+    } else if !confirm(&theme, &t!("peers.detach_confirm", host = host))? {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    detach(backend, host, force)
+}
 
-    println!("🔧 Ejecutando: sudo gluster peer probe {}", peer_host);
+pub fn manage_peers(backend: &dyn GlusterBackend) -> Result<()> {
+    loop {
+        println!("\n{}", t!("peers.menu_title"));
+
+        let options = [t!("peers.menu_add"), t!("peers.menu_detach"), t!("menu.exit")];
+        // Esc vuelve al menú principal
+        let Some(selection) = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("common.choose_option"))
+            .items(&options)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        match selection {
+            0 => error::report(add_peer(backend)),
+            1 => error::report(detach_peer(backend)),
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    #[test]
+    fn probe_adds_peer_to_pool() {
+        let fake = FakeBackend::new();
+
+        probe(&fake, "vm2").unwrap();
+        let peers = list(&fake);
+        assert_eq!(peers.len(), 1);
+        assert!(peers[0].matches("vm2") && peers[0].is_healthy());
+        assert_eq!(fake.commands(), vec!["gluster --mode=script peer probe vm2"]);
+    }
+
+    #[test]
+    fn detach_refuses_peers_with_bricks() {
+        let fake = FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started);
+
+        let peer = find(&fake, "vm2").unwrap();
+        assert_eq!(hosted_bricks(&fake, &peer).unwrap(), vec![("datos".to_string(), vec!["vm2:/gluster/a".to_string()])]);
+        assert!(detach(&fake, "vm2", false).is_err());
+        assert!(detach(&fake, "vm2", true).is_err());
+        assert!(fake.commands().is_empty());
+    }
 
-    let status = Command::new("sudo")
-        .arg("gluster")
-        .arg("peer")
-        .arg("probe")
-        .arg(&peer_host)
-        .status()
-        .expect("❌ Error ejecutando gluster peer probe");
+    #[test]
+    fn disconnected_peers_need_force() {
+        let fake = FakeBackend::new().with_peer("vm2", true).with_peer("vm3", false);
 
-    if status.success() {
-        println!("✅ Nodo '{}' añadido correctamente al cluster.", peer_host);
-    } else {
-        println!("❌ No se pudo añadir el nodo '{}'. Revisa la conexión y que el nodo esté disponible.", peer_host);
+        assert!(detach(&fake, "vm9", false).is_err());
+        assert!(detach(&fake, "vm3", false).is_err());
+        detach(&fake, "vm3", true).unwrap();
+        detach(&fake, "vm2", false).unwrap();
+        assert!(list(&fake).is_empty());
+        assert_eq!(
+            fake.commands(),
+            vec!["gluster --mode=script peer detach vm3 force", "gluster --mode=script peer detach vm2"]
+        );
     }
 }
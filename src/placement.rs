@@ -0,0 +1,187 @@
+//! Reparto de bricks entre nodos: cada conjunto de réplica o dispersión debería quedar
+//! en nodos distintos para que la caída de un nodo no se lleve varias copias del mismo dato.
+
+use std::collections::VecDeque;
+
+use crate::layout::VolumeLayout;
+use crate::pool::Peer;
+use crate::t;
+
+/// Nodo de un brick `host:/ruta`.
+pub fn brick_host(brick: &str) -> &str {
+    brick.split_once(':').map(|(host, _)| host).unwrap_or(brick)
+}
+
+/// Orden propuesto para los bricks y los avisos que quedan con ese orden.
+#[derive(Debug, PartialEq)]
+pub struct Placement {
+    pub bricks: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl Placement {
+    /// Todos los conjuntos quedan en nodos distintos.
+    pub fn is_spread(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// Reordena los bricks para que cada conjunto use nodos distintos. En cada conjunto se toma
+/// primero un brick de los nodos con más bricks pendientes, que es lo que evita quedarse al final
+/// con varios bricks del mismo nodo; si no hay nodos suficientes se repite y se avisa.
+pub fn arrange(layout: &VolumeLayout, bricks: &[String]) -> Placement {
+    let mut hosts: Vec<(&str, VecDeque<&String>)> = vec![];
+    for brick in bricks {
+        let host = brick_host(brick);
+        match hosts.iter_mut().find(|(h, _)| *h == host) {
+            Some((_, pending)) => pending.push_back(brick),
+            None => hosts.push((host, VecDeque::from([brick]))),
+        }
+    }
+
+    let set_size = layout.set_size();
+    let mut ordered = Vec::with_capacity(bricks.len());
+    while ordered.len() < bricks.len() {
+        // Orden estable: a igual número de pendientes se respeta el orden en que se escribieron.
+        let mut candidates: Vec<usize> = (0..hosts.len()).filter(|&i| !hosts[i].1.is_empty()).collect();
+        candidates.sort_by_key(|&i| std::cmp::Reverse(hosts[i].1.len()));
+
+        let mut set = 0;
+        for &i in candidates.iter().cycle().take(set_size.min(bricks.len() - ordered.len())) {
+            let brick = match hosts[i].1.pop_front() {
+                Some(brick) => brick,
+                // Menos nodos que bricks por conjunto: se completa con el nodo que más bricks conserve.
+                None => match hosts.iter_mut().max_by_key(|(_, p)| p.len()).and_then(|(_, p)| p.pop_front()) {
+                    Some(brick) => brick,
+                    None => break,
+                },
+            };
+            ordered.push(brick.clone());
+            set += 1;
+        }
+        if set == 0 {
+            break;
+        }
+    }
+
+    Placement {
+        warnings: check_spread(layout, &ordered),
+        bricks: ordered,
+    }
+}
+
+/// Avisos de los conjuntos que repiten nodo, en el orden dado.
+pub fn check_spread(layout: &VolumeLayout, bricks: &[String]) -> Vec<String> {
+    if layout.set_size() == 1 {
+        return vec![];
+    }
+
+    let mut warnings = vec![];
+    for (i, set) in layout.sets(bricks).iter().enumerate() {
+        let mut seen: Vec<&str> = vec![];
+        for brick in set.iter() {
+            let host = brick_host(brick);
+            if seen.contains(&host) {
+                continue;
+            }
+            seen.push(host);
+
+            let count = set.iter().filter(|b| brick_host(b) == host).count() as u32;
+            if count > 1 {
+                let key = if count > layout.fault_tolerance() {
+                    "placement.same_host_data_loss"
+                } else {
+                    "placement.same_host"
+                };
+                warnings.push(t!(key, set = i + 1, host = host, count = count));
+            }
+        }
+    }
+    warnings
+}
+
+/// Avisos de bricks en nodos que no están en el pool o no están sanos.
+pub fn check_hosts(bricks: &[String], peers: &[Peer], local: &str) -> Vec<String> {
+    let mut warnings = vec![];
+    let mut checked: Vec<&str> = vec![];
+
+    for brick in bricks {
+        let host = brick_host(brick);
+        if checked.contains(&host) || host == local || host == "localhost" {
+            continue;
+        }
+        checked.push(host);
+
+        match peers.iter().find(|p| p.matches(host)) {
+            None => warnings.push(t!("placement.unknown_host", host = host)),
+            Some(peer) if !peer.is_healthy() => {
+                warnings.push(t!("placement.unhealthy_host", host = host, state = peer.state))
+            }
+            Some(_) => {}
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bricks(list: &[&str]) -> Vec<String> {
+        list.iter().map(|b| b.to_string()).collect()
+    }
+
+    fn hosts(bricks: &[String]) -> Vec<&str> {
+        bricks.iter().map(|b| brick_host(b)).collect()
+    }
+
+    #[test]
+    fn spreads_replica_sets_across_hosts() {
+        let layout = VolumeLayout::Replicate { replica: 3, arbiter: 0 };
+        // Escritos nodo por nodo, como suele hacerlo quien prepara los discos.
+        let input = bricks(&[
+            "vm1:/gluster/a", "vm1:/gluster/b", "vm2:/gluster/a", "vm2:/gluster/b", "vm3:/gluster/a", "vm3:/gluster/b",
+        ]);
+        assert_eq!(check_spread(&layout, &input).len(), 2);
+
+        let placement = arrange(&layout, &input);
+        assert!(placement.is_spread(), "{:?}", placement.warnings);
+        assert_eq!(hosts(&placement.bricks), vec!["vm1", "vm2", "vm3", "vm1", "vm2", "vm3"]);
+    }
+
+    #[test]
+    fn hosts_with_more_bricks_go_first() {
+        let layout = VolumeLayout::Replicate { replica: 2, arbiter: 0 };
+        let input = bricks(&["vm1:/a", "vm2:/a", "vm3:/a", "vm3:/b"]);
+
+        let placement = arrange(&layout, &input);
+        assert!(placement.is_spread(), "{:?}", placement.warnings);
+        assert_eq!(placement.bricks.len(), 4);
+        assert!(placement.bricks.iter().all(|b| input.contains(b)));
+    }
+
+    #[test]
+    fn warns_when_hosts_are_not_enough() {
+        let input = bricks(&["vm1:/a", "vm1:/b", "vm2:/a"]);
+
+        let replica = arrange(&VolumeLayout::Replicate { replica: 3, arbiter: 0 }, &input);
+        assert_eq!(replica.warnings.len(), 1);
+        assert!(replica.warnings[0].contains("vm1"));
+
+        // Dos fragmentos en un nodo con redundancia 1: perder ese nodo pierde datos.
+        let disperse = check_spread(&VolumeLayout::Disperse { disperse: 3, redundancy: 1 }, &input);
+        assert_eq!(disperse, vec![t!("placement.same_host_data_loss", set = 1, host = "vm1", count = 2)]);
+        assert!(arrange(&VolumeLayout::Distribute, &input).is_spread());
+    }
+
+    #[test]
+    fn unknown_and_unhealthy_hosts_are_reported() {
+        let peers = vec![
+            Peer { uuid: "a".into(), hostnames: vec!["vm2".into()], state: "Peer in Cluster".into(), connected: true },
+            Peer { uuid: "b".into(), hostnames: vec!["vm3".into()], state: "Peer in Cluster".into(), connected: false },
+        ];
+        let warnings = check_hosts(&bricks(&["vm1:/a", "vm2:/a", "vm3:/a", "vm9:/a", "vm9:/b"]), &peers, "vm1");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("vm3") && warnings[1].contains("vm9"));
+    }
+}
@@ -0,0 +1,189 @@
+//! Modelo tipado del pool de confianza (`gluster peer status --xml` / `pool list --xml`).
+
+use console::style;
+
+use crate::backend::GlusterBackend;
+use crate::error::Result;
+use crate::t;
+use crate::xml;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Peer {
+    pub uuid: String,
+    /// Nombre principal primero, seguido de los nombres/IPs alternativos.
+    pub hostnames: Vec<String>,
+    /// Estado tal como lo informa gluster (`Peer in Cluster`, `Peer Rejected`, ...).
+    pub state: String,
+    pub connected: bool,
+}
+
+impl Peer {
+    pub fn hostname(&self) -> &str {
+        self.hostnames.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Indica si `host` es cualquiera de los nombres o IPs del peer.
+    pub fn matches(&self, host: &str) -> bool {
+        self.hostnames.iter().any(|h| h == host)
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.state.contains("Rejected")
+    }
+
+    /// Conectado y aceptado en el clúster: puede alojar bricks.
+    pub fn is_healthy(&self) -> bool {
+        self.connected && self.state == "Peer in Cluster"
+    }
+}
+
+/// Peers remotos (`peer status`), sin incluir el nodo local.
+pub fn peers(backend: &dyn GlusterBackend) -> Result<Vec<Peer>> {
+    parse(&backend.gluster_xml(&["peer", "status"])?)
+}
+
+/// Pool completo (`pool list`), incluido el nodo local como `localhost`.
+pub fn pool(backend: &dyn GlusterBackend) -> Result<Vec<Peer>> {
+    parse(&backend.gluster_xml(&["pool", "list"])?)
+}
+
+pub fn parse(output: &str) -> Result<Vec<Peer>> {
+    let doc = xml::parse_cli_output(output)?;
+
+    let Some(status) = xml::descendant(doc.root_element(), "peerStatus") else {
+        return Ok(vec![]);
+    };
+
+    Ok(xml::children(status, "peer").map(parse_peer).collect())
+}
+
+fn parse_peer(node: roxmltree::Node) -> Peer {
+    let mut hostnames: Vec<String> = xml::child_text(node, "hostname")
+        .map(|h| vec![h.to_string()])
+        .unwrap_or_default();
+
+    if let Some(alternatives) = xml::child(node, "hostnames") {
+        for name in xml::children(alternatives, "hostname").filter_map(|n| n.text()) {
+            let name = name.trim().to_string();
+            if !name.is_empty() && !hostnames.contains(&name) {
+                hostnames.push(name);
+            }
+        }
+    }
+
+    Peer {
+        uuid: xml::child_text(node, "uuid").unwrap_or_default().to_string(),
+        hostnames,
+        // `pool list` no trae stateStr para el nodo local
+        state: xml::child_text(node, "stateStr")
+            .unwrap_or("Peer in Cluster")
+            .to_string(),
+        connected: xml::child_text(node, "connected") == Some("1"),
+    }
+}
+
+/// Tabla de peers; los desconectados o rechazados se resaltan en rojo.
+pub fn print_table(peers: &[Peer]) {
+    if peers.is_empty() {
+        println!("{}", t!("pool.empty"));
+        return;
+    }
+
+    let width = peers.iter().map(|p| p.hostname().len()).max().unwrap_or(4).max(4);
+    println!(
+        "   {:<width$}  {:<36}  {:<12}  {}",
+        t!("pool.host"),
+        "UUID",
+        t!("pool.connection"),
+        t!("pool.state")
+    );
+
+    for peer in peers {
+        let connection = if peer.connected { t!("pool.connected") } else { t!("pool.disconnected_state") };
+        let mut line = format!(
+            "   {:<width$}  {:<36}  {:<12}  {}",
+            peer.hostname(),
+            peer.uuid,
+            connection,
+            peer.state
+        );
+        if peer.hostnames.len() > 1 {
+            line.push_str(&t!("pool.also", names = peer.hostnames[1..].join(", ")));
+        }
+
+        if peer.is_healthy() {
+            println!("{}", line);
+        } else {
+            println!("{}", style(line).red().bold());
+        }
+    }
+
+    let disconnected = peers.iter().filter(|p| !p.connected).count();
+    let rejected = peers.iter().filter(|p| p.is_rejected()).count();
+    if disconnected > 0 {
+        println!("{}", style(t!("pool.disconnected", count = disconnected)).red());
+    }
+    if rejected > 0 {
+        println!("{}", style(t!("pool.rejected", count = rejected)).red().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEERS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cliOutput>
+  <opRet>0</opRet>
+  <opErrno>0</opErrno>
+  <opErrstr/>
+  <peerStatus>
+    <peer>
+      <uuid>4a1e7a3c-0000-4000-8000-000000000002</uuid>
+      <hostname>vm2</hostname>
+      <hostnames>
+        <hostname>vm2</hostname>
+        <hostname>192.168.1.11</hostname>
+      </hostnames>
+      <connected>1</connected>
+      <state>3</state>
+      <stateStr>Peer in Cluster</stateStr>
+    </peer>
+    <peer>
+      <uuid>4a1e7a3c-0000-4000-8000-000000000003</uuid>
+      <hostname>vm3</hostname>
+      <hostnames>
+        <hostname>vm3</hostname>
+      </hostnames>
+      <connected>0</connected>
+      <state>6</state>
+      <stateStr>Peer Rejected</stateStr>
+    </peer>
+  </peerStatus>
+</cliOutput>"#;
+
+    #[test]
+    fn parses_peers_with_alternate_names() {
+        let peers = parse(PEERS).unwrap();
+        assert_eq!(peers.len(), 2);
+
+        assert_eq!(peers[0].hostname(), "vm2");
+        assert_eq!(peers[0].hostnames, vec!["vm2", "192.168.1.11"]);
+        assert!(peers[0].matches("192.168.1.11"));
+        assert!(peers[0].is_healthy());
+
+        assert!(!peers[1].connected);
+        assert!(peers[1].is_rejected());
+        assert!(!peers[1].is_healthy());
+    }
+
+    #[test]
+    fn pool_list_includes_localhost() {
+        use crate::fake::FakeBackend;
+
+        let fake = FakeBackend::new().with_peer("vm2", true);
+        let pool = pool(&fake).unwrap();
+        assert_eq!(pool.iter().map(Peer::hostname).collect::<Vec<_>>(), vec!["vm2", "localhost"]);
+        assert_eq!(peers(&fake).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,357 @@
+//! Perfiles de opciones: los grupos que trae gluster (`/var/lib/glusterd/groups`, se aplican con
+//! `volume set NOMBRE group virt`) y los perfiles propios que guarda la herramienta.
+//!
+//! Ambos usan el formato de los grupos de gluster, una opción por línea:
+//!
+//! ```text
+//! # Ajustes para bases de datos
+//! performance.stat-prefetch=off
+//! network.ping-timeout=10
+//! ```
+
+use std::fmt;
+
+use console::style;
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::options::{self, OptionDoc, VolumeOption};
+use crate::t;
+
+pub const GROUPS_DIR: &str = "/var/lib/glusterd/groups";
+pub const PROFILES_DIR: &str = "/etc/cluster_almacenamiento/profiles";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProfileSource {
+    /// Grupo de gluster: lo aplica gluster con `group NOMBRE`.
+    Group,
+    /// Perfil propio: la herramienta aplica y valida cada opción.
+    Custom,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub source: ProfileSource,
+    pub options: Vec<(String, String)>,
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self.source {
+            ProfileSource::Group => t!("profiles.source_group"),
+            ProfileSource::Custom => t!("profiles.source_custom"),
+        };
+        write!(f, "{} ({}, {})", self.name, source, t!("profiles.option_count", count = self.options.len()))
+    }
+}
+
+/// Diferencia entre el valor de una opción en el volumen y en el perfil.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionDiff {
+    pub key: String,
+    /// `None` si el volumen no tiene esa opción (nombre mal escrito o de otra versión).
+    pub current: Option<String>,
+    pub wanted: String,
+}
+
+impl OptionDiff {
+    pub fn differs(&self) -> bool {
+        self.current.as_deref() != Some(self.wanted.as_str())
+    }
+}
+
+/// Lee `clave=valor` por línea, ignorando líneas vacías y comentarios.
+pub fn parse(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Un nombre de perfil se usa como nombre de fichero: solo letras, números, `-` y `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn read_dir(backend: &dyn GlusterBackend, dir: &str, source: ProfileSource) -> Vec<Profile> {
+    // Un directorio que no existe simplemente no aporta perfiles.
+    let mut names = backend.list_files(dir).unwrap_or_default();
+    names.sort();
+    names
+        .into_iter()
+        .filter(|name| is_valid_name(name))
+        .filter_map(|name| {
+            let text = backend.read_file(&format!("{}/{}", dir, name)).ok()?;
+            Some(Profile { name, source, options: parse(&text) })
+        })
+        .collect()
+}
+
+/// Grupos de gluster y perfiles propios; un perfil propio con el mismo nombre que un grupo lo oculta.
+pub fn list(backend: &dyn GlusterBackend) -> Vec<Profile> {
+    let custom = read_dir(backend, PROFILES_DIR, ProfileSource::Custom);
+    let mut profiles: Vec<Profile> = read_dir(backend, GROUPS_DIR, ProfileSource::Group)
+        .into_iter()
+        .filter(|group| !custom.iter().any(|c| c.name == group.name))
+        .collect();
+    profiles.extend(custom);
+    profiles
+}
+
+pub fn find(backend: &dyn GlusterBackend, name: &str) -> Result<Profile> {
+    list(backend)
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| Error::Message(t!("profiles.not_found", name = name, groups = GROUPS_DIR, custom = PROFILES_DIR)))
+}
+
+/// Compara cada opción del perfil con su valor actual en el volumen.
+pub fn diff(profile: &Profile, current: &[VolumeOption]) -> Vec<OptionDiff> {
+    profile
+        .options
+        .iter()
+        .map(|(key, wanted)| OptionDiff {
+            key: key.clone(),
+            current: current.iter().find(|o| o.name == *key).map(|o| o.value.clone()),
+            wanted: wanted.clone(),
+        })
+        .collect()
+}
+
+pub fn print_diff(volume: &str, profile: &Profile, diffs: &[OptionDiff]) {
+    println!("{}", t!("profiles.diff_title", name = volume, profile = profile.name));
+    let width = diffs.iter().map(|d| d.key.len()).max().unwrap_or(0);
+    for diff in diffs {
+        match &diff.current {
+            Some(current) if !diff.differs() => {
+                println!("{}", style(format!("   = {:<width$}  {}", diff.key, current)).dim());
+            }
+            Some(current) => {
+                println!("{}", style(format!("   ≠ {:<width$}  {} → {}", diff.key, current, diff.wanted)).yellow().bold());
+            }
+            None => {
+                println!("{}", style(format!("   ? {:<width$}  {}", diff.key, t!("profiles.unknown_option", value = diff.wanted))).red());
+            }
+        }
+    }
+    let changes = diffs.iter().filter(|d| d.differs()).count();
+    println!("{}", t!("profiles.diff_summary", count = changes, total = diffs.len()));
+}
+
+/// Aplica un perfil: los grupos con `volume set NOMBRE group`, los propios opción a opción.
+pub fn apply(
+    backend: &dyn GlusterBackend,
+    docs: &[OptionDoc],
+    volume: &str,
+    profile: &Profile,
+    diffs: &[OptionDiff],
+) -> Result<()> {
+    match profile.source {
+        ProfileSource::Group => {
+            backend
+                .sudo_gluster(&["volume", "set", volume, "group", &profile.name])
+                .map_err(|e| e.context(&t!("profiles.apply_failed", profile = profile.name)))?;
+        }
+        ProfileSource::Custom => {
+            for diff in diffs.iter().filter(|d| d.differs()) {
+                options::set(backend, docs, volume, &diff.key, &diff.wanted)?;
+            }
+        }
+    }
+    println!("{}", t!("profiles.applied", profile = profile.name, name = volume));
+    Ok(())
+}
+
+/// Guarda las opciones cambiadas de un volumen como perfil propio.
+pub fn save(backend: &dyn GlusterBackend, name: &str, volume: &str, options: &[VolumeOption]) -> Result<()> {
+    if !is_valid_name(name) {
+        return Err(Error::Message(t!("profiles.invalid_name", name = name)));
+    }
+
+    let mut text = format!("# {}\n", t!("profiles.saved_header", name = volume));
+    for option in options.iter().filter(|o| o.changed) {
+        text.push_str(&format!("{}={}\n", option.name, option.value));
+    }
+
+    let path = format!("{}/{}", PROFILES_DIR, name);
+    backend
+        .create_dir_all(PROFILES_DIR)
+        .and_then(|_| backend.write_file(&path, &text))
+        .map_err(|e| Error::Message(t!("profiles.save_failed", path = path, error = e)))?;
+    println!("{}", t!("profiles.saved", path = path));
+    Ok(())
+}
+
+/// Pantalla de perfiles de un volumen: comparar, aplicar y guardar.
+pub fn manage_profiles(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+
+    let docs = options::catalog(backend);
+    loop {
+        let items = [
+            t!("profiles.menu_apply"),
+            t!("profiles.menu_diff"),
+            t!("profiles.menu_save"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("profiles.menu_title", name = volume))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 | 1 => compare_and_apply(backend, &theme, &docs, &volume, selection == 0),
+            2 => save_interactive(backend, &theme, &docs, &volume),
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+fn compare_and_apply(
+    backend: &dyn GlusterBackend,
+    theme: &ColorfulTheme,
+    docs: &[OptionDoc],
+    volume: &str,
+    apply_after: bool,
+) -> Result<()> {
+    let profiles = list(backend);
+    if profiles.is_empty() {
+        println!("{}", t!("profiles.none", groups = GROUPS_DIR, custom = PROFILES_DIR));
+        return Ok(());
+    }
+
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("profiles.pick_prompt"))
+        .items(&profiles)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let profile = &profiles[index];
+
+    let diffs = diff(profile, &options::list(backend, docs, volume)?);
+    print_diff(volume, profile, &diffs);
+    if !apply_after {
+        return Ok(());
+    }
+    if !diffs.iter().any(OptionDiff::differs) {
+        println!("{}", t!("profiles.already_applied", profile = profile.name));
+        return Ok(());
+    }
+
+    if !Confirm::with_theme(theme)
+        .with_prompt(t!("profiles.apply_confirm", profile = profile.name, name = volume))
+        .default(true)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    apply(backend, docs, volume, profile, &diffs)
+}
+
+fn save_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, docs: &[OptionDoc], volume: &str) -> Result<()> {
+    let current = options::list(backend, docs, volume)?;
+    if !current.iter().any(|o| o.changed) {
+        println!("{}", t!("options.none_changed"));
+        return Ok(());
+    }
+
+    let name: String = Input::with_theme(theme)
+        .with_prompt(t!("profiles.name_prompt", exit = t!("common.exit_word")))
+        .validate_with(|input: &String| {
+            if crate::i18n::is_exit(input) || is_valid_name(input.trim()) {
+                Ok(())
+            } else {
+                Err(t!("profiles.invalid_name", name = input.trim()))
+            }
+        })
+        .interact_text()?;
+    if crate::i18n::is_exit(&name) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+
+    let name = name.trim();
+    if backend.exists(&format!("{}/{}", PROFILES_DIR, name))
+        && !Confirm::with_theme(theme)
+            .with_prompt(t!("profiles.overwrite_confirm", profile = name))
+            .default(false)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    save(backend, name, volume, &current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn cluster() -> FakeBackend {
+        FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started)
+            .with_file(
+                &format!("{}/virt", GROUPS_DIR),
+                "performance.readdir-ahead=off\ncluster.quorum-type=auto\nnetwork.ping-timeout=42\n",
+            )
+            .with_file(&format!("{}/db", PROFILES_DIR), "# bases de datos\nnetwork.ping-timeout = 10\nperformance.cache-sise=1GB\n")
+    }
+
+    #[test]
+    fn lists_groups_and_custom_profiles() {
+        let profiles = list(&cluster());
+        assert_eq!(profiles.len(), 2);
+        assert_eq!((profiles[0].name.as_str(), profiles[0].source), ("virt", ProfileSource::Group));
+        assert_eq!(profiles[1].options[0], ("network.ping-timeout".to_string(), "10".to_string()));
+        assert!(find(&cluster(), "samba").is_err());
+    }
+
+    #[test]
+    fn diff_shows_what_would_change() {
+        let fake = cluster();
+        let current = options::list(&fake, &options::catalog(&fake), "datos").unwrap();
+
+        let diffs = diff(&find(&fake, "virt").unwrap(), &current);
+        let changes: Vec<&str> = diffs.iter().filter(|d| d.differs()).map(|d| d.key.as_str()).collect();
+        assert_eq!(changes, vec!["performance.readdir-ahead", "cluster.quorum-type"]);
+
+        // Una opción que el volumen no tiene se informa en lugar de ignorarse.
+        let diffs = diff(&find(&fake, "db").unwrap(), &current);
+        assert_eq!(diffs[1].current, None);
+    }
+
+    #[test]
+    fn applies_groups_through_gluster_and_saves_custom_profiles() {
+        let fake = cluster();
+        let docs = options::catalog(&fake);
+        let virt = find(&fake, "virt").unwrap();
+        apply(&fake, &docs, "datos", &virt, &[]).unwrap();
+        assert_eq!(fake.commands(), vec!["gluster --mode=script volume set datos group virt"]);
+
+        options::set(&fake, &docs, "datos", "network.ping-timeout", "20").unwrap();
+        save(&fake, "ajustes", "datos", &options::list(&fake, &docs, "datos").unwrap()).unwrap();
+        let saved = find(&fake, "ajustes").unwrap();
+        assert_eq!(saved.source, ProfileSource::Custom);
+        // Las opciones del grupo aplicado también quedan como cambiadas.
+        assert_eq!(saved.options.len(), 3);
+        assert!(saved.options.contains(&("network.ping-timeout".to_string(), "20".to_string())));
+        assert!(save(&fake, "../etc", "datos", &[]).is_err());
+    }
+}
@@ -0,0 +1,470 @@
+//! Cuotas por directorio: límites de espacio (`limit-usage`) y de número de ficheros y
+//! directorios (`limit-objects`), con el informe de `gluster volume quota NOMBRE list --xml`.
+
+use console::style;
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+use crate::volinfo;
+use crate::volstatus::format_size;
+use crate::xml;
+
+/// Qué limita la cuota: espacio ocupado o número de objetos (ficheros + directorios).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuotaKind {
+    Usage,
+    Objects,
+}
+
+impl QuotaKind {
+    fn list_command(self) -> &'static str {
+        match self {
+            QuotaKind::Usage => "list",
+            QuotaKind::Objects => "list-objects",
+        }
+    }
+
+    fn limit_command(self) -> &'static str {
+        match self {
+            QuotaKind::Usage => "limit-usage",
+            QuotaKind::Objects => "limit-objects",
+        }
+    }
+
+    fn remove_command(self) -> &'static str {
+        match self {
+            QuotaKind::Usage => "remove",
+            QuotaKind::Objects => "remove-objects",
+        }
+    }
+
+    /// Bytes con unidad para el espacio, el número tal cual para los objetos.
+    fn format(self, value: u64) -> String {
+        match self {
+            QuotaKind::Usage => format_size(value),
+            QuotaKind::Objects => value.to_string(),
+        }
+    }
+}
+
+/// Un límite configurado y su uso actual. El uso es `None` mientras gluster no lo ha calculado.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuotaLimit {
+    pub kind: QuotaKind,
+    pub path: String,
+    pub hard_limit: u64,
+    /// Límite blando en porcentaje del duro (`80%`).
+    pub soft_percent: Option<u32>,
+    pub used: Option<u64>,
+    pub available: Option<u64>,
+    pub soft_exceeded: bool,
+    pub hard_exceeded: bool,
+}
+
+impl QuotaLimit {
+    /// Porcentaje del límite duro en uso.
+    pub fn used_percent(&self) -> Option<f64> {
+        let used = self.used?;
+        (self.hard_limit > 0).then(|| used as f64 * 100.0 / self.hard_limit as f64)
+    }
+}
+
+/// Interpreta `gluster volume quota NOMBRE list --xml` (o `list-objects`).
+pub fn parse_list(output: &str, kind: QuotaKind) -> Result<Vec<QuotaLimit>> {
+    let doc = xml::parse_cli_output(output)?;
+    // Sin límites configurados gluster no incluye `<volQuota>`.
+    let Some(quota) = xml::descendant(doc.root_element(), "volQuota") else {
+        return Ok(vec![]);
+    };
+
+    let mut limits = vec![];
+    for node in xml::children(quota, "limit") {
+        let Some(path) = xml::child_text(node, "path") else {
+            continue;
+        };
+        let used = match kind {
+            QuotaKind::Usage => xml::child_parse(node, "used_space"),
+            QuotaKind::Objects => xml::child_parse::<u64>(node, "file_count")
+                .zip(xml::child_parse::<u64>(node, "dir_count"))
+                .map(|(files, dirs)| files + dirs),
+        };
+        let available = match kind {
+            QuotaKind::Usage => xml::child_parse(node, "avail_space"),
+            QuotaKind::Objects => xml::child_parse(node, "available"),
+        };
+        limits.push(QuotaLimit {
+            kind,
+            path: path.to_string(),
+            hard_limit: xml::child_parse(node, "hard_limit").unwrap_or(0),
+            soft_percent: xml::child_text(node, "soft_limit_percent").and_then(|p| p.trim_end_matches('%').parse().ok()),
+            used,
+            available,
+            soft_exceeded: xml::child_text(node, "sl_exceeded") == Some("Yes"),
+            hard_exceeded: xml::child_text(node, "hl_exceeded") == Some("Yes"),
+        });
+    }
+    Ok(limits)
+}
+
+/// La cuota está activa si el volumen tiene `features.quota` a `on`.
+pub fn is_enabled(backend: &dyn GlusterBackend, volume: &str) -> Result<bool> {
+    let info = volinfo::fetch_one(backend, volume)?;
+    Ok(info.options.get("features.quota").map(String::as_str) == Some("on"))
+}
+
+pub fn enable(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["volume", "quota", volume, "enable"])
+        .map_err(|e| e.context(&t!("quota.enable_failed", name = volume)))?;
+    println!("{}", t!("quota.enabled", name = volume));
+    Ok(())
+}
+
+/// Desactiva la cuota; gluster borra con ella todos los límites configurados.
+pub fn disable(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["volume", "quota", volume, "disable"])
+        .map_err(|e| e.context(&t!("quota.disable_failed", name = volume)))?;
+    println!("{}", t!("quota.disabled", name = volume));
+    Ok(())
+}
+
+pub fn list(backend: &dyn GlusterBackend, volume: &str, kind: QuotaKind) -> Result<Vec<QuotaLimit>> {
+    let output = backend
+        .gluster_xml(&["volume", "quota", volume, kind.list_command()])
+        .map_err(|e| e.context(&t!("quota.list_failed", name = volume)))?;
+    parse_list(&output, kind)
+}
+
+/// Comprueba un límite y lo devuelve como lo espera gluster (`10GB`, `5000`).
+pub fn validate_limit(kind: QuotaKind, limit: &str) -> Result<String> {
+    let limit = limit.trim();
+    let valid = match kind {
+        QuotaKind::Usage => crate::options::parse_size(limit).filter(|&bytes| bytes > 0).map(|_| limit.to_uppercase()),
+        QuotaKind::Objects => limit.parse::<u64>().ok().filter(|&n| n > 0).map(|n| n.to_string()),
+    };
+    valid.ok_or_else(|| match kind {
+        QuotaKind::Usage => Error::Message(t!("quota.invalid_size", value = limit)),
+        QuotaKind::Objects => Error::Message(t!("quota.invalid_count", value = limit)),
+    })
+}
+
+fn validate_path(path: &str) -> Result<()> {
+    if path.starts_with('/') && !path.split('/').any(|part| part == "..") {
+        Ok(())
+    } else {
+        Err(Error::Message(t!("quota.invalid_path", path = path)))
+    }
+}
+
+fn validate_percent(percent: u32) -> Result<()> {
+    if (1..=100).contains(&percent) {
+        Ok(())
+    } else {
+        Err(Error::Message(t!("quota.invalid_percent", value = percent)))
+    }
+}
+
+/// Fija el límite de `path` (ruta dentro del volumen); sin `soft` se usa el límite blando por defecto.
+pub fn set_limit(
+    backend: &dyn GlusterBackend,
+    volume: &str,
+    kind: QuotaKind,
+    path: &str,
+    limit: &str,
+    soft: Option<u32>,
+) -> Result<()> {
+    validate_path(path)?;
+    let limit = validate_limit(kind, limit)?;
+    let soft = soft.map(|percent| validate_percent(percent).map(|_| format!("{}%", percent))).transpose()?;
+
+    let mut args = vec!["volume", "quota", volume, kind.limit_command(), path, &limit];
+    if let Some(soft) = &soft {
+        args.push(soft);
+    }
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("quota.limit_failed", path = path)))?;
+    println!("{}", t!("quota.limit_set", path = path, limit = limit));
+    Ok(())
+}
+
+pub fn remove_limit(backend: &dyn GlusterBackend, volume: &str, kind: QuotaKind, path: &str) -> Result<()> {
+    validate_path(path)?;
+    backend
+        .sudo_gluster(&["volume", "quota", volume, kind.remove_command(), path])
+        .map_err(|e| e.context(&t!("quota.remove_failed", path = path)))?;
+    println!("{}", t!("quota.limit_removed", path = path));
+    Ok(())
+}
+
+/// Límite blando que se aplica a los directorios sin uno propio.
+pub fn set_default_soft_limit(backend: &dyn GlusterBackend, volume: &str, percent: u32) -> Result<()> {
+    validate_percent(percent)?;
+    let value = format!("{}%", percent);
+    backend
+        .sudo_gluster(&["volume", "quota", volume, "default-soft-limit", &value])
+        .map_err(|e| e.context(&t!("quota.default_soft_failed")))?;
+    println!("{}", t!("quota.default_soft_set", value = value));
+    Ok(())
+}
+
+/// Tabla de uso; en rojo lo que supera el límite duro y en amarillo lo que supera el blando.
+pub fn print_table(volume: &str, kind: QuotaKind, limits: &[QuotaLimit]) {
+    let title = match kind {
+        QuotaKind::Usage => t!("quota.title_usage", name = volume),
+        QuotaKind::Objects => t!("quota.title_objects", name = volume),
+    };
+    println!("{}", title);
+    if limits.is_empty() {
+        println!("{}", t!("quota.no_limits"));
+        return;
+    }
+
+    let width = limits.iter().map(|l| l.path.len()).max().unwrap_or(4).max(4);
+    println!(
+        "   {:<width$}  {:>12}  {:>6}  {:>12}  {:>12}  {:>6}",
+        t!("quota.column_path"),
+        t!("quota.column_hard"),
+        t!("quota.column_soft"),
+        t!("quota.column_used"),
+        t!("quota.column_available"),
+        "%",
+    );
+    for limit in limits {
+        let line = format!(
+            "   {:<width$}  {:>12}  {:>6}  {:>12}  {:>12}  {:>6}",
+            limit.path,
+            kind.format(limit.hard_limit),
+            limit.soft_percent.map(|p| format!("{}%", p)).unwrap_or_else(|| "-".to_string()),
+            limit.used.map(|u| kind.format(u)).unwrap_or_else(|| "-".to_string()),
+            limit.available.map(|a| kind.format(a)).unwrap_or_else(|| "-".to_string()),
+            limit.used_percent().map(|p| format!("{:.0}%", p)).unwrap_or_else(|| "-".to_string()),
+        );
+        if limit.hard_exceeded {
+            println!("{}", style(line).red().bold());
+        } else if limit.soft_exceeded {
+            println!("{}", style(line).yellow());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Pantalla de cuotas de un volumen.
+pub fn manage_quotas(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+
+    if !is_enabled(backend, &volume)? {
+        if !Confirm::with_theme(&theme)
+            .with_prompt(t!("quota.enable_confirm", name = volume))
+            .default(true)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?
+        {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
+        }
+        enable(backend, &volume)?;
+    }
+
+    loop {
+        let items = [
+            t!("quota.menu_report"),
+            t!("quota.menu_limit_usage"),
+            t!("quota.menu_limit_objects"),
+            t!("quota.menu_remove"),
+            t!("quota.menu_default_soft"),
+            t!("quota.menu_disable"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("quota.menu_title", name = volume))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => report(backend, &volume),
+            1 => limit_interactive(backend, &theme, &volume, QuotaKind::Usage),
+            2 => limit_interactive(backend, &theme, &volume, QuotaKind::Objects),
+            3 => remove_interactive(backend, &theme, &volume),
+            4 => default_soft_interactive(backend, &theme, &volume),
+            5 => {
+                if Confirm::with_theme(&theme)
+                    .with_prompt(t!("quota.disable_confirm", name = volume))
+                    .default(false)
+                    .interact_opt()?
+                    .ok_or(Error::Cancelled)?
+                {
+                    crate::error::report(disable(backend, &volume));
+                    break;
+                }
+                Ok(())
+            }
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+fn report(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    print_table(volume, QuotaKind::Usage, &list(backend, volume, QuotaKind::Usage)?);
+    let objects = list(backend, volume, QuotaKind::Objects)?;
+    if !objects.is_empty() {
+        println!();
+        print_table(volume, QuotaKind::Objects, &objects);
+    }
+    Ok(())
+}
+
+fn limit_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str, kind: QuotaKind) -> Result<()> {
+    let path: String = Input::with_theme(theme)
+        .with_prompt(t!("quota.path_prompt"))
+        .validate_with(|input: &String| validate_path(input.trim()).map_err(|e| e.to_string()))
+        .interact_text()?;
+
+    let prompt = match kind {
+        QuotaKind::Usage => t!("quota.size_prompt"),
+        QuotaKind::Objects => t!("quota.count_prompt"),
+    };
+    let limit: String = Input::with_theme(theme)
+        .with_prompt(prompt)
+        .validate_with(|input: &String| validate_limit(kind, input).map(|_| ()).map_err(|e| e.to_string()))
+        .interact_text()?;
+
+    let soft: String = Input::with_theme(theme)
+        .with_prompt(t!("quota.soft_prompt"))
+        .allow_empty(true)
+        .validate_with(|input: &String| match input.trim() {
+            "" => Ok(()),
+            text => match text.trim_end_matches('%').parse::<u32>() {
+                Ok(percent) => validate_percent(percent).map_err(|e| e.to_string()),
+                Err(_) => Err(t!("quota.invalid_percent", value = text)),
+            },
+        })
+        .interact_text()?;
+    let soft = soft.trim().trim_end_matches('%').parse().ok();
+
+    set_limit(backend, volume, kind, path.trim(), &limit, soft)
+}
+
+fn remove_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    let mut limits = list(backend, volume, QuotaKind::Usage)?;
+    limits.extend(list(backend, volume, QuotaKind::Objects)?);
+    if limits.is_empty() {
+        println!("{}", t!("quota.no_limits"));
+        return Ok(());
+    }
+
+    let items: Vec<String> = limits
+        .iter()
+        .map(|l| match l.kind {
+            QuotaKind::Usage => t!("quota.item_usage", path = l.path, limit = l.kind.format(l.hard_limit)),
+            QuotaKind::Objects => t!("quota.item_objects", path = l.path, limit = l.hard_limit),
+        })
+        .collect();
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("quota.remove_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let limit = &limits[index];
+
+    if !Confirm::with_theme(theme)
+        .with_prompt(t!("quota.remove_confirm", item = items[index]))
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    remove_limit(backend, volume, limit.kind, &limit.path)
+}
+
+fn default_soft_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    let percent: u32 = Input::with_theme(theme)
+        .with_prompt(t!("quota.default_soft_prompt"))
+        .default(80)
+        .validate_with(|input: &u32| validate_percent(*input).map_err(|e| e.to_string()))
+        .interact_text()?;
+    set_default_soft_limit(backend, volume, percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    #[test]
+    fn parses_usage_and_object_lists() {
+        let usage = r#"<cliOutput><opRet>0</opRet><opErrno>0</opErrno><opErrstr/><volQuota>
+            <limit><path>/ventas</path><hard_limit>10737418240</hard_limit><soft_limit_percent>80%</soft_limit_percent>
+            <soft_limit_value>8589934592</soft_limit_value><used_space>9663676416</used_space><avail_space>1073741824</avail_space>
+            <sl_exceeded>Yes</sl_exceeded><hl_exceeded>No</hl_exceeded></limit>
+            <limit><path>/rrhh</path><hard_limit>1073741824</hard_limit><soft_limit_percent>80%</soft_limit_percent>
+            <used_space>N/A</used_space><avail_space>N/A</avail_space><sl_exceeded>N/A</sl_exceeded><hl_exceeded>N/A</hl_exceeded></limit>
+            </volQuota></cliOutput>"#;
+        let limits = parse_list(usage, QuotaKind::Usage).unwrap();
+        assert_eq!(limits.len(), 2);
+        assert_eq!((limits[0].soft_percent, limits[0].soft_exceeded, limits[0].hard_exceeded), (Some(80), true, false));
+        assert_eq!(limits[0].used_percent().map(|p| p.round()), Some(90.0));
+        assert_eq!(limits[1].used, None);
+
+        let objects = r#"<cliOutput><opRet>0</opRet><volQuota><limit><path>/</path><hard_limit>100</hard_limit>
+            <soft_limit_percent>80%</soft_limit_percent><file_count>40</file_count><dir_count>2</dir_count>
+            <available>58</available><sl_exceeded>No</sl_exceeded><hl_exceeded>No</hl_exceeded></limit></volQuota></cliOutput>"#;
+        let objects = parse_list(objects, QuotaKind::Objects).unwrap();
+        assert_eq!((objects[0].used, objects[0].available), (Some(42), Some(58)));
+
+        assert!(parse_list("<cliOutput><opRet>0</opRet></cliOutput>", QuotaKind::Usage).unwrap().is_empty());
+    }
+
+    #[test]
+    fn limits_are_validated_before_calling_gluster() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        enable(&fake, "datos").unwrap();
+
+        assert!(set_limit(&fake, "datos", QuotaKind::Usage, "ventas", "10GB", None).is_err());
+        assert!(set_limit(&fake, "datos", QuotaKind::Usage, "/ventas", "diez", None).is_err());
+        assert!(set_limit(&fake, "datos", QuotaKind::Usage, "/ventas", "10GB", Some(120)).is_err());
+        assert!(set_limit(&fake, "datos", QuotaKind::Objects, "/../etc", "100", None).is_err());
+        assert_eq!(fake.commands(), vec!["gluster --mode=script volume quota datos enable"]);
+    }
+
+    #[test]
+    fn quota_lifecycle() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        assert!(!is_enabled(&fake, "datos").unwrap());
+        // Sin cuota activa gluster rechaza los límites.
+        assert!(set_limit(&fake, "datos", QuotaKind::Usage, "/ventas", "10GB", None).is_err());
+
+        enable(&fake, "datos").unwrap();
+        assert!(is_enabled(&fake, "datos").unwrap());
+        set_limit(&fake, "datos", QuotaKind::Usage, "/ventas", "10gb", Some(90)).unwrap();
+        set_limit(&fake, "datos", QuotaKind::Objects, "/ventas", "5000", None).unwrap();
+
+        let usage = list(&fake, "datos", QuotaKind::Usage).unwrap();
+        assert_eq!((usage[0].path.as_str(), usage[0].hard_limit, usage[0].soft_percent), ("/ventas", 10 << 30, Some(90)));
+        assert_eq!(list(&fake, "datos", QuotaKind::Objects).unwrap()[0].soft_percent, Some(80));
+
+        remove_limit(&fake, "datos", QuotaKind::Usage, "/ventas").unwrap();
+        assert!(list(&fake, "datos", QuotaKind::Usage).unwrap().is_empty());
+        assert_eq!(list(&fake, "datos", QuotaKind::Objects).unwrap().len(), 1);
+
+        disable(&fake, "datos").unwrap();
+        assert!(!is_enabled(&fake, "datos").unwrap());
+        assert!(fake.commands().contains(&"gluster --mode=script volume quota datos limit-usage /ventas 10GB 90%".to_string()));
+    }
+}
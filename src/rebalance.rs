@@ -0,0 +1,460 @@
+//! Rebalanceo de volúmenes distribuidos: reparte los datos existentes entre todos los
+//! subvolúmenes después de agregar bricks (`volume rebalance VOL start|status|stop`).
+
+use std::fmt;
+use std::time::Duration;
+
+use console::style;
+use dialoguer::{Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+use crate::volstatus::format_size;
+
+/// Variante de `rebalance start`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    /// Recalcula el reparto y mueve los ficheros.
+    Data,
+    /// Solo recalcula el reparto: los ficheros nuevos ya usan los bricks nuevos, los existentes no se mueven.
+    FixLayout,
+    /// Mueve los ficheros aunque el brick de destino tenga menos espacio libre.
+    Force,
+}
+
+impl Kind {
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Kind::Data => &["start"],
+            Kind::FixLayout => &["fix-layout", "start"],
+            Kind::Force => &["start", "force"],
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Kind::Data => t!("rebalance.kind_data"),
+            Kind::FixLayout => t!("rebalance.kind_fix_layout"),
+            Kind::Force => t!("rebalance.kind_force"),
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Progreso del rebalanceo en un nodo.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeProgress {
+    /// `localhost` para este nodo.
+    pub node: String,
+    /// Ficheros movidos y su tamaño en bytes.
+    pub moved: u64,
+    pub size: u64,
+    /// Ficheros examinados.
+    pub scanned: u64,
+    pub failures: u64,
+    pub skipped: u64,
+    /// `in progress`, `completed`, `stopped`, `failed`, `fix-layout in progress`, ...
+    pub status: String,
+    /// Segundos.
+    pub runtime: f64,
+}
+
+impl NodeProgress {
+    pub fn running(&self) -> bool {
+        // Un nodo `not started` (sin bricks del volumen, por ejemplo) puede no arrancar nunca.
+        self.status.contains("in progress")
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RebalanceStatus {
+    pub nodes: Vec<NodeProgress>,
+    /// Estimación de gluster del tiempo restante (`hh:mm:ss`), si la da.
+    pub eta: Option<String>,
+}
+
+impl RebalanceStatus {
+    pub fn running(&self) -> bool {
+        self.nodes.iter().any(NodeProgress::running)
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.nodes.iter().map(|n| n.failures).sum()
+    }
+}
+
+/// Parsea la salida de texto de `rebalance status` y de `remove-brick status`. Se usa el texto y no
+/// el XML porque solo el texto trae la estimación de tiempo restante:
+///
+/// ```text
+///      Node Rebalanced-files     size  scanned  failures  skipped       status  run time in h:m:s
+///  ---------      -----------  -------  -------  --------  -------  -----------  -----------------
+/// localhost               20   20.0MB       60         0        2  in progress            0:00:45
+/// Estimated time left for rebalance to complete :        0:01:30
+/// ```
+///
+/// Con fix-layout gluster solo muestra las columnas de nodo, estado y duración.
+pub fn parse_status(text: &str) -> Result<RebalanceStatus> {
+    let mut nodes = vec![];
+    let mut full = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("Node ") {
+            full = Some(line.contains("Rebalanced-files"));
+            continue;
+        }
+        let Some(full) = full else { continue };
+        if line.is_empty() || line.starts_with("Estimated time left") || line.starts_with("volume ") {
+            break;
+        }
+        nodes.extend(parse_node(line, full));
+    }
+    if full.is_none() {
+        return Err(Error::Parse(t!("rebalance.unreadable_status", output = text.trim())));
+    }
+    Ok(RebalanceStatus { nodes, eta: parse_eta(text) })
+}
+
+/// Una fila de la tabla; `None` para las líneas de guiones.
+fn parse_node(line: &str, full: bool) -> Option<NodeProgress> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let counters = if full { 5 } else { 0 };
+    if fields.len() < counters + 3 {
+        return None;
+    }
+    let mut node = NodeProgress {
+        node: fields[0].to_string(),
+        status: fields[counters + 1..fields.len() - 1].join(" "),
+        runtime: parse_runtime(fields[fields.len() - 1])?,
+        ..NodeProgress::default()
+    };
+    if full {
+        node.moved = fields[1].parse().ok()?;
+        node.size = parse_size(fields[2])?;
+        node.scanned = fields[3].parse().ok()?;
+        node.failures = fields[4].parse().ok()?;
+        node.skipped = fields[5].parse().ok()?;
+    }
+    Some(node)
+}
+
+/// `h:m:s` a segundos.
+fn parse_runtime(text: &str) -> Option<f64> {
+    let parts: Vec<f64> = text.split(':').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [h, m, s] => Some(h * 3600.0 + m * 60.0 + s),
+        _ => None,
+    }
+}
+
+/// Tamaños como los escribe gluster: `0Bytes`, `512.0KB`, `20.0MB`, `1.5GB`.
+fn parse_size(text: &str) -> Option<u64> {
+    let (number, unit) = text.split_at(text.find(|c: char| c.is_ascii_alphabetic())?);
+    let factor: u64 = match unit {
+        "Bytes" => 1,
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        "TB" => 1 << 40,
+        "PB" => 1 << 50,
+        _ => return None,
+    };
+    Some((number.parse::<f64>().ok()? * factor as f64) as u64)
+}
+
+/// `Estimated time left for rebalance to complete :        0:05:32`.
+pub fn parse_eta(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(|l| l.trim_start().strip_prefix("Estimated time left"))
+        .and_then(|rest| rest.split_once(':'))
+        .map(|(_, eta)| eta.trim().to_string())
+        .filter(|eta| !eta.is_empty())
+}
+
+pub fn status(backend: &dyn GlusterBackend, volume: &str) -> Result<RebalanceStatus> {
+    match backend.gluster(&["volume", "rebalance", volume, "status"]) {
+        Ok(text) => parse_status(&text),
+        // Nunca se ha rebalanceado: gluster lo trata como error.
+        Err(Error::CommandFailed { stderr, .. }) if stderr.contains("not started") => Ok(RebalanceStatus::default()),
+        Err(e) => Err(e.context(&t!("rebalance.status_failed", name = volume))),
+    }
+}
+
+pub fn start(backend: &dyn GlusterBackend, volume: &str, kind: Kind) -> Result<()> {
+    let mut args = vec!["volume", "rebalance", volume];
+    args.extend(kind.args());
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("rebalance.start_failed", name = volume)))?;
+    println!("{}", t!("rebalance.started", name = volume, kind = kind));
+    Ok(())
+}
+
+pub fn stop(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["volume", "rebalance", volume, "stop"])
+        .map_err(|e| e.context(&t!("rebalance.stop_failed", name = volume)))?;
+    println!("{}", t!("rebalance.stopped", name = volume));
+    Ok(())
+}
+
+fn format_runtime(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+pub fn print_status(volume: &str, status: &RebalanceStatus) {
+    println!("{}", t!("rebalance.title", name = volume));
+    print_progress(volume, status);
+}
+
+/// Tabla de progreso por nodo, sin título.
+pub fn print_progress(volume: &str, status: &RebalanceStatus) {
+    if status.nodes.is_empty() {
+        println!("{}", t!("rebalance.never_started"));
+        return;
+    }
+    let width = status.nodes.iter().map(|n| n.node.len()).max().unwrap_or(4).max(4);
+    let header = format!(
+        "   {:<width$}  {:>9}  {:>10}  {:>10}  {:>7}  {:>7}  {:>9}  {}",
+        t!("rebalance.column_node"),
+        t!("rebalance.column_scanned"),
+        t!("rebalance.column_moved"),
+        t!("rebalance.column_size"),
+        t!("rebalance.column_failures"),
+        t!("rebalance.column_skipped"),
+        t!("rebalance.column_runtime"),
+        t!("rebalance.column_status"),
+    );
+    println!("{}", header);
+    for node in &status.nodes {
+        let line = format!(
+            "   {:<width$}  {:>9}  {:>10}  {:>10}  {:>7}  {:>7}  {:>9}  {}",
+            node.node,
+            node.scanned,
+            node.moved,
+            format_size(node.size),
+            node.failures,
+            node.skipped,
+            format_runtime(node.runtime),
+            node.status,
+        );
+        if node.failures > 0 || node.status == "failed" {
+            println!("{}", style(line).red());
+        } else if node.running() {
+            println!("{}", style(line).yellow());
+        } else {
+            println!("{}", line);
+        }
+    }
+    if let Some(eta) = status.eta.as_ref().filter(|_| status.running()) {
+        println!("{}", t!("rebalance.eta", eta = eta));
+    }
+    if status.failures() > 0 {
+        println!("{}", t!("rebalance.failures_hint", name = volume));
+    }
+}
+
+/// Muestra el progreso cada `interval` hasta que todos los nodos terminen; falla si se agotan
+/// `rounds` o si algún nodo termina con error.
+pub fn watch(backend: &dyn GlusterBackend, volume: &str, interval: Duration, rounds: u64) -> Result<()> {
+    let Some(last) = follow(volume, interval, rounds, || status(backend, volume))? else {
+        return Err(Error::Message(t!("rebalance.watch_timeout", name = volume)));
+    };
+    if last.nodes.iter().any(|n| n.status == "failed") {
+        return Err(Error::Message(t!("rebalance.failed", name = volume)));
+    }
+    Ok(())
+}
+
+/// Bucle de progreso compartido con `remove-brick`, que migra los datos igual que un rebalanceo.
+/// Devuelve el último estado al terminar, o `None` si se agotan `rounds`.
+pub fn follow(
+    volume: &str,
+    interval: Duration,
+    rounds: u64,
+    mut fetch: impl FnMut() -> Result<RebalanceStatus>,
+) -> Result<Option<RebalanceStatus>> {
+    for round in 0..rounds {
+        if round > 0 {
+            std::thread::sleep(interval);
+        }
+        let current = fetch()?;
+        let scanned: u64 = current.nodes.iter().map(|n| n.scanned).sum();
+        let moved: u64 = current.nodes.iter().map(|n| n.moved).sum();
+        let size: u64 = current.nodes.iter().map(|n| n.size).sum();
+        let running = current.nodes.iter().filter(|n| n.running()).count();
+        println!(
+            "{}",
+            t!(
+                "rebalance.watch_line",
+                time = chrono::Local::now().format("%H:%M:%S"),
+                scanned = scanned,
+                moved = moved,
+                size = format_size(size),
+                running = running,
+                total = current.nodes.len(),
+                eta = current.eta.as_deref().unwrap_or("-")
+            )
+        );
+        if !current.running() {
+            print_progress(volume, &current);
+            return Ok(Some(current));
+        }
+    }
+    Ok(None)
+}
+
+/// Propuesta al terminar `add_bricks`: sin rebalanceo los bricks nuevos no reciben los datos existentes.
+pub fn offer_after_add(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    let kinds = [Kind::Data, Kind::FixLayout];
+    let mut items: Vec<String> = kinds.iter().map(Kind::to_string).collect();
+    items.push(t!("rebalance.later"));
+    let selection = Select::with_theme(theme)
+        .with_prompt(t!("rebalance.offer", name = volume))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    match kinds.get(selection) {
+        Some(kind) => start(backend, volume, *kind),
+        None => {
+            println!("{}", t!("rebalance.later_hint", name = volume));
+            Ok(())
+        }
+    }
+}
+
+/// Pantalla de rebalanceo de un volumen.
+pub fn manage_rebalance(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+
+    loop {
+        let items = [
+            t!("rebalance.menu_status"),
+            t!("rebalance.menu_start"),
+            t!("rebalance.menu_fix_layout"),
+            t!("rebalance.menu_force"),
+            t!("rebalance.menu_watch"),
+            t!("rebalance.menu_stop"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("rebalance.menu_title", name = volume))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => status(backend, &volume).map(|s| print_status(&volume, &s)),
+            1 => start(backend, &volume, Kind::Data),
+            2 => start(backend, &volume, Kind::FixLayout),
+            3 => start(backend, &volume, Kind::Force),
+            4 => watch(backend, &volume, Duration::from_secs(5), 8640),
+            5 => stop(backend, &volume),
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn expanded() -> FakeBackend {
+        let fake = FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started);
+        crate::volume::add(&fake, "datos", &["vm2:/gluster/b".to_string()], false).unwrap();
+        fake
+    }
+
+    #[test]
+    fn starts_each_kind() {
+        let fake = expanded();
+        start(&fake, "datos", Kind::FixLayout).unwrap();
+        assert!(start(&fake, "datos", Kind::Data).is_err());
+        stop(&fake, "datos").unwrap();
+        start(&fake, "datos", Kind::Force).unwrap();
+        let commands = fake.commands();
+        assert_eq!(
+            &commands[1..],
+            [
+                "gluster --mode=script volume rebalance datos fix-layout start",
+                "gluster --mode=script volume rebalance datos start",
+                "gluster --mode=script volume rebalance datos stop",
+                "gluster --mode=script volume rebalance datos start force",
+            ]
+        );
+    }
+
+    #[test]
+    fn status_is_parsed_per_node() {
+        let fake = expanded();
+        assert!(status(&fake, "datos").unwrap().nodes.is_empty());
+
+        start(&fake, "datos", Kind::Data).unwrap();
+        let current = status(&fake, "datos").unwrap();
+        assert_eq!(current.nodes.len(), 2);
+        assert_eq!(current.nodes[0].node, "localhost");
+        assert_eq!(current.nodes[0].status, "in progress");
+        assert_eq!(current.eta.as_deref(), Some("0:01:30"));
+        assert!(current.running());
+
+        let done = status(&fake, "datos").unwrap();
+        assert!(!done.running());
+        assert_eq!((done.nodes[1].scanned, done.nodes[1].moved, done.nodes[1].skipped), (120, 40, 2));
+    }
+
+    #[test]
+    fn watch_waits_for_every_node() {
+        let fake = expanded();
+        start(&fake, "datos", Kind::Data).unwrap();
+        watch(&fake, "datos", Duration::ZERO, 5).unwrap();
+    }
+
+    #[test]
+    fn parses_the_text_table() {
+        let text = "\
+                                    Node Rebalanced-files          size       scanned      failures       skipped               status  run time in h:m:s
+                               ---------      -----------   -----------   -----------   -----------   -----------         ------------     --------------
+                               localhost               20        20.0MB            60             0             2          in progress        0:00:45
+                                     vm2                0        0Bytes             0             0             0          not started        0:00:00
+Estimated time left for rebalance to complete :        0:01:30
+volume rebalance: datos: success
+";
+        let status = parse_status(text).unwrap();
+        assert_eq!(status.nodes.len(), 2);
+        assert_eq!((status.nodes[0].moved, status.nodes[0].size, status.nodes[0].runtime), (20, 20 * 1_048_576, 45.0));
+        assert_eq!(status.nodes[1].status, "not started");
+        assert!(!status.nodes[1].running());
+        assert_eq!(status.eta.as_deref(), Some("0:01:30"));
+
+        let fix_layout = "\
+Node                                    status           run time in h:m:s
+---------                          -----------           ------------
+localhost                          fix-layout in progress        0:0:12
+volume rebalance: datos: success
+";
+        let status = parse_status(fix_layout).unwrap();
+        assert_eq!((status.nodes[0].status.as_str(), status.nodes[0].runtime), ("fix-layout in progress", 12.0));
+        assert!(parse_status("volume rebalance: datos: success\n").is_err());
+    }
+
+    #[test]
+    fn parses_eta() {
+        assert_eq!(parse_eta("x\nEstimated time left for rebalance to complete :        0:05:32\n").as_deref(), Some("0:05:32"));
+        assert_eq!(parse_eta("volume rebalance: datos: success\n"), None);
+    }
+}
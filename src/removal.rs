@@ -0,0 +1,491 @@
+//! Ciclo completo de `remove-brick`: iniciar la migración, seguir su progreso, detenerla y
+//! confirmarla (`commit`) solo cuando terminó sin fallos.
+//!
+//! La selección se valida contra la disposición del volumen antes de llamar a gluster: en un volumen
+//! replicado o disperso solo se quitan conjuntos completos, o un brick de cada conjunto para bajar
+//! la réplica (`remove-brick VOL replica N ... force`, sin migración).
+//!
+//! Las eliminaciones en curso se guardan en `/var/lib/cluster_almacenamiento/remove-brick.json`
+//! para retomarlas después de salir del programa: gluster pide la lista de bricks en cada paso.
+
+use std::time::Duration;
+
+use dialoguer::{Confirm, Select, theme::ColorfulTheme};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::layout::VolumeLayout;
+use crate::rebalance::RebalanceStatus;
+use crate::t;
+
+pub const STATE_PATH: &str = "/var/lib/cluster_almacenamiento/remove-brick.json";
+
+/// Eliminación iniciada y todavía sin confirmar ni detener.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingRemoval {
+    pub volume: String,
+    pub bricks: Vec<String>,
+    pub started: String,
+}
+
+pub fn load(backend: &dyn GlusterBackend) -> Vec<PendingRemoval> {
+    backend
+        .read_file(STATE_PATH)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save(backend: &dyn GlusterBackend, pending: &[PendingRemoval]) -> Result<()> {
+    let json = serde_json::to_string_pretty(pending).map_err(|e| Error::Message(e.to_string()))?;
+    backend
+        .create_dir_all("/var/lib/cluster_almacenamiento")
+        .and_then(|_| backend.write_file(STATE_PATH, &(json + "\n")))
+        .map_err(|e| Error::Message(t!("removal.write_failed", path = STATE_PATH, error = e)))
+}
+
+/// gluster solo admite una eliminación en curso por volumen.
+pub fn pending(backend: &dyn GlusterBackend, volume: &str) -> Result<PendingRemoval> {
+    load(backend)
+        .into_iter()
+        .find(|p| p.volume == volume)
+        .ok_or_else(|| Error::Message(t!("removal.none_pending", name = volume)))
+}
+
+fn forget(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    let mut all = load(backend);
+    all.retain(|p| p.volume != volume);
+    save(backend, &all)
+}
+
+fn args<'a>(removal: &'a PendingRemoval, action: &'a str) -> Vec<&'a str> {
+    let mut args = vec!["volume", "remove-brick", removal.volume.as_str()];
+    args.extend(removal.bricks.iter().map(String::as_str));
+    args.push(action);
+    args
+}
+
+/// Comprueba que `selected` se pueda quitar de un volumen con esta disposición y estos `bricks`.
+/// Con `replica` se valida una reducción de réplica en lugar de la eliminación de conjuntos.
+pub fn check_selection(layout: &VolumeLayout, bricks: &[String], selected: &[String], replica: Option<u32>) -> Result<()> {
+    if selected.is_empty() {
+        return Err(Error::Message(t!("removal.nothing_selected")));
+    }
+    for (i, brick) in selected.iter().enumerate() {
+        if !bricks.contains(brick) {
+            return Err(Error::Message(t!("removal.unknown_brick", brick = brick)));
+        }
+        if selected[..i].contains(brick) {
+            return Err(Error::Message(t!("removal.duplicate_brick", brick = brick)));
+        }
+    }
+
+    let sets = layout.sets(bricks);
+    let Some(replica) = replica else {
+        for (i, set) in sets.iter().enumerate() {
+            let chosen = set.iter().filter(|b| selected.contains(b)).count();
+            if chosen > 0 && chosen < set.len() {
+                return Err(Error::Message(t!(
+                    "removal.incomplete_set",
+                    number = i + 1,
+                    bricks = set.join(", "),
+                    layout = layout
+                )));
+            }
+        }
+        if selected.len() == bricks.len() {
+            return Err(Error::Message(t!("removal.all_sets")));
+        }
+        return Ok(());
+    };
+
+    let VolumeLayout::Replicate { replica: current, arbiter } = *layout else {
+        return Err(Error::Message(t!("removal.not_replicated", layout = layout)));
+    };
+    if replica == 0 || replica >= current {
+        return Err(Error::Message(t!("removal.bad_replica", max = current - 1)));
+    }
+    // Réplica 2 más árbitro no existe: solo se puede bajar a réplica 2 quitando los árbitros.
+    if arbiter > 0 && replica != 2 {
+        return Err(Error::Message(t!("removal.arbiter_only")));
+    }
+    let per_set = (current - replica) as usize;
+    for (i, set) in sets.iter().enumerate() {
+        let chosen: Vec<usize> = (0..set.len()).filter(|&p| selected.contains(&set[p])).collect();
+        if chosen.len() != per_set {
+            return Err(Error::Message(t!(
+                "removal.wrong_per_set",
+                from = current,
+                to = replica,
+                count = per_set,
+                number = i + 1,
+                selected = chosen.len()
+            )));
+        }
+        if arbiter > 0 && !chosen.iter().all(|&p| layout.is_arbiter(p)) {
+            return Err(Error::Message(t!("removal.arbiter_only")));
+        }
+    }
+    Ok(())
+}
+
+/// Valida la selección contra la disposición actual del volumen.
+fn validate(backend: &dyn GlusterBackend, volume: &str, selected: &[String], replica: Option<u32>) -> Result<()> {
+    let info = crate::volinfo::fetch_one(backend, volume)?;
+    check_selection(&VolumeLayout::from_info(&info), &info.brick_names(), selected, replica)
+}
+
+/// Baja la réplica quitando un brick de cada conjunto. No hay migración: los bricks que quedan
+/// ya tienen los datos, así que se niega si hay entradas pendientes de reparar o si no se puede
+/// saber porque la consulta falla o alguno de los que quedan no responde.
+pub fn reduce_replica(backend: &dyn GlusterBackend, volume: &str, replica: u32, bricks: &[String]) -> Result<()> {
+    validate(backend, volume, bricks, Some(replica))?;
+    let heal = crate::heal::summary(backend, volume)
+        .map_err(|e| e.context(&t!("removal.heal_unknown", name = volume)))?;
+    let offline: Vec<&str> = heal
+        .iter()
+        .filter(|b| !b.connected() && !bricks.contains(&b.brick))
+        .map(|b| b.brick.as_str())
+        .collect();
+    if !offline.is_empty() {
+        return Err(Error::Message(t!("removal.remaining_offline", name = volume, bricks = offline.join(", "))));
+    }
+    let pending = crate::heal::total_pending(&heal);
+    if pending > 0 {
+        return Err(Error::Message(t!("removal.heal_pending", name = volume, count = pending)));
+    }
+
+    println!("{}", t!("removal.reducing", name = volume, replica = replica));
+    let replica = replica.to_string();
+    let mut args = vec!["volume", "remove-brick", volume, "replica", replica.as_str()];
+    args.extend(bricks.iter().map(String::as_str));
+    args.push("force");
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("removal.reduce_failed")))?;
+    println!("{}", t!("removal.reduced", name = volume, replica = replica, bricks = bricks.join(", ")));
+    println!("{}", t!("removal.cleanup_hint"));
+    Ok(())
+}
+
+/// Inicia la migración de los datos fuera de los bricks y la registra.
+pub fn start(backend: &dyn GlusterBackend, volume: &str, bricks: &[String]) -> Result<PendingRemoval> {
+    if let Ok(existing) = pending(backend, volume) {
+        return Err(Error::Message(t!("removal.already_pending", name = volume, bricks = existing.bricks.join(", "))));
+    }
+    validate(backend, volume, bricks, None)?;
+    let removal = PendingRemoval {
+        volume: volume.to_string(),
+        bricks: bricks.to_vec(),
+        started: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+    };
+
+    println!("{}", t!("removal.starting"));
+    backend
+        .sudo_gluster(&args(&removal, "start"))
+        .map_err(|e| e.context(&t!("removal.start_failed")))?;
+
+    let mut all = load(backend);
+    all.push(removal.clone());
+    save(backend, &all)?;
+    println!("{}", t!("removal.started", bricks = removal.bricks.join(", ")));
+    Ok(removal)
+}
+
+/// Progreso de la migración por nodo.
+pub fn status(backend: &dyn GlusterBackend, removal: &PendingRemoval) -> Result<RebalanceStatus> {
+    let output = backend
+        .gluster(&args(removal, "status"))
+        .map_err(|e| e.context(&t!("removal.status_failed", name = removal.volume)))?;
+    crate::rebalance::parse_status(&output)
+}
+
+/// Motivo por el que todavía no se puede confirmar, o `None` si la migración terminó sin fallos.
+pub fn commit_blocker(status: &RebalanceStatus) -> Option<String> {
+    if status.nodes.is_empty() {
+        return Some(t!("removal.no_status"));
+    }
+    if status.running() {
+        return Some(t!("removal.still_running"));
+    }
+    if status.failures() > 0 {
+        return Some(t!("removal.has_failures", count = status.failures()));
+    }
+    status
+        .nodes
+        .iter()
+        .find(|n| n.status != "completed")
+        .map(|n| t!("removal.node_not_completed", node = n.node, status = n.status))
+}
+
+/// Quita definitivamente los bricks; se niega si la migración no terminó limpia.
+pub fn commit(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    let removal = pending(backend, volume)?;
+    if let Some(reason) = commit_blocker(&status(backend, &removal)?) {
+        return Err(Error::Message(t!("removal.cannot_commit", reason = reason)));
+    }
+    backend
+        .sudo_gluster(&args(&removal, "commit"))
+        .map_err(|e| e.context(&t!("removal.commit_failed")))?;
+    forget(backend, volume)?;
+    println!("{}", t!("removal.committed", bricks = removal.bricks.join(", "), name = volume));
+    println!("{}", t!("removal.cleanup_hint"));
+    Ok(())
+}
+
+/// Detiene la migración; los bricks siguen en el volumen.
+pub fn stop(backend: &dyn GlusterBackend, volume: &str) -> Result<()> {
+    let removal = pending(backend, volume)?;
+    backend
+        .sudo_gluster(&args(&removal, "stop"))
+        .map_err(|e| e.context(&t!("removal.stop_failed")))?;
+    forget(backend, volume)?;
+    println!("{}", t!("removal.stopped", name = volume));
+    Ok(())
+}
+
+/// Sigue la migración hasta que termine e indica si ya se puede confirmar.
+pub fn watch(backend: &dyn GlusterBackend, volume: &str, interval: Duration, rounds: u64) -> Result<()> {
+    let removal = pending(backend, volume)?;
+    let Some(last) = crate::rebalance::follow(volume, interval, rounds, || status(backend, &removal))? else {
+        return Err(Error::Message(t!("removal.watch_timeout", name = volume)));
+    };
+    match commit_blocker(&last) {
+        None => {
+            println!("{}", t!("removal.ready", name = volume));
+            Ok(())
+        }
+        Some(reason) => Err(Error::Message(t!("removal.cannot_commit", reason = reason))),
+    }
+}
+
+/// Pantalla de una eliminación en curso: progreso, seguimiento, detener y, si terminó, confirmar.
+pub fn manage_pending(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    loop {
+        let removal = pending(backend, volume)?;
+        let current = status(backend, &removal)?;
+        println!("{}", t!("removal.title", name = volume, bricks = removal.bricks.join(", ")));
+        crate::rebalance::print_progress(volume, &current);
+        let blocker = commit_blocker(&current);
+        if let Some(reason) = &blocker {
+            println!("{}", t!("removal.not_ready", reason = reason));
+        }
+
+        let mut items = vec![t!("removal.menu_refresh"), t!("removal.menu_watch"), t!("removal.menu_stop")];
+        if blocker.is_none() {
+            items.push(t!("removal.menu_commit"));
+        }
+        items.push(t!("volume.menu_back"));
+        let Some(selection) = Select::with_theme(theme)
+            .with_prompt(t!("common.choose_option"))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        match selection {
+            0 => {}
+            1 => crate::error::report(watch(backend, volume, Duration::from_secs(5), 8640)),
+            2 => {
+                if confirm(theme, &t!("removal.stop_confirm", name = volume))? {
+                    crate::error::report(stop(backend, volume));
+                    break;
+                }
+            }
+            3 if blocker.is_none() => {
+                if confirm(theme, &t!("removal.commit_confirm", bricks = removal.bricks.join(", "), name = volume))? {
+                    crate::error::report(commit(backend, volume));
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+fn confirm(theme: &ColorfulTheme, prompt: &str) -> Result<bool> {
+    Confirm::with_theme(theme)
+        .with_prompt(prompt)
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)
+}
+
+/// Si hay eliminaciones a medias, ofrece retomar una en lugar de empezar otra.
+/// Devuelve `true` si el operador eligió retomar.
+pub fn offer_resume(backend: &dyn GlusterBackend, theme: &ColorfulTheme) -> Result<bool> {
+    let pending = load(backend);
+    if pending.is_empty() {
+        return Ok(false);
+    }
+    let mut items: Vec<String> = pending
+        .iter()
+        .map(|p| t!("removal.resume_item", name = p.volume, bricks = p.bricks.join(", "), started = p.started))
+        .collect();
+    items.push(t!("removal.new_removal"));
+    let selection = Select::with_theme(theme)
+        .with_prompt(t!("removal.resume_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    match pending.get(selection) {
+        Some(removal) => manage_pending(backend, theme, &removal.volume).map(|_| true),
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn distributed() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started)
+    }
+
+    #[test]
+    fn start_is_recorded_and_resumable() {
+        let fake = distributed();
+        start(&fake, "datos", &["vm2:/gluster/a".to_string()]).unwrap();
+        assert_eq!(fake.volume("datos").unwrap().removing, vec!["vm2:/gluster/a"]);
+        assert_eq!(pending(&fake, "datos").unwrap().bricks, vec!["vm2:/gluster/a"]);
+        assert!(start(&fake, "datos", &["vm1:/gluster/a".to_string()]).is_err());
+    }
+
+    #[test]
+    fn commit_only_after_a_clean_migration() {
+        let fake = distributed();
+        start(&fake, "datos", &["vm2:/gluster/a".to_string()]).unwrap();
+
+        // La primera consulta la ve en curso.
+        assert!(commit(&fake, "datos").is_err());
+        assert_eq!(fake.volume("datos").unwrap().bricks.len(), 2);
+
+        commit(&fake, "datos").unwrap();
+        assert_eq!(fake.volume("datos").unwrap().bricks, vec!["vm1:/gluster/a"]);
+        assert!(load(&fake).is_empty());
+        assert_eq!(
+            fake.commands().last().unwrap(),
+            "gluster --mode=script volume remove-brick datos vm2:/gluster/a commit"
+        );
+    }
+
+    #[test]
+    fn failures_block_the_commit() {
+        let fake = distributed();
+        start(&fake, "datos", &["vm2:/gluster/a".to_string()]).unwrap();
+        let fake = fake.with_removal_failures("datos", 3);
+        watch(&fake, "datos", Duration::ZERO, 5).unwrap_err();
+        assert!(commit(&fake, "datos").is_err());
+        assert_eq!(fake.volume("datos").unwrap().bricks.len(), 2);
+    }
+
+    fn replicated() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_peer("vm3", true)
+            .with_volume(
+                "datos",
+                &["vm1:/gluster/a", "vm2:/gluster/a", "vm3:/gluster/a", "vm1:/gluster/b", "vm2:/gluster/b", "vm3:/gluster/b"],
+                VolumeState::Started,
+            )
+            .with_replica("datos", 3)
+    }
+
+    fn names(bricks: &[&str]) -> Vec<String> {
+        bricks.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn selection_follows_the_layout() {
+        let bricks = names(&["a:/1", "b:/1", "c:/1", "a:/2", "b:/2", "c:/2"]);
+        let replica3 = VolumeLayout::Replicate { replica: 3, arbiter: 0 };
+        assert!(check_selection(&replica3, &bricks, &names(&["a:/2", "b:/2", "c:/2"]), None).is_ok());
+        assert!(check_selection(&replica3, &bricks, &names(&["a:/1", "b:/1"]), None).is_err());
+        assert!(check_selection(&replica3, &bricks, &bricks, None).is_err());
+        assert!(check_selection(&replica3, &bricks, &[], None).is_err());
+        assert!(check_selection(&replica3, &bricks, &names(&["x:/1", "b:/1", "c:/1"]), None).is_err());
+
+        // Reducir la réplica: los mismos bricks de cada conjunto.
+        assert!(check_selection(&replica3, &bricks, &names(&["c:/1", "c:/2"]), Some(2)).is_ok());
+        assert!(check_selection(&replica3, &bricks, &names(&["c:/1", "c:/1"]), Some(2)).is_err());
+        assert!(check_selection(&replica3, &bricks, &names(&["b:/1", "c:/1", "c:/2"]), Some(2)).is_err());
+        assert!(check_selection(&replica3, &bricks, &names(&["b:/1", "c:/1", "b:/2", "c:/2"]), Some(1)).is_ok());
+        assert!(check_selection(&replica3, &bricks, &names(&["c:/1", "c:/2"]), Some(3)).is_err());
+
+        let arbiter = VolumeLayout::Replicate { replica: 3, arbiter: 1 };
+        assert!(check_selection(&arbiter, &bricks, &names(&["c:/1", "c:/2"]), Some(2)).is_ok());
+        assert!(check_selection(&arbiter, &bricks, &names(&["a:/1", "a:/2"]), Some(2)).is_err());
+
+        let disperse = VolumeLayout::Disperse { disperse: 3, redundancy: 1 };
+        assert!(check_selection(&disperse, &bricks, &names(&["c:/1", "c:/2"]), Some(2)).is_err());
+        assert!(check_selection(&VolumeLayout::Distribute, &bricks, &names(&["c:/1", "c:/2"]), None).is_ok());
+    }
+
+    #[test]
+    fn start_rejects_partial_sets_before_calling_gluster() {
+        let fake = replicated();
+        assert!(start(&fake, "datos", &names(&["vm1:/gluster/b", "vm2:/gluster/b"])).is_err());
+        assert!(!fake.commands().iter().any(|c| c.contains("remove-brick")));
+
+        start(&fake, "datos", &names(&["vm1:/gluster/b", "vm2:/gluster/b", "vm3:/gluster/b"])).unwrap();
+        assert_eq!(fake.volume("datos").unwrap().removing.len(), 3);
+    }
+
+    #[test]
+    fn reduce_replica_removes_one_brick_per_set() {
+        let fake = replicated();
+        reduce_replica(&fake, "datos", 2, &names(&["vm3:/gluster/a", "vm3:/gluster/b"])).unwrap();
+        assert_eq!(
+            fake.commands().last().unwrap(),
+            "gluster --mode=script volume remove-brick datos replica 2 vm3:/gluster/a vm3:/gluster/b force"
+        );
+        let volume = fake.volume("datos").unwrap();
+        assert_eq!(volume.replica, 2);
+        assert_eq!(volume.bricks.len(), 4);
+        assert!(load(&fake).is_empty());
+    }
+
+    #[test]
+    fn reduce_replica_waits_for_pending_heals() {
+        let fake = replicated().with_pending_heal("datos", "vm1:/gluster/a", &["/docs/informe.odt"]);
+        assert!(reduce_replica(&fake, "datos", 2, &names(&["vm3:/gluster/a", "vm3:/gluster/b"])).is_err());
+        assert_eq!(fake.volume("datos").unwrap().replica, 3);
+    }
+
+    #[test]
+    fn reduce_replica_refuses_when_a_remaining_brick_is_offline() {
+        let fake = replicated().with_offline_brick("datos", "vm1:/gluster/b");
+        let err = reduce_replica(&fake, "datos", 2, &names(&["vm3:/gluster/a", "vm3:/gluster/b"])).unwrap_err();
+        assert!(err.to_string().contains("vm1:/gluster/b"));
+        assert_eq!(fake.volume("datos").unwrap().replica, 3);
+
+        let fake = replicated().with_offline_brick("datos", "vm3:/gluster/a");
+        reduce_replica(&fake, "datos", 2, &names(&["vm3:/gluster/a", "vm3:/gluster/b"])).unwrap();
+        assert_eq!(fake.volume("datos").unwrap().replica, 2);
+    }
+
+    #[test]
+    fn reduce_replica_refuses_when_the_heal_summary_fails() {
+        let fake = replicated().with_command_failure("gluster volume heal datos info", "Volume heal failed.");
+        assert!(reduce_replica(&fake, "datos", 2, &names(&["vm3:/gluster/a", "vm3:/gluster/b"])).is_err());
+        assert_eq!(fake.volume("datos").unwrap().replica, 3);
+    }
+
+    #[test]
+    fn stop_keeps_the_bricks() {
+        let fake = distributed();
+        start(&fake, "datos", &["vm2:/gluster/a".to_string()]).unwrap();
+        stop(&fake, "datos").unwrap();
+        assert!(fake.volume("datos").unwrap().removing.is_empty());
+        assert_eq!(fake.volume("datos").unwrap().bricks.len(), 2);
+        assert!(pending(&fake, "datos").is_err());
+    }
+}
@@ -0,0 +1,297 @@
+//! Sustituir un brick averiado: `replace-brick` lo cambia por otro en otra ruta u otro nodo y
+//! `reset-brick` lo reincorpora en la misma ruta con un disco nuevo. En los dos casos el brick
+//! nuevo se comprueba antes (nodo sano, directorio vacío y fuera de la partición raíz) y después
+//! se sigue la reparación hasta que tiene todas las copias.
+
+use std::time::Duration;
+
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+use crate::volinfo::{self, VolumeInfo};
+
+/// Directorios que un sistema de ficheros recién creado ya trae y no cuentan como datos.
+const FRESH_FS_ENTRIES: [&str; 1] = ["lost+found"];
+
+/// Dispositivo que monta `path`, o el de su directorio padre si todavía no existe.
+fn device_of(backend: &dyn GlusterBackend, path: &str) -> Option<String> {
+    let mut target = path;
+    while !backend.exists(target) && target != "/" {
+        target = match target.rsplit_once('/') {
+            Some(("", _)) | None => "/",
+            Some((parent, _)) => parent,
+        };
+    }
+    backend
+        .command("findmnt", &["-n", "-o", "SOURCE", "--target", target])
+        .ok()
+        .map(|out| out.trim().to_string())
+        .filter(|device| !device.is_empty())
+}
+
+/// Comprueba que el brick nuevo esté en un nodo sano y, si es de este nodo, vacío y fuera de la
+/// partición raíz. Devuelve los avisos de lo que no se pudo comprobar.
+pub fn check_new_brick(backend: &dyn GlusterBackend, brick: &str) -> Result<Vec<String>> {
+    if !crate::volume::is_valid_brick(brick) {
+        return Err(Error::Message(t!("replace.invalid_brick", brick = brick)));
+    }
+    let local = backend.local_hostname();
+    let peers = crate::pool::peers(backend)?;
+    if let Some(problem) = crate::placement::check_hosts(&[brick.to_string()], &peers, &local).pop() {
+        return Err(Error::Message(problem));
+    }
+
+    let (host, path) = brick.split_once(':').unwrap_or(("", brick));
+    if host != local && host != "localhost" {
+        return Ok(vec![t!("replace.remote_unchecked", brick = brick)]);
+    }
+
+    if backend.exists(path) {
+        let mut entries = backend.list_dirs(path).unwrap_or_default();
+        entries.extend(backend.list_files(path).unwrap_or_default());
+        if entries.iter().any(|e| !FRESH_FS_ENTRIES.contains(&e.as_str())) {
+            return Err(Error::Message(t!("replace.not_empty", path = path)));
+        }
+    }
+    match (device_of(backend, path), device_of(backend, "/")) {
+        (Some(device), Some(root)) if device == root => {
+            Err(Error::Message(t!("replace.on_root", path = path, device = device)))
+        }
+        (Some(_), Some(_)) => Ok(vec![]),
+        _ => Ok(vec![t!("replace.device_unknown", path = path)]),
+    }
+}
+
+/// Solo se sustituyen bricks de volúmenes iniciados con réplica o dispersión: en uno distribuido
+/// los datos del brick se perderían y sin iniciar no hay reparación que los copie.
+fn check_volume(backend: &dyn GlusterBackend, volume: &str, brick: &str) -> Result<VolumeInfo> {
+    let info = volinfo::fetch_one(backend, volume)?;
+    if info.replica_count <= 1 && info.disperse_count == 0 {
+        return Err(Error::Message(t!("replace.distribute", name = volume)));
+    }
+    if info.status != "Started" {
+        return Err(Error::Message(t!("replace.not_started", name = volume)));
+    }
+    if !info.brick_names().iter().any(|b| b == brick) {
+        return Err(Error::Message(t!("replace.unknown_brick", brick = brick, name = volume)));
+    }
+    Ok(info)
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        println!("{}", warning);
+    }
+}
+
+/// Todas las comprobaciones previas a `replace-brick`; devuelve los avisos que no lo impiden.
+pub fn check_replacement(backend: &dyn GlusterBackend, volume: &str, old: &str, new: &str) -> Result<Vec<String>> {
+    check_volume(backend, volume, old)?;
+    if let Some(other) = volinfo::fetch(backend, None)?
+        .into_iter()
+        .find(|v| v.brick_names().iter().any(|b| b == new))
+    {
+        return Err(Error::Message(t!("replace.in_use", brick = new, name = other.name)));
+    }
+    check_new_brick(backend, new)
+}
+
+/// Cambia `old` por `new` (`replace-brick ... commit force`); la reparación rellena `new`.
+pub fn replace(backend: &dyn GlusterBackend, volume: &str, old: &str, new: &str) -> Result<()> {
+    print_warnings(&check_replacement(backend, volume, old, new)?);
+    replace_checked(backend, volume, old, new)
+}
+
+/// Como [`replace`], para quien ya pasó [`check_replacement`].
+fn replace_checked(backend: &dyn GlusterBackend, volume: &str, old: &str, new: &str) -> Result<()> {
+    println!("{}", t!("replace.replacing", old = old, new = new));
+    backend
+        .sudo_gluster(&["volume", "replace-brick", volume, old, new, "commit", "force"])
+        .map_err(|e| e.context(&t!("replace.replace_failed")))?;
+    println!("{}", t!("replace.replaced", old = old, new = new, name = volume));
+    Ok(())
+}
+
+/// Detiene el brick para cambiar su disco (`reset-brick ... start`).
+pub fn reset_start(backend: &dyn GlusterBackend, volume: &str, brick: &str) -> Result<()> {
+    check_volume(backend, volume, brick)?;
+    println!("{}", t!("replace.reset_starting", brick = brick));
+    backend
+        .sudo_gluster(&["volume", "reset-brick", volume, brick, "start"])
+        .map_err(|e| e.context(&t!("replace.reset_start_failed")))?;
+    let path = brick.split_once(':').map_or(brick, |(_, path)| path);
+    println!("{}", t!("replace.reset_started", path = path));
+    Ok(())
+}
+
+/// Reincorpora el brick con el disco nuevo ya montado (`reset-brick ... commit force`).
+pub fn reset_commit(backend: &dyn GlusterBackend, volume: &str, brick: &str) -> Result<()> {
+    check_volume(backend, volume, brick)?;
+    print_warnings(&check_new_brick(backend, brick)?);
+
+    println!("{}", t!("replace.resetting", brick = brick, name = volume));
+    backend
+        .sudo_gluster(&["volume", "reset-brick", volume, brick, brick, "commit", "force"])
+        .map_err(|e| e.context(&t!("replace.reset_failed")))?;
+    println!("{}", t!("replace.reset_done", brick = brick, name = volume));
+    Ok(())
+}
+
+/// Sigue la reparación que llena el brick nuevo.
+pub fn watch_heal(backend: &dyn GlusterBackend, volume: &str, interval: Duration, rounds: u64) -> Result<()> {
+    println!("{}", t!("replace.watching"));
+    crate::heal::watch(backend, volume, interval, rounds)
+}
+
+fn confirm(theme: &ColorfulTheme, prompt: &str) -> Result<bool> {
+    Confirm::with_theme(theme)
+        .with_prompt(prompt)
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)
+}
+
+/// Asistente: elegir el brick averiado, sustituirlo o cambiar su disco y seguir la reparación.
+pub fn manage_replace(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+    let info = volinfo::fetch_one(backend, &volume)?;
+    let bricks = info.brick_names();
+
+    // Los bricks caídos son los candidatos habituales
+    let status = crate::volstatus::fetch(backend, Some(&volume)).ok().and_then(|mut v| v.pop());
+    let offline = |b: &String| status.as_ref().and_then(|s| s.brick(b)).is_some_and(|brick| !brick.online);
+    let labels: Vec<String> = bricks
+        .iter()
+        .map(|b| if offline(b) { t!("volume.brick_offline", brick = b) } else { b.clone() })
+        .collect();
+    let index = Select::with_theme(&theme)
+        .with_prompt(t!("replace.brick_prompt"))
+        .items(&labels)
+        .default(bricks.iter().position(offline).unwrap_or(0))
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let brick = &bricks[index];
+
+    let modes = [t!("replace.mode_replace"), t!("replace.mode_reset")];
+    let mode = Select::with_theme(&theme)
+        .with_prompt(t!("replace.mode_prompt", brick = brick))
+        .items(&modes)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    if mode == 0 {
+        let new: String = Input::with_theme(&theme)
+            .with_prompt(t!("replace.new_brick_prompt"))
+            .interact_text()?;
+        let new = new.trim();
+        // Comprobar antes de preguntar, para no pedir confirmación de algo que gluster rechazaría
+        print_warnings(&check_replacement(backend, &volume, brick, new)?);
+        if !confirm(&theme, &t!("replace.replace_confirm", old = brick, new = new, name = volume))? {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
+        }
+        replace_checked(backend, &volume, brick, new)?;
+    } else {
+        let path = brick.split_once(':').map_or(brick.as_str(), |(_, path)| path);
+        // Si el proceso del brick ya está caído no hace falta detenerlo
+        if !offline(brick) {
+            if !confirm(&theme, &t!("replace.reset_confirm", brick = brick))? {
+                println!("{}", t!("common.cancelled"));
+                return Ok(());
+            }
+            reset_start(backend, &volume, brick)?;
+        }
+        if !confirm(&theme, &t!("replace.disk_ready_confirm", path = path))? {
+            println!("{}", t!("replace.reset_commit_hint", name = volume, brick = brick));
+            return Ok(());
+        }
+        reset_commit(backend, &volume, brick)?;
+    }
+    watch_heal(backend, &volume, Duration::from_secs(10), 8640)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn replicated() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_peer("vm3", false)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started)
+            .with_replica("datos", 2)
+            .with_filesystem("/gluster", "/dev/sdb1", None)
+            .with_dir("/gluster")
+    }
+
+    #[test]
+    fn new_brick_must_be_empty_healthy_and_off_the_root_disk() {
+        let fake = replicated()
+            .with_file("/gluster/usado/fichero", "datos")
+            .with_dir("/gluster/usado")
+            .with_dir("/gluster/nuevo")
+            .with_dir("/gluster/nuevo/lost+found");
+        assert!(check_new_brick(&fake, "vm1:/gluster/nuevo").unwrap().is_empty());
+        assert!(check_new_brick(&fake, "vm1:/gluster/usado").is_err());
+        assert!(check_new_brick(&fake, "vm1:/srv/brick").is_err());
+        assert!(check_new_brick(&fake, "vm3:/gluster/a").is_err());
+        assert!(check_new_brick(&fake, "vm9:/gluster/a").is_err());
+        assert!(check_new_brick(&fake, "sin-ruta").is_err());
+        assert_eq!(check_new_brick(&fake, "vm2:/gluster/b").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn replace_swaps_the_brick_and_heals() {
+        let fake = replicated();
+        assert!(replace(&fake, "datos", "vm2:/gluster/a", "vm1:/gluster/a").is_err());
+        replace(&fake, "datos", "vm2:/gluster/a", "vm1:/gluster/b").unwrap();
+        assert_eq!(
+            fake.commands().last().unwrap(),
+            "gluster --mode=script volume replace-brick datos vm2:/gluster/a vm1:/gluster/b commit force"
+        );
+        assert_eq!(fake.volume("datos").unwrap().bricks, vec!["vm1:/gluster/a", "vm1:/gluster/b"]);
+        watch_heal(&fake, "datos", Duration::ZERO, 5).unwrap();
+    }
+
+    #[test]
+    fn replace_needs_a_replicated_started_volume() {
+        let fake = replicated().with_volume("plano", &["vm1:/gluster/p"], VolumeState::Started);
+        assert!(replace(&fake, "plano", "vm1:/gluster/p", "vm1:/gluster/q").is_err());
+        let fake = fake.with_state("datos", VolumeState::Stopped);
+        assert!(replace(&fake, "datos", "vm2:/gluster/a", "vm1:/gluster/b").is_err());
+        assert!(fake.commands().is_empty());
+    }
+
+    #[test]
+    fn check_replacement_rejects_a_brick_in_use_before_any_change() {
+        let fake = replicated().with_volume("otro", &["vm1:/gluster/x"], VolumeState::Started);
+        let err = check_replacement(&fake, "datos", "vm2:/gluster/a", "vm1:/gluster/x").unwrap_err();
+        assert!(err.to_string().contains("otro"));
+        assert!(check_replacement(&fake, "datos", "vm2:/gluster/a", "vm1:/gluster/b").unwrap().is_empty());
+        assert!(fake.commands().is_empty());
+    }
+
+    #[test]
+    fn reset_brick_in_two_steps() {
+        let fake = replicated();
+        // Sin detenerlo antes gluster no lo reincorpora.
+        assert!(reset_commit(&fake, "datos", "vm1:/gluster/a").is_err());
+
+        reset_start(&fake, "datos", "vm1:/gluster/a").unwrap();
+        assert!(fake.volume("datos").unwrap().offline.contains(&"vm1:/gluster/a".to_string()));
+        reset_commit(&fake, "datos", "vm1:/gluster/a").unwrap();
+        assert!(fake.volume("datos").unwrap().offline.is_empty());
+        assert_eq!(
+            fake.commands().last().unwrap(),
+            "gluster --mode=script volume reset-brick datos vm1:/gluster/a vm1:/gluster/a commit force"
+        );
+        watch_heal(&fake, "datos", Duration::ZERO, 5).unwrap();
+    }
+}
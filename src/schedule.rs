@@ -0,0 +1,628 @@
+//! Snapshots programados: políticas por volumen (cada hora, día o semana, conservando los N
+//! más recientes) en `/etc/cluster_almacenamiento/snapshots.toml`.
+//!
+//! ```toml
+//! [[policy]]
+//! volume = "datos"
+//! frequency = "daily"   # hourly | daily | weekly
+//! keep = 7
+//! ```
+//!
+//! Una entrada de cron ejecuta `snapshot run-schedule` cada hora: crea los snapshots que tocan,
+//! borra los que sobran y guarda el resultado de cada política para detectar ejecuciones perdidas.
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, FixedOffset};
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::GlusterBackend;
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::t;
+
+pub const SCHEDULE_PATH: &str = "/etc/cluster_almacenamiento/snapshots.toml";
+pub const STATE_PATH: &str = "/var/lib/cluster_almacenamiento/snapshot-schedule.json";
+pub const CRON_PATH: &str = "/etc/cron.d/cluster_almacenamiento-snapshots";
+pub const LOG_PATH: &str = "/var/log/cluster_almacenamiento/snapshots.log";
+
+/// Margen para que un cron horario que arranca unos minutos tarde no se salte una ejecución.
+const SLACK_MINUTES: i64 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Frequency {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl Frequency {
+    pub const ALL: [Frequency; 3] = [Frequency::Hourly, Frequency::Daily, Frequency::Weekly];
+
+    pub fn period(self) -> Duration {
+        match self {
+            Frequency::Hourly => Duration::hours(1),
+            Frequency::Daily => Duration::days(1),
+            Frequency::Weekly => Duration::weeks(1),
+        }
+    }
+
+    /// Nombre en los snapshots y en el fichero de políticas.
+    pub fn key(self) -> &'static str {
+        match self {
+            Frequency::Hourly => "hourly",
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+        }
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Frequency::Hourly => t!("schedule.frequency.hourly"),
+            Frequency::Daily => t!("schedule.frequency.daily"),
+            Frequency::Weekly => t!("schedule.frequency.weekly"),
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    pub volume: String,
+    pub frequency: Frequency,
+    /// Snapshots de esta política que se conservan.
+    pub keep: usize,
+}
+
+impl Policy {
+    /// Prefijo de los snapshots de la política: `datos-daily-`.
+    pub fn prefix(&self) -> String {
+        format!("{}-{}-", self.volume, self.frequency.key())
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    #[serde(default, rename = "policy")]
+    pub policies: Vec<Policy>,
+}
+
+impl Schedule {
+    pub fn parse(text: &str) -> Result<Self> {
+        let schedule: Schedule = toml::from_str(text).map_err(|e| Error::Parse(t!("schedule.invalid", path = SCHEDULE_PATH, error = e)))?;
+        if let Some(policy) = schedule.policies.iter().find(|p| p.keep == 0) {
+            return Err(Error::Parse(t!("schedule.keep_zero", name = policy.volume)));
+        }
+        Ok(schedule)
+    }
+
+    pub fn load(backend: &dyn GlusterBackend) -> Result<Self> {
+        if !backend.exists(SCHEDULE_PATH) {
+            return Ok(Schedule::default());
+        }
+        let text = backend
+            .read_file(SCHEDULE_PATH)
+            .map_err(|e| Error::Message(t!("schedule.read_failed", path = SCHEDULE_PATH, error = e)))?;
+        Self::parse(&text)
+    }
+
+    pub fn save(&self, backend: &dyn GlusterBackend) -> Result<()> {
+        let text = toml::to_string(self).map_err(|e| Error::Message(e.to_string()))?;
+        backend
+            .create_dir_all("/etc/cluster_almacenamiento")
+            .and_then(|_| backend.write_file(SCHEDULE_PATH, &text))
+            .map_err(|e| Error::Message(t!("schedule.write_failed", path = SCHEDULE_PATH, error = e)))
+    }
+
+    /// Añade la política o cambia cuántos snapshots conserva si ya existía.
+    pub fn set(&mut self, volume: &str, frequency: Frequency, keep: usize) -> Result<()> {
+        if keep == 0 {
+            return Err(Error::Message(t!("schedule.keep_zero", name = volume)));
+        }
+        match self.policies.iter_mut().find(|p| p.volume == volume && p.frequency == frequency) {
+            Some(policy) => policy.keep = keep,
+            None => self.policies.push(Policy { volume: volume.to_string(), frequency, keep }),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, volume: &str, frequency: Frequency) -> bool {
+        let before = self.policies.len();
+        self.policies.retain(|p| !(p.volume == volume && p.frequency == frequency));
+        self.policies.len() != before
+    }
+}
+
+/// Resultado de la última ejecución de una política.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunState {
+    pub volume: String,
+    pub frequency: Frequency,
+    /// Desde cuándo existe la política; cuenta para las ejecuciones perdidas si nunca tuvo éxito.
+    pub since: String,
+    #[serde(default)]
+    pub last_success: Option<String>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl RunState {
+    fn new(policy: &Policy, now: DateTime<FixedOffset>) -> Self {
+        Self {
+            volume: policy.volume.clone(),
+            frequency: policy.frequency,
+            since: timestamp(now),
+            last_success: None,
+            last_error: None,
+        }
+    }
+
+    fn matches(&self, policy: &Policy) -> bool {
+        self.volume == policy.volume && self.frequency == policy.frequency
+    }
+
+    /// Última ejecución correcta o, si no hubo ninguna, la creación de la política.
+    fn reference(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(self.last_success.as_deref().unwrap_or(&self.since)).ok()
+    }
+}
+
+fn timestamp(time: DateTime<FixedOffset>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+pub fn now() -> DateTime<FixedOffset> {
+    chrono::Local::now().fixed_offset()
+}
+
+pub fn load_state(backend: &dyn GlusterBackend) -> Vec<RunState> {
+    // Un estado ilegible solo hace que todas las políticas se consideren pendientes.
+    backend
+        .read_file(STATE_PATH)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(backend: &dyn GlusterBackend, states: &[RunState]) -> Result<()> {
+    let json = serde_json::to_string_pretty(states).map_err(|e| Error::Message(e.to_string()))?;
+    backend
+        .create_dir_all("/var/lib/cluster_almacenamiento")
+        .and_then(|_| backend.write_file(STATE_PATH, &(json + "\n")))
+        .map_err(|e| Error::Message(t!("schedule.write_failed", path = STATE_PATH, error = e)))
+}
+
+/// Toca crear un snapshot: nunca se creó uno o ya pasó el periodo desde el último.
+pub fn is_due(policy: &Policy, state: Option<&RunState>, now: DateTime<FixedOffset>) -> bool {
+    match state.and_then(|s| s.last_success.as_deref()).and_then(|t| DateTime::parse_from_rfc3339(t).ok()) {
+        Some(last) => now >= last + policy.frequency.period() - Duration::minutes(SLACK_MINUTES),
+        None => true,
+    }
+}
+
+/// Ejecuciones que deberían haberse hecho y no se hicieron (cron parado, errores, nodo apagado).
+pub fn missed_runs(policy: &Policy, state: Option<&RunState>, now: DateTime<FixedOffset>) -> i64 {
+    let Some(reference) = state.and_then(RunState::reference) else {
+        return 0;
+    };
+    let elapsed = now - reference + Duration::minutes(SLACK_MINUTES);
+    // La ejecución que toca ahora no cuenta como perdida.
+    (elapsed.num_seconds() / policy.frequency.period().num_seconds() - 1).max(0)
+}
+
+/// Borra los snapshots de la política más antiguos que los `keep` más recientes.
+pub fn prune(backend: &dyn GlusterBackend, policy: &Policy) -> Result<Vec<String>> {
+    let prefix = policy.prefix();
+    // Los nombres llevan la fecha `AAAAMMDD-HHMM`, así que el orden alfabético es el cronológico.
+    let mut names: Vec<String> = crate::snapshot::list(backend, Some(&policy.volume))?
+        .into_iter()
+        .map(|s| s.name)
+        .filter(|name| name.starts_with(&prefix))
+        .collect();
+    names.sort();
+
+    let excess = names.len().saturating_sub(policy.keep);
+    let old: Vec<String> = names.into_iter().take(excess).collect();
+    for name in &old {
+        crate::snapshot::delete(backend, name)?;
+    }
+    Ok(old)
+}
+
+/// Lo que hizo una ejecución del planificador.
+#[derive(Debug, Default, PartialEq)]
+pub struct RunReport {
+    pub created: Vec<String>,
+    pub pruned: Vec<String>,
+    /// (política, error) de las que fallaron.
+    pub failed: Vec<(String, String)>,
+    /// (política, ejecuciones perdidas).
+    pub missed: Vec<(String, i64)>,
+}
+
+/// Ejecuta las políticas que tocan: crea el snapshot, borra los sobrantes y guarda el resultado.
+pub fn run(backend: &dyn GlusterBackend, now: DateTime<FixedOffset>) -> Result<RunReport> {
+    let schedule = Schedule::load(backend)?;
+    let mut states = load_state(backend);
+    // Las políticas eliminadas dejan de tener estado.
+    states.retain(|s| schedule.policies.iter().any(|p| s.matches(p)));
+    let mut report = RunReport::default();
+
+    for policy in &schedule.policies {
+        let label = t!("schedule.policy_label", name = policy.volume, frequency = policy.frequency);
+        if !states.iter().any(|s| s.matches(policy)) {
+            states.push(RunState::new(policy, now));
+        }
+        let Some(state) = states.iter_mut().find(|s| s.matches(policy)) else {
+            continue;
+        };
+
+        let missed = missed_runs(policy, Some(state), now);
+        if missed > 0 {
+            println!("{}", t!("schedule.missed", policy = label, count = missed));
+            report.missed.push((label.clone(), missed));
+        }
+        if !is_due(policy, Some(state), now) {
+            continue;
+        }
+
+        let name = format!("{}{}", policy.prefix(), now.format("%Y%m%d-%H%M"));
+        let result = crate::snapshot::create(backend, &policy.volume, &name, Some(&t!("schedule.description")), true)
+            .and_then(|created| prune(backend, policy).map(|pruned| (created, pruned)));
+        match result {
+            Ok((created, pruned)) => {
+                state.last_success = Some(timestamp(now));
+                state.last_error = None;
+                report.created.push(created);
+                report.pruned.extend(pruned);
+            }
+            Err(e) => {
+                println!("❌ {}: {}", label, e);
+                state.last_error = Some(e.to_string());
+                report.failed.push((label, e.to_string()));
+            }
+        }
+    }
+
+    save_state(backend, &states)?;
+    if report.failed.is_empty() {
+        Ok(report)
+    } else {
+        // Con cron, un código de salida distinto de 0 deja constancia del fallo.
+        Err(Error::Message(t!("schedule.run_failed", count = report.failed.len())))
+    }
+}
+
+/// Entrada de cron que lanza el planificador cada hora.
+pub fn cron_entry(exe: &str) -> String {
+    format!(
+        "# {}\nSHELL=/bin/sh\n17 * * * * root {} snapshot run-schedule >> {} 2>&1\n",
+        t!("schedule.cron_header"),
+        exe,
+        LOG_PATH
+    )
+}
+
+pub fn install_cron(backend: &dyn GlusterBackend, exe: &str) -> Result<()> {
+    backend
+        .create_dir_all("/var/log/cluster_almacenamiento")
+        .and_then(|_| backend.write_file(CRON_PATH, &cron_entry(exe)))
+        .map_err(|e| Error::Message(t!("schedule.write_failed", path = CRON_PATH, error = e)))?;
+    println!("{}", t!("schedule.cron_installed", path = CRON_PATH));
+    Ok(())
+}
+
+/// Quita la entrada de cron cuando ya no queda ninguna política.
+pub fn uninstall_cron(backend: &dyn GlusterBackend) -> Result<()> {
+    backend
+        .remove_file(CRON_PATH)
+        .map_err(|e| Error::Message(t!("schedule.remove_failed", path = CRON_PATH, error = e)))?;
+    println!("{}", t!("schedule.cron_removed", path = CRON_PATH));
+    Ok(())
+}
+
+/// Guarda las políticas y deja cron al día: reescrito si su entrada no es la esperada,
+/// retirado si no queda ninguna política.
+pub fn save_and_install(backend: &dyn GlusterBackend, schedule: &Schedule) -> Result<()> {
+    schedule.save(backend)?;
+    if schedule.policies.is_empty() {
+        if backend.exists(CRON_PATH) {
+            uninstall_cron(backend)?;
+        }
+        return Ok(());
+    }
+
+    let config = Config::load(backend)?;
+    let exe = config.executable();
+    if !backend.exists(exe) {
+        println!("{}", t!("schedule.missing_executable", path = exe, config = crate::config::CONFIG_PATH));
+    }
+    if backend.read_file(CRON_PATH).ok() != Some(cron_entry(exe)) {
+        install_cron(backend, exe)?;
+    }
+    Ok(())
+}
+
+/// Tabla de políticas con su última ejecución, la próxima y las perdidas.
+pub fn print_status(backend: &dyn GlusterBackend, schedule: &Schedule, now: DateTime<FixedOffset>) {
+    println!("{}", t!("schedule.title"));
+    if schedule.policies.is_empty() {
+        println!("{}", t!("schedule.no_policies"));
+        return;
+    }
+    if !backend.exists(CRON_PATH) {
+        println!("{}", t!("schedule.no_cron", path = CRON_PATH));
+    }
+
+    let states = load_state(backend);
+    for policy in &schedule.policies {
+        let state = states.iter().find(|s| s.matches(policy));
+        let last = state.and_then(|s| s.last_success.clone()).unwrap_or_else(|| t!("schedule.never"));
+        let next = match state.and_then(|s| s.last_success.as_deref()).and_then(|t| DateTime::parse_from_rfc3339(t).ok()) {
+            Some(last) if !is_due(policy, state, now) => timestamp(last + policy.frequency.period()),
+            _ => t!("schedule.next_run"),
+        };
+        println!(
+            "{}",
+            t!(
+                "schedule.status_line",
+                name = policy.volume,
+                frequency = policy.frequency,
+                keep = policy.keep,
+                last = last,
+                next = next
+            )
+        );
+        let missed = missed_runs(policy, state, now);
+        if missed > 0 {
+            println!("      ⚠️ {}", t!("schedule.missed_short", count = missed));
+        }
+        if let Some(error) = state.and_then(|s| s.last_error.as_ref()) {
+            println!("      ❌ {}", error);
+        }
+    }
+}
+
+/// Pantalla de snapshots programados de un volumen.
+pub fn manage_schedule(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let Some(volume) = crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+
+    loop {
+        let items = [
+            t!("schedule.menu_status"),
+            t!("schedule.menu_set"),
+            t!("schedule.menu_remove"),
+            t!("schedule.menu_run"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("schedule.menu_title", name = volume))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => Schedule::load(backend).map(|schedule| {
+                let policies = schedule.policies.into_iter().filter(|p| p.volume == volume).collect();
+                print_status(backend, &Schedule { policies }, now());
+            }),
+            1 => set_interactive(backend, &theme, &volume),
+            2 => remove_interactive(backend, &theme, &volume),
+            3 => run(backend, now()).map(|report| {
+                println!("{}", t!("schedule.run_summary", created = report.created.len(), pruned = report.pruned.len()))
+            }),
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+fn set_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    let mut schedule = Schedule::load(backend)?;
+
+    let frequency = Select::with_theme(theme)
+        .with_prompt(t!("schedule.frequency_prompt"))
+        .items(&Frequency::ALL)
+        .default(1)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let frequency = Frequency::ALL[frequency];
+
+    let current = schedule
+        .policies
+        .iter()
+        .find(|p| p.volume == volume && p.frequency == frequency)
+        .map(|p| p.keep);
+    let default_keep = match frequency {
+        Frequency::Hourly => 24,
+        Frequency::Daily => 7,
+        Frequency::Weekly => 4,
+    };
+    let keep: usize = Input::with_theme(theme)
+        .with_prompt(t!("schedule.keep_prompt"))
+        .default(current.unwrap_or(default_keep))
+        .validate_with(|keep: &usize| if *keep > 0 { Ok(()) } else { Err(t!("schedule.keep_zero", name = volume)) })
+        .interact_text()?;
+
+    // Los snapshots programados fallarán igual que los manuales si los bricks no están en LVM thin.
+    let check = crate::snapshot::preflight(backend, volume)?;
+    if !check.ok() {
+        check.print();
+        if !Confirm::with_theme(theme)
+            .with_prompt(t!("schedule.save_anyway"))
+            .default(false)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?
+        {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
+        }
+    }
+
+    schedule.set(volume, frequency, keep)?;
+    save_and_install(backend, &schedule)?;
+    println!("{}", t!("schedule.saved", name = volume, frequency = frequency, keep = keep));
+    Ok(())
+}
+
+fn remove_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    let mut schedule = Schedule::load(backend)?;
+    let policies: Vec<Policy> = schedule.policies.iter().filter(|p| p.volume == volume).cloned().collect();
+    if policies.is_empty() {
+        println!("{}", t!("schedule.no_policies"));
+        return Ok(());
+    }
+
+    let items: Vec<String> = policies
+        .iter()
+        .map(|p| t!("schedule.policy_item", frequency = p.frequency, keep = p.keep))
+        .collect();
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("schedule.remove_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    schedule.remove(volume, policies[index].frequency);
+    save_and_install(backend, &schedule)?;
+    println!("{}", t!("schedule.removed", name = volume, frequency = policies[index].frequency));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn at(text: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(text).unwrap()
+    }
+
+    fn cluster(policies: &str) -> FakeBackend {
+        FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started)
+            .with_filesystem("/gluster/a", "/dev/mapper/vg-brick_a", Some("Vwi-aotz--"))
+            .with_file(SCHEDULE_PATH, policies)
+    }
+
+    #[test]
+    fn parses_policies_and_rejects_bad_ones() {
+        let schedule = Schedule::parse("[[policy]]\nvolume = \"datos\"\nfrequency = \"daily\"\nkeep = 7\n").unwrap();
+        assert_eq!(schedule.policies[0].frequency, Frequency::Daily);
+        assert!(Schedule::parse("[[policy]]\nvolume = \"datos\"\nfrequency = \"monthly\"\nkeep = 7\n").is_err());
+        assert!(Schedule::parse("[[policy]]\nvolume = \"datos\"\nfrequency = \"daily\"\nkeep = 0\n").is_err());
+
+        let mut schedule = Schedule::default();
+        schedule.set("datos", Frequency::Hourly, 24).unwrap();
+        schedule.set("datos", Frequency::Hourly, 12).unwrap();
+        assert_eq!(schedule.policies.len(), 1);
+        assert_eq!(Schedule::parse(&toml::to_string(&schedule).unwrap()).unwrap(), schedule);
+    }
+
+    #[test]
+    fn due_and_missed_runs() {
+        let policy = Policy { volume: "datos".into(), frequency: Frequency::Daily, keep: 7 };
+        let state = RunState {
+            volume: "datos".into(),
+            frequency: Frequency::Daily,
+            since: "2026-10-01T00:00:00+00:00".into(),
+            last_success: Some("2026-10-10T03:17:00+00:00".into()),
+            last_error: None,
+        };
+        assert!(!is_due(&policy, Some(&state), at("2026-10-10T20:17:00+00:00")));
+        // Un cron que llega unos minutos antes de las 24 h sigue contando.
+        assert!(is_due(&policy, Some(&state), at("2026-10-11T03:12:00+00:00")));
+        assert_eq!(missed_runs(&policy, Some(&state), at("2026-10-11T03:17:00+00:00")), 0);
+        assert_eq!(missed_runs(&policy, Some(&state), at("2026-10-13T03:17:00+00:00")), 2);
+        assert!(is_due(&policy, None, at("2026-10-13T03:17:00+00:00")));
+    }
+
+    #[test]
+    fn run_creates_prunes_and_records_state() {
+        let fake = cluster("[[policy]]\nvolume = \"datos\"\nfrequency = \"hourly\"\nkeep = 2\n");
+
+        for hour in 0..4 {
+            let report = run(&fake, at(&format!("2026-10-18T0{}:17:00+02:00", hour))).unwrap();
+            assert_eq!(report.created.len(), 1);
+        }
+        // La misma hora no crea otro.
+        assert!(run(&fake, at("2026-10-18T03:30:00+02:00")).unwrap().created.is_empty());
+
+        let names: Vec<String> = crate::snapshot::list(&fake, Some("datos")).unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["datos-hourly-20261018-0217", "datos-hourly-20261018-0317"]);
+
+        let report = run(&fake, at("2026-10-18T07:17:00+02:00")).unwrap();
+        assert_eq!(report.missed, vec![(t!("schedule.policy_label", name = "datos", frequency = Frequency::Hourly), 3)]);
+        let state = load_state(&fake);
+        assert_eq!(state[0].last_success.as_deref(), Some("2026-10-18T07:17:00+02:00"));
+    }
+
+    #[test]
+    fn failures_are_recorded_and_reported() {
+        let fake = FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started)
+            .with_file(SCHEDULE_PATH, "[[policy]]\nvolume = \"datos\"\nfrequency = \"daily\"\nkeep = 7\n");
+
+        assert!(run(&fake, at("2026-10-18T03:17:00+02:00")).is_err());
+        let state = load_state(&fake);
+        assert!(state[0].last_error.is_some() && state[0].last_success.is_none());
+        assert_eq!(missed_runs(&Schedule::load(&fake).unwrap().policies[0], Some(&state[0]), at("2026-10-20T03:17:00+02:00")), 1);
+    }
+
+    #[test]
+    fn cron_entry_runs_the_scheduler_hourly() {
+        let fake = FakeBackend::new();
+        install_cron(&fake, "/usr/local/bin/cluster_almacenamiento").unwrap();
+        let entry = fake.read_file(CRON_PATH).unwrap();
+        assert!(entry.contains("17 * * * * root /usr/local/bin/cluster_almacenamiento snapshot run-schedule"));
+    }
+
+    #[test]
+    fn removing_the_last_policy_uninstalls_cron() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        let mut schedule = Schedule::load(&fake).unwrap();
+        schedule.set("datos", Frequency::Daily, 7).unwrap();
+        schedule.set("datos", Frequency::Hourly, 24).unwrap();
+        save_and_install(&fake, &schedule).unwrap();
+        assert!(fake.exists(CRON_PATH));
+
+        schedule.remove("datos", Frequency::Daily);
+        save_and_install(&fake, &schedule).unwrap();
+        assert!(fake.exists(CRON_PATH));
+
+        schedule.remove("datos", Frequency::Hourly);
+        save_and_install(&fake, &schedule).unwrap();
+        assert!(!fake.exists(CRON_PATH));
+        assert!(Schedule::load(&fake).unwrap().policies.is_empty());
+    }
+
+    #[test]
+    fn stale_cron_entries_are_rewritten_with_the_installed_path() {
+        let fake = FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started)
+            .with_file(CRON_PATH, &cron_entry("/home/operador/cluster/target/debug/cluster_almacenamiento"));
+        let mut schedule = Schedule::load(&fake).unwrap();
+        schedule.set("datos", Frequency::Daily, 7).unwrap();
+
+        save_and_install(&fake, &schedule).unwrap();
+        assert_eq!(fake.read_file(CRON_PATH).unwrap(), cron_entry(crate::config::INSTALL_PATH));
+
+        let fake = fake.with_file(crate::config::CONFIG_PATH, "executable = \"/opt/cluster/cluster_almacenamiento\"\n");
+        save_and_install(&fake, &schedule).unwrap();
+        assert!(fake.read_file(CRON_PATH).unwrap().contains("root /opt/cluster/cluster_almacenamiento snapshot run-schedule"));
+    }
+}
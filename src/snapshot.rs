@@ -0,0 +1,613 @@
+//! Snapshots de volúmenes (`gluster snapshot ...`): crear, consultar, activar, restaurar,
+//! clonar y borrar, con una comprobación previa de que los bricks están en LVM thin.
+
+use std::fmt;
+
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::i18n::is_exit;
+use crate::t;
+use crate::volinfo;
+use crate::xml;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub name: String,
+    pub uuid: String,
+    pub description: Option<String>,
+    pub created: String,
+    /// Volumen del que se tomó.
+    pub volume: String,
+    /// Activado: sus bricks están en marcha y se puede montar o clonar.
+    pub active: bool,
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = if self.active { t!("snapshot.active") } else { t!("snapshot.inactive") };
+        write!(f, "{}  [{}]  {}  {}", self.name, self.volume, self.created, state)?;
+        if let Some(description) = &self.description {
+            write!(f, "  \"{}\"", description)?;
+        }
+        Ok(())
+    }
+}
+
+/// Interpreta `gluster snapshot info [...] --xml`. `volume` es el volumen consultado con
+/// `info volume NOMBRE`, cuya respuesta no repite el volumen de origen en cada snapshot.
+pub fn parse_info(output: &str, volume: Option<&str>) -> Result<Vec<Snapshot>> {
+    let doc = xml::parse_cli_output(output)?;
+    let Some(snapshots) = xml::descendant(doc.root_element(), "snapshots") else {
+        return Ok(vec![]);
+    };
+
+    let mut result = vec![];
+    for node in xml::children(snapshots, "snapshot") {
+        let Some(name) = xml::child_text(node, "name") else {
+            continue;
+        };
+        let snap_volume = xml::child(node, "snapVolume");
+        let origin = snap_volume
+            .and_then(|v| xml::child(v, "originVolume"))
+            .and_then(|o| xml::child_text(o, "name"))
+            .or(volume)
+            .unwrap_or_default();
+        result.push(Snapshot {
+            name: name.to_string(),
+            uuid: xml::child_text(node, "uuid").unwrap_or_default().to_string(),
+            description: xml::child_text(node, "description").map(str::to_string),
+            created: xml::child_text(node, "createTime").unwrap_or_default().to_string(),
+            volume: origin.to_string(),
+            active: snap_volume.and_then(|v| xml::child_text(v, "status")) == Some("Started"),
+        });
+    }
+    Ok(result)
+}
+
+/// Snapshots de un volumen, o de todos con `None`.
+pub fn list(backend: &dyn GlusterBackend, volume: Option<&str>) -> Result<Vec<Snapshot>> {
+    let mut args = vec!["snapshot", "info"];
+    if let Some(volume) = volume {
+        args.extend(["volume", volume]);
+    }
+    let output = backend.gluster_xml(&args).map_err(|e| e.context(&t!("snapshot.list_failed")))?;
+    parse_info(&output, volume)
+}
+
+pub fn find(backend: &dyn GlusterBackend, name: &str) -> Result<Snapshot> {
+    let output = backend
+        .gluster_xml(&["snapshot", "info", name])
+        .map_err(|e| e.context(&t!("snapshot.list_failed")))?;
+    parse_info(&output, None)?
+        .into_iter()
+        .find(|s| s.name == name)
+        .ok_or_else(|| Error::Message(t!("snapshot.not_found", name = name)))
+}
+
+/// Estado de los bricks de un snapshot, tal como lo muestra gluster.
+pub fn status(backend: &dyn GlusterBackend, name: Option<&str>) -> Result<String> {
+    let mut args = vec!["snapshot", "status"];
+    args.extend(name);
+    backend.gluster(&args).map_err(|e| e.context(&t!("snapshot.status_failed")))
+}
+
+/// Dónde está cada brick, visto desde este nodo.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BrickStorage {
+    /// Volumen lógico thin: admite snapshots.
+    Thin { device: String },
+    /// Volumen lógico normal (thick): gluster no puede hacer snapshot.
+    ThickLvm { device: String },
+    /// Dispositivo que no es LVM (partición, disco o el sistema de ficheros raíz).
+    NotLvm { device: String },
+    /// Brick de otro nodo: solo se puede comprobar desde ese nodo.
+    Remote,
+    Unknown { reason: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrickCheck {
+    pub brick: String,
+    pub storage: BrickStorage,
+}
+
+/// Resultado de la comprobación previa de un volumen.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Preflight {
+    pub bricks: Vec<BrickCheck>,
+    /// Problemas que harían fallar el snapshot.
+    pub errors: Vec<String>,
+    /// Lo que no se pudo comprobar desde aquí.
+    pub warnings: Vec<String>,
+}
+
+impl Preflight {
+    pub fn ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn print(&self) {
+        for check in &self.bricks {
+            let line = match &check.storage {
+                BrickStorage::Thin { device } => t!("snapshot.brick_thin", brick = check.brick, device = device),
+                BrickStorage::ThickLvm { device } => t!("snapshot.brick_thick", brick = check.brick, device = device),
+                BrickStorage::NotLvm { device } => t!("snapshot.brick_not_lvm", brick = check.brick, device = device),
+                BrickStorage::Remote => t!("snapshot.brick_remote", brick = check.brick),
+                BrickStorage::Unknown { reason } => t!("snapshot.brick_unknown", brick = check.brick, reason = reason),
+            };
+            println!("   {}", line);
+        }
+        for warning in &self.warnings {
+            println!("⚠️ {}", warning);
+        }
+        for error in &self.errors {
+            println!("❌ {}", error);
+        }
+        if self.ok() {
+            println!("{}", t!("snapshot.preflight_ok"));
+        } else {
+            println!("{}", t!("snapshot.thin_explanation"));
+        }
+    }
+}
+
+/// Averigua sobre qué dispositivo está `path` y si es un volumen lógico thin.
+fn brick_storage(backend: &dyn GlusterBackend, path: &str) -> BrickStorage {
+    let device = match backend.command("findmnt", &["-n", "-o", "SOURCE", "--target", path]) {
+        Ok(output) => output.trim().to_string(),
+        Err(e) => return BrickStorage::Unknown { reason: e.to_string() },
+    };
+    // `lvs` necesita root; solo su "no es un volumen lógico" descarta LVM, el resto se desconoce.
+    match backend.sudo_query("lvs", &["--noheadings", "-o", "lv_attr", &device]) {
+        Ok(attr) if attr.trim().starts_with('V') => BrickStorage::Thin { device },
+        Ok(_) => BrickStorage::ThickLvm { device },
+        Err(Error::CommandFailed { stderr, .. }) if is_not_logical_volume(&stderr) => BrickStorage::NotLvm { device },
+        Err(e) => BrickStorage::Unknown { reason: e.to_string() },
+    }
+}
+
+/// Errores de `lvs` que significan que el dispositivo no es un volumen lógico.
+fn is_not_logical_volume(stderr: &str) -> bool {
+    stderr.contains("Failed to find logical volume") || stderr.contains("Cannot process volume group")
+}
+
+/// Comprueba lo que gluster exige para un snapshot: volumen iniciado y bricks en LVM thin.
+pub fn preflight(backend: &dyn GlusterBackend, volume: &str) -> Result<Preflight> {
+    let info = volinfo::fetch_one(backend, volume)?;
+    let local = backend.local_hostname();
+    let mut result = Preflight::default();
+
+    if info.status != "Started" {
+        result.errors.push(t!("snapshot.not_started", name = volume));
+    }
+
+    for brick in &info.bricks {
+        let storage = if brick.host == local || brick.host == "localhost" {
+            brick_storage(backend, &brick.path)
+        } else {
+            BrickStorage::Remote
+        };
+        match &storage {
+            BrickStorage::ThickLvm { device } => {
+                result.errors.push(t!("snapshot.thick_error", brick = brick.name, device = device))
+            }
+            BrickStorage::NotLvm { device } => {
+                result.errors.push(t!("snapshot.not_lvm_error", brick = brick.name, device = device))
+            }
+            _ => {}
+        }
+        result.bricks.push(BrickCheck { brick: brick.name.clone(), storage });
+    }
+
+    let remote = result.bricks.iter().filter(|b| b.storage == BrickStorage::Remote).count();
+    if remote > 0 {
+        result.warnings.push(t!("snapshot.remote_warning", count = remote));
+    }
+    Ok(result)
+}
+
+/// Crea un snapshot y devuelve su nombre definitivo (gluster añade la fecha salvo con `no_timestamp`).
+pub fn create(
+    backend: &dyn GlusterBackend,
+    volume: &str,
+    name: &str,
+    description: Option<&str>,
+    no_timestamp: bool,
+) -> Result<String> {
+    let check = preflight(backend, volume)?;
+    if !check.ok() {
+        check.print();
+        return Err(Error::Message(t!("snapshot.preflight_failed", name = volume)));
+    }
+
+    let mut args = vec!["snapshot", "create", name, volume];
+    if no_timestamp {
+        args.push("no-timestamp");
+    }
+    if let Some(description) = description.filter(|d| !d.trim().is_empty()) {
+        args.extend(["description", description]);
+    }
+    let output = backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("snapshot.create_failed", name = name)))?;
+
+    // "snapshot create: success: Snap datos_GMT-2026.10.18-09.15.02 created successfully"
+    let created = output
+        .split_whitespace()
+        .skip_while(|word| *word != "Snap")
+        .nth(1)
+        .unwrap_or(name)
+        .to_string();
+    println!("{}", t!("snapshot.created", name = created));
+    Ok(created)
+}
+
+pub fn activate(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["snapshot", "activate", name])
+        .map_err(|e| e.context(&t!("snapshot.activate_failed", name = name)))?;
+    println!("{}", t!("snapshot.activated", name = name));
+    Ok(())
+}
+
+pub fn deactivate(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["snapshot", "deactivate", name])
+        .map_err(|e| e.context(&t!("snapshot.deactivate_failed", name = name)))?;
+    println!("{}", t!("snapshot.deactivated", name = name));
+    Ok(())
+}
+
+/// Restaura el volumen de origen al estado del snapshot. Gluster exige el volumen detenido:
+/// si estaba iniciado se detiene y se vuelve a iniciar al terminar. El snapshot desaparece.
+pub fn restore(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    let snapshot = find(backend, name)?;
+    let was_started = volinfo::fetch_one(backend, &snapshot.volume)?.status == "Started";
+
+    if was_started {
+        println!("{}", t!("snapshot.stopping", name = snapshot.volume));
+        crate::volume::stop(backend, &snapshot.volume)?;
+    }
+
+    let restored = backend
+        .sudo_gluster(&["snapshot", "restore", name])
+        .map_err(|e| e.context(&t!("snapshot.restore_failed", name = name)));
+
+    // Aunque falle la restauración, el volumen no debe quedarse detenido por nuestra culpa.
+    let restarted = if was_started { crate::volume::start(backend, &snapshot.volume) } else { Ok(()) };
+    if let Err(e) = restored {
+        // El fallo que importa es el de la restauración; el del arranque solo se añade.
+        return Err(match restarted {
+            Err(start) => e.context(&t!("snapshot.left_stopped", name = snapshot.volume, error = start)),
+            Ok(()) => e,
+        });
+    }
+    restarted?;
+    println!("{}", t!("snapshot.restored", name = name, volume = snapshot.volume));
+    Ok(())
+}
+
+/// Crea un volumen nuevo a partir de un snapshot (que debe estar activado).
+pub fn clone(backend: &dyn GlusterBackend, name: &str, clone_name: &str, start: bool) -> Result<()> {
+    if !find(backend, name)?.active {
+        return Err(Error::Message(t!("snapshot.clone_needs_active", name = name)));
+    }
+    backend
+        .sudo_gluster(&["snapshot", "clone", clone_name, name])
+        .map_err(|e| e.context(&t!("snapshot.clone_failed", name = name)))?;
+    println!("{}", t!("snapshot.cloned", name = name, clone = clone_name));
+    if start {
+        crate::volume::start(backend, clone_name)?;
+    }
+    Ok(())
+}
+
+pub fn delete(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["snapshot", "delete", name])
+        .map_err(|e| e.context(&t!("snapshot.delete_failed", name = name)))?;
+    println!("{}", t!("snapshot.deleted", name = name));
+    Ok(())
+}
+
+pub fn print_list(snapshots: &[Snapshot]) {
+    if snapshots.is_empty() {
+        println!("{}", t!("snapshot.none"));
+    }
+    for snapshot in snapshots {
+        println!("   📸 {}", snapshot);
+    }
+}
+
+/// Pantalla de snapshots.
+pub fn manage_snapshots(backend: &dyn GlusterBackend) -> Result<()> {
+    let theme = ColorfulTheme::default();
+
+    loop {
+        let items = [
+            t!("snapshot.menu_list"),
+            t!("snapshot.menu_create"),
+            t!("snapshot.menu_toggle"),
+            t!("snapshot.menu_restore"),
+            t!("snapshot.menu_clone"),
+            t!("snapshot.menu_delete"),
+            t!("snapshot.menu_status"),
+            t!("snapshot.menu_preflight"),
+            t!("volume.menu_back"),
+        ];
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("snapshot.menu_title"))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => list(backend, None).map(|snapshots| print_list(&snapshots)),
+            1 => create_interactive(backend, &theme),
+            2 => choose_snapshot(backend, &theme).and_then(|snapshot| match snapshot {
+                Some(s) if s.active => deactivate(backend, &s.name),
+                Some(s) => activate(backend, &s.name),
+                None => Ok(()),
+            }),
+            3 => restore_interactive(backend, &theme),
+            4 => clone_interactive(backend, &theme),
+            5 => delete_interactive(backend, &theme),
+            6 => choose_snapshot(backend, &theme).and_then(|snapshot| match snapshot {
+                Some(s) => status(backend, Some(&s.name)).map(|text| println!("{}", text)),
+                None => Ok(()),
+            }),
+            7 => match crate::volume::choose_volume(backend, &theme, &t!("options.volume_prompt"))? {
+                Some(volume) => preflight(backend, &volume).map(|check| check.print()),
+                None => Ok(()),
+            },
+            _ => break,
+        };
+        crate::error::report(result);
+    }
+    Ok(())
+}
+
+fn choose_snapshot(backend: &dyn GlusterBackend, theme: &ColorfulTheme) -> Result<Option<Snapshot>> {
+    let mut snapshots = list(backend, None)?;
+    if snapshots.is_empty() {
+        println!("{}", t!("snapshot.none"));
+        return Ok(None);
+    }
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("snapshot.pick_prompt"))
+        .items(&snapshots)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    Ok(Some(snapshots.swap_remove(index)))
+}
+
+fn create_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme) -> Result<()> {
+    let Some(volume) = crate::volume::choose_volume(backend, theme, &t!("options.volume_prompt"))? else {
+        return Ok(());
+    };
+
+    let check = preflight(backend, &volume)?;
+    check.print();
+    if !check.ok() {
+        return Ok(());
+    }
+
+    let name: String = Input::with_theme(theme)
+        .with_prompt(t!("snapshot.name_prompt", exit = t!("common.exit_word")))
+        .default(format!("{}-snap", volume))
+        .interact_text()?;
+    if is_exit(&name) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    let description: String = Input::with_theme(theme)
+        .with_prompt(t!("snapshot.description_prompt"))
+        .allow_empty(true)
+        .interact_text()?;
+    let timestamp = Confirm::with_theme(theme)
+        .with_prompt(t!("snapshot.timestamp_confirm"))
+        .default(true)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    create(backend, &volume, name.trim(), Some(description.trim()), !timestamp).map(|_| ())
+}
+
+fn restore_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme) -> Result<()> {
+    let Some(snapshot) = choose_snapshot(backend, theme)? else {
+        return Ok(());
+    };
+    if !Confirm::with_theme(theme)
+        .with_prompt(t!("snapshot.restore_confirm", name = snapshot.name, volume = snapshot.volume))
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    restore(backend, &snapshot.name)
+}
+
+fn clone_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme) -> Result<()> {
+    let Some(snapshot) = choose_snapshot(backend, theme)? else {
+        return Ok(());
+    };
+    if !snapshot.active {
+        if !Confirm::with_theme(theme)
+            .with_prompt(t!("snapshot.activate_for_clone", name = snapshot.name))
+            .default(true)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?
+        {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
+        }
+        activate(backend, &snapshot.name)?;
+    }
+
+    let clone_name: String = Input::with_theme(theme)
+        .with_prompt(t!("snapshot.clone_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
+    if is_exit(&clone_name) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    let start = Confirm::with_theme(theme)
+        .with_prompt(t!("snapshot.clone_start_confirm"))
+        .default(true)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    clone(backend, &snapshot.name, clone_name.trim(), start)
+}
+
+fn delete_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme) -> Result<()> {
+    let Some(snapshot) = choose_snapshot(backend, theme)? else {
+        return Ok(());
+    };
+    if !Confirm::with_theme(theme)
+        .with_prompt(t!("snapshot.delete_confirm", name = snapshot.name))
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+    delete(backend, &snapshot.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    /// Volumen con un brick local en LVM thin y otro en un nodo remoto.
+    fn cluster() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started)
+            .with_filesystem("/gluster/a", "/dev/mapper/vg_gluster-brick_a", Some("Vwi-aotz--"))
+    }
+
+    #[test]
+    fn preflight_explains_why_snapshots_would_fail() {
+        let fake = cluster();
+        let check = preflight(&fake, "datos").unwrap();
+        assert!(check.ok(), "{:?}", check.errors);
+        assert_eq!(check.bricks[1].storage, BrickStorage::Remote);
+        assert_eq!(check.warnings.len(), 1);
+
+        // Brick creado con mkdir en la partición raíz, como los de `brick create`.
+        let fake = FakeBackend::new().with_volume("raiz", &["vm1:/gluster/b"], VolumeState::Started);
+        let check = preflight(&fake, "raiz").unwrap();
+        assert_eq!(check.bricks[0].storage, BrickStorage::NotLvm { device: "/dev/sda1".into() });
+        assert!(!check.ok());
+        assert!(create(&fake, "raiz", "antes", None, true).is_err());
+        assert!(fake.commands().is_empty());
+
+        let fake = FakeBackend::new()
+            .with_volume("grueso", &["vm1:/gluster/c"], VolumeState::Stopped)
+            .with_filesystem("/gluster", "/dev/mapper/vg-gluster", Some("-wi-ao----"));
+        let check = preflight(&fake, "grueso").unwrap();
+        assert_eq!(check.errors.len(), 2);
+    }
+
+    #[test]
+    fn lvs_failures_other_than_not_lvm_do_not_block_snapshots() {
+        let fake = cluster().with_sudo_denied("lvs");
+        let check = preflight(&fake, "datos").unwrap();
+        assert!(matches!(&check.bricks[0].storage, BrickStorage::Unknown { reason } if reason.contains("password")));
+        assert!(check.ok(), "{:?}", check.errors);
+
+        create(&fake, "datos", "diario", None, true).unwrap();
+        assert_eq!(fake.commands().last().unwrap(), "gluster --mode=script snapshot create diario datos no-timestamp");
+    }
+
+    #[test]
+    fn lvs_runs_in_dry_run_and_is_not_audited() {
+        let fake = FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started)
+            .with_filesystem("/gluster/a", "/dev/mapper/vg-grueso", Some("-wi-ao----"));
+        let audited = crate::audit::AuditBackend::new(&fake, "/var/log/auditoria.jsonl", "root");
+        let dry = crate::dryrun::DryRunBackend::new(&audited, true);
+
+        // La simulación no oculta un brick en LVM grueso.
+        let check = preflight(&dry, "datos").unwrap();
+        assert_eq!(check.bricks[0].storage, BrickStorage::ThickLvm { device: "/dev/mapper/vg-grueso".into() });
+        assert!(!check.ok());
+        assert!(fake.commands().is_empty());
+        assert!(!fake.exists("/var/log/auditoria.jsonl"));
+    }
+
+    #[test]
+    fn create_list_and_delete() {
+        let fake = cluster();
+
+        let name = create(&fake, "datos", "antes-migrar", Some("antes de migrar"), false).unwrap();
+        assert!(name.starts_with("antes-migrar_GMT-"));
+        create(&fake, "datos", "diario", None, true).unwrap();
+
+        let snapshots = list(&fake, Some("datos")).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].description.as_deref(), Some("antes de migrar"));
+        assert!(snapshots.iter().all(|s| s.volume == "datos" && !s.active));
+
+        activate(&fake, "diario").unwrap();
+        assert!(find(&fake, "diario").unwrap().active);
+        delete(&fake, &name).unwrap();
+        assert_eq!(list(&fake, None).unwrap().len(), 1);
+        assert_eq!(fake.commands()[1], "gluster --mode=script snapshot create diario datos no-timestamp");
+    }
+
+    #[test]
+    fn restore_stops_and_restarts_the_volume() {
+        let fake = cluster();
+        create(&fake, "datos", "diario", None, true).unwrap();
+
+        restore(&fake, "diario").unwrap();
+        assert_eq!(fake.volume("datos").unwrap().state, VolumeState::Started);
+        assert!(list(&fake, None).unwrap().is_empty());
+        assert_eq!(
+            fake.commands()[1..],
+            [
+                "gluster --mode=script volume stop datos force",
+                "gluster --mode=script snapshot restore diario",
+                "gluster --mode=script volume start datos",
+            ]
+        );
+    }
+
+    #[test]
+    fn restore_failure_is_kept_when_the_restart_also_fails() {
+        let fake = cluster()
+            .with_command_failure("gluster --mode=script snapshot restore", "snapshot restore: failed: Commit failed\n")
+            .with_command_failure("gluster --mode=script volume start", "volume start: datos: failed: Commit failed\n");
+        create(&fake, "datos", "diario", None, true).unwrap();
+
+        match restore(&fake, "diario") {
+            Err(Error::CommandFailed { command, stderr }) => {
+                assert!(command.contains("snapshot restore"));
+                assert!(stderr.contains("snapshot restore: failed") && stderr.contains("volume start: datos: failed"));
+            }
+            other => panic!("{:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn clone_needs_an_active_snapshot() {
+        let fake = cluster();
+        create(&fake, "datos", "diario", None, true).unwrap();
+
+        assert!(clone(&fake, "diario", "pruebas", true).is_err());
+        activate(&fake, "diario").unwrap();
+        clone(&fake, "diario", "pruebas", true).unwrap();
+        assert_eq!(fake.volume("pruebas").unwrap().state, VolumeState::Started);
+    }
+}
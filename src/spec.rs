@@ -0,0 +1,622 @@
+//! Especificación declarativa del clúster (TOML) y su plan/aplicación contra el clúster real.
+//!
+//! El plan solo contiene operaciones aditivas (agregar, crear, iniciar, montar): lo que sobra
+//! en el clúster se informa como advertencia y nunca se elimina automáticamente.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::layout::VolumeLayout;
+use crate::t;
+use crate::volume::is_valid_brick;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterSpec {
+    /// Nodos del clúster; en el TOML debe ir antes de las tablas `[[...]]`.
+    #[serde(default)]
+    pub peers: Vec<String>,
+    #[serde(default)]
+    pub hosts: Vec<HostSpec>,
+    #[serde(default)]
+    pub bricks: Vec<BrickSpec>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeSpec>,
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+}
+
+/// Entrada de /etc/hosts.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostSpec {
+    pub ip: String,
+    pub name: String,
+}
+
+/// Brick `/gluster/<name>` en el nodo `host`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BrickSpec {
+    pub host: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VolumeSpec {
+    pub name: String,
+    /// `distribute`, `replicate` o `disperse`; si falta se deduce de los contadores.
+    #[serde(rename = "type", default)]
+    pub vol_type: Option<String>,
+    #[serde(default)]
+    pub replica: Option<u32>,
+    #[serde(default)]
+    pub arbiter: Option<u32>,
+    #[serde(default)]
+    pub disperse: Option<u32>,
+    #[serde(default)]
+    pub redundancy: Option<u32>,
+    pub bricks: Vec<String>,
+    #[serde(default = "default_start")]
+    pub start: bool,
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+    /// Pasa `force` a gluster (bricks en la partición raíz o conjuntos en un mismo nodo).
+    #[serde(default)]
+    pub force: bool,
+}
+
+fn default_start() -> bool {
+    true
+}
+
+impl VolumeSpec {
+    pub fn layout(&self) -> Result<VolumeLayout> {
+        let inferred = match self.vol_type.as_deref() {
+            Some("distribute") => "distribute",
+            Some("replicate") => "replicate",
+            Some("disperse") => "disperse",
+            Some(other) => return Err(Error::Message(t!("spec.unknown_type", name = self.name, kind = other))),
+            None if self.disperse.is_some() => "disperse",
+            None if self.replica.is_some() => "replicate",
+            None => "distribute",
+        };
+
+        let layout = match inferred {
+            "replicate" => VolumeLayout::Replicate {
+                replica: self
+                    .replica
+                    .ok_or_else(|| t!("spec.missing_field", name = self.name, field = "replica"))?,
+                arbiter: self.arbiter.unwrap_or(0),
+            },
+            "disperse" => VolumeLayout::Disperse {
+                disperse: self
+                    .disperse
+                    .ok_or_else(|| t!("spec.missing_field", name = self.name, field = "disperse"))?,
+                redundancy: self.redundancy.unwrap_or(1),
+            },
+            _ => VolumeLayout::Distribute,
+        };
+
+        let mixed = match layout {
+            VolumeLayout::Distribute => self.replica.is_some() || self.disperse.is_some(),
+            VolumeLayout::Replicate { .. } => self.disperse.is_some() || self.redundancy.is_some(),
+            VolumeLayout::Disperse { .. } => self.replica.is_some() || self.arbiter.is_some(),
+        };
+        if mixed {
+            return Err(Error::Message(t!("spec.mixed_counts", name = self.name, kind = inferred)));
+        }
+
+        Ok(layout)
+    }
+}
+
+/// Montaje de `server:/volume` en `/media/<dir>`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MountSpec {
+    pub volume: String,
+    pub server: String,
+    pub dir: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+impl ClusterSpec {
+    pub fn parse(text: &str) -> Result<Self> {
+        let spec: ClusterSpec =
+            toml::from_str(text).map_err(|e| Error::Parse(t!("spec.invalid", error = e)))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn load(backend: &dyn GlusterBackend, path: &str) -> Result<Self> {
+        let text = backend
+            .read_file(path)
+            .map_err(|e| t!("spec.read_failed", path = path, error = e))?;
+        Self::parse(&text)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for volume in &self.volumes {
+            if let Some(invalid) = volume.bricks.iter().find(|b| !is_valid_brick(b)) {
+                return Err(Error::Message(t!("spec.invalid_brick", name = volume.name, brick = invalid)));
+            }
+            volume
+                .layout()?
+                .check_brick_count(volume.bricks.len())
+                .map_err(|e| e.context(&t!("spec.volume_prefix", name = volume.name)))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    AddHost { ip: String, name: String },
+    UpdateHost { name: String, ip: String },
+    ProbePeer(String),
+    CreateBrick(String),
+    CreateVolume { name: String, layout: VolumeLayout, bricks: Vec<String>, force: bool },
+    AddBricks { volume: String, bricks: Vec<String>, force: bool },
+    SetOption { volume: String, key: String, value: String },
+    StartVolume(String),
+    Mount { server: String, volume: String, dir: String },
+    SetOwner { mount_point: String, owner: String },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::AddHost { ip, name } => write!(f, "{}", t!("spec.action.add_host", ip = ip, name = name)),
+            Action::UpdateHost { name, ip } => write!(f, "{}", t!("spec.action.update_host", name = name, ip = ip)),
+            Action::ProbePeer(host) => write!(f, "peer probe {}", host),
+            Action::CreateBrick(name) => write!(f, "{}", t!("spec.action.create_brick", name = name)),
+            Action::CreateVolume { name, layout, bricks, force } => {
+                write!(
+                    f,
+                    "{}",
+                    t!("spec.action.create_volume", name = name, layout = layout, bricks = bricks.join(" "))
+                )?;
+                if *force {
+                    write!(f, " (force)")?;
+                }
+                Ok(())
+            }
+            Action::AddBricks { volume, bricks, force } => {
+                write!(f, "{}", t!("spec.action.add_bricks", name = volume, bricks = bricks.join(" ")))?;
+                if *force {
+                    write!(f, " (force)")?;
+                }
+                Ok(())
+            }
+            Action::SetOption { volume, key, value } => {
+                write!(f, "{}", t!("spec.action.set_option", name = volume, key = key, value = value))
+            }
+            Action::StartVolume(name) => write!(f, "{}", t!("spec.action.start_volume", name = name)),
+            Action::Mount { server, volume, dir } => {
+                write!(f, "{}", t!("spec.action.mount", server = server, volume = volume, dir = dir))
+            }
+            Action::SetOwner { mount_point, owner } => {
+                write!(f, "{}", t!("spec.action.set_owner", path = mount_point, owner = owner))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub actions: Vec<Action>,
+    pub warnings: Vec<String>,
+}
+
+impl Plan {
+    pub fn print(&self) {
+        if self.actions.is_empty() {
+            println!("{}", t!("spec.up_to_date"));
+        } else {
+            println!("{}", t!("spec.actions_needed", count = self.actions.len()));
+            for (i, action) in self.actions.iter().enumerate() {
+                println!("   {}. {}", i + 1, action);
+            }
+        }
+        for warning in &self.warnings {
+            println!("⚠️ {}", warning);
+        }
+    }
+}
+
+/// Compara la especificación con el clúster y devuelve las operaciones en orden seguro:
+/// hosts → peers → bricks → volúmenes (crear, ampliar, opciones, iniciar) → montajes.
+pub fn plan(backend: &dyn GlusterBackend, spec: &ClusterSpec) -> Result<Plan> {
+    let mut plan = Plan::default();
+
+    plan_hosts(backend, spec, &mut plan)?;
+    plan_peers(backend, spec, &mut plan)?;
+    plan_bricks(backend, spec, &mut plan);
+    plan_volumes(backend, spec, &mut plan)?;
+    plan_mounts(backend, spec, &mut plan)?;
+
+    Ok(plan)
+}
+
+fn plan_hosts(backend: &dyn GlusterBackend, spec: &ClusterSpec, plan: &mut Plan) -> Result<()> {
+    if spec.hosts.is_empty() {
+        return Ok(());
+    }
+
+    let entries = crate::hosts::read_entries(backend)?;
+    for host in &spec.hosts {
+        let current: Vec<&str> = entries
+            .iter()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .filter(|line| line.split_whitespace().skip(1).any(|n| n == host.name))
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+
+        if current.is_empty() {
+            plan.actions.push(Action::AddHost {
+                ip: host.ip.clone(),
+                name: host.name.clone(),
+            });
+        } else if !current.contains(&host.ip.as_str()) {
+            plan.actions.push(Action::UpdateHost {
+                name: host.name.clone(),
+                ip: host.ip.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn plan_peers(backend: &dyn GlusterBackend, spec: &ClusterSpec, plan: &mut Plan) -> Result<()> {
+    if spec.peers.is_empty() {
+        return Ok(());
+    }
+
+    let local = backend.local_hostname();
+    let peers = crate::pool::peers(backend)?;
+
+    for host in spec.peers.iter().filter(|h| **h != local) {
+        match peers.iter().find(|p| p.matches(host)) {
+            None => plan.actions.push(Action::ProbePeer(host.clone())),
+            Some(peer) if !peer.is_healthy() => plan.warnings.push(if peer.connected {
+                t!("spec.peer_not_accepted", host = host, state = peer.state)
+            } else {
+                t!("spec.peer_disconnected", host = host, state = peer.state)
+            }),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn plan_bricks(backend: &dyn GlusterBackend, spec: &ClusterSpec, plan: &mut Plan) {
+    let local = backend.local_hostname();
+
+    for brick in &spec.bricks {
+        if brick.host != local && brick.host != "localhost" {
+            plan.warnings.push(t!("spec.remote_brick", name = brick.name, host = brick.host));
+            continue;
+        }
+        if !backend.exists(&format!("/gluster/{}", brick.name)) {
+            plan.actions.push(Action::CreateBrick(brick.name.clone()));
+        }
+    }
+}
+
+fn plan_volumes(backend: &dyn GlusterBackend, spec: &ClusterSpec, plan: &mut Plan) -> Result<()> {
+    if spec.volumes.is_empty() {
+        return Ok(());
+    }
+
+    let existing = crate::volinfo::fetch(backend, None)?;
+    let mut starts = vec![];
+
+    for volume in &spec.volumes {
+        let layout = volume.layout()?;
+
+        let Some(info) = existing.iter().find(|v| v.name == volume.name) else {
+            // La especificación manda sobre el orden: solo se avisa si algún conjunto repite nodo.
+            if !volume.force {
+                for warning in crate::placement::check_spread(&layout, &volume.bricks) {
+                    plan.warnings.push(t!("spec.placement", name = volume.name, warning = warning));
+                }
+            }
+            plan.actions.push(Action::CreateVolume {
+                name: volume.name.clone(),
+                layout,
+                bricks: volume.bricks.clone(),
+                force: volume.force,
+            });
+            for (key, value) in &volume.options {
+                plan.actions.push(Action::SetOption {
+                    volume: volume.name.clone(),
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+            if volume.start {
+                starts.push(Action::StartVolume(volume.name.clone()));
+            }
+            continue;
+        };
+
+        let current_layout = VolumeLayout::from_info(info);
+        if current_layout != layout {
+            plan.warnings.push(t!(
+                "spec.layout_mismatch",
+                name = volume.name,
+                current = current_layout,
+                wanted = layout
+            ));
+        }
+
+        let current_bricks = info.brick_names();
+        let missing: Vec<String> = volume
+            .bricks
+            .iter()
+            .filter(|b| !current_bricks.contains(b))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            if missing.len().is_multiple_of(current_layout.set_size()) {
+                if !volume.force {
+                    for warning in crate::placement::check_spread(&current_layout, &missing) {
+                        plan.warnings.push(t!("spec.placement", name = volume.name, warning = warning));
+                    }
+                }
+                plan.actions.push(Action::AddBricks {
+                    volume: volume.name.clone(),
+                    bricks: missing,
+                    force: volume.force,
+                });
+            } else {
+                plan.warnings.push(t!(
+                    "spec.uneven_bricks",
+                    name = volume.name,
+                    size = current_layout.set_size(),
+                    bricks = missing.join(" ")
+                ));
+            }
+        }
+
+        for extra in current_bricks.iter().filter(|b| !volume.bricks.contains(b)) {
+            plan.warnings.push(t!("spec.extra_brick", brick = extra, name = volume.name));
+        }
+
+        for (key, value) in &volume.options {
+            if info.options.get(key) != Some(value) {
+                plan.actions.push(Action::SetOption {
+                    volume: volume.name.clone(),
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        if volume.start && info.status != "Started" {
+            starts.push(Action::StartVolume(volume.name.clone()));
+        } else if !volume.start && info.status == "Started" {
+            plan.warnings.push(t!("spec.unexpected_start", name = volume.name));
+        }
+    }
+
+    // Los volúmenes se inician al final, ya con todos sus bricks y opciones.
+    plan.actions.extend(starts);
+    Ok(())
+}
+
+fn plan_mounts(backend: &dyn GlusterBackend, spec: &ClusterSpec, plan: &mut Plan) -> Result<()> {
+    if spec.mounts.is_empty() {
+        return Ok(());
+    }
+
+    let mounted = crate::mount::media_mounts(backend)?;
+
+    for mount in &spec.mounts {
+        let mount_point = format!("/media/{}", mount.dir);
+        let source = format!("{}:/{}", mount.server, mount.volume);
+
+        match mounted.iter().find(|(_, target)| *target == mount_point) {
+            Some((current, _)) if *current != source => plan.warnings.push(t!(
+                "spec.mount_mismatch",
+                path = mount_point,
+                current = current,
+                wanted = source
+            )),
+            Some(_) => {}
+            None => {
+                plan.actions.push(Action::Mount {
+                    server: mount.server.clone(),
+                    volume: mount.volume.clone(),
+                    dir: mount.dir.clone(),
+                });
+                if let Some(owner) = &mount.owner {
+                    plan.actions.push(Action::SetOwner {
+                        mount_point,
+                        owner: owner.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ejecuta las operaciones del plan en orden; se detiene en el primer error.
+pub fn apply(backend: &dyn GlusterBackend, plan: &Plan) -> Result<()> {
+    // El catálogo de opciones se consulta una sola vez, y solo si el plan cambia alguna.
+    let mut docs = None;
+    for (i, action) in plan.actions.iter().enumerate() {
+        println!("\n▶️ [{}/{}] {}", i + 1, plan.actions.len(), action);
+
+        match action {
+            Action::AddHost { ip, name } => crate::hosts::add_entry(backend, ip, name),
+            Action::UpdateHost { name, ip } => crate::hosts::update_entry(backend, name, ip),
+            Action::ProbePeer(host) => crate::peers::probe(backend, host),
+            Action::CreateBrick(name) => crate::bricks::create(backend, name),
+            Action::CreateVolume { name, layout, bricks, force } => {
+                crate::volume::create(backend, name, layout, bricks, false, *force)
+            }
+            Action::AddBricks { volume, bricks, force } => crate::volume::add(backend, volume, bricks, *force),
+            Action::SetOption { volume, key, value } => {
+                let docs = docs.get_or_insert_with(|| crate::options::catalog(backend));
+                crate::options::set(backend, docs, volume, key, value)
+            }
+            Action::StartVolume(name) => crate::volume::start(backend, name),
+            Action::Mount { server, volume, dir } => crate::mount::mount(backend, server, volume, dir).map(|_| ()),
+            Action::SetOwner { mount_point, owner } => crate::mount::set_owner(backend, mount_point, owner),
+        }
+        .map_err(|e| e.context(&t!("spec.stopped_at", step = i + 1, action = action)))?;
+    }
+
+    println!("\n{}", t!("spec.applied"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    const SPEC: &str = r#"
+peers = ["vm1", "vm2", "vm3"]
+
+[[hosts]]
+ip = "192.168.1.10"
+name = "vm1"
+
+[[hosts]]
+ip = "192.168.1.12"
+name = "vm3"
+
+[[bricks]]
+host = "vm1"
+name = "datos"
+
+[[bricks]]
+host = "vm2"
+name = "datos"
+
+[[volumes]]
+name = "datos"
+replica = 3
+arbiter = 1
+bricks = ["vm1:/gluster/datos", "vm2:/gluster/datos", "vm3:/gluster/arb"]
+
+[volumes.options]
+"performance.cache-size" = "256MB"
+
+[[mounts]]
+volume = "datos"
+server = "vm1"
+dir = "datos"
+owner = "operador"
+"#;
+
+    fn cluster() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_file("/etc/hosts", "127.0.0.1 localhost\n192.168.1.10 vm1\n192.168.1.99 vm3\n")
+            .with_dir("/gluster")
+    }
+
+    #[test]
+    fn plan_lists_missing_operations_in_safe_order() {
+        let fake = cluster();
+        let spec = ClusterSpec::parse(SPEC).unwrap();
+
+        let plan = plan(&fake, &spec).unwrap();
+        let layout = VolumeLayout::Replicate { replica: 3, arbiter: 1 };
+        assert_eq!(
+            plan.actions,
+            vec![
+                Action::UpdateHost { name: "vm3".into(), ip: "192.168.1.12".into() },
+                Action::ProbePeer("vm3".into()),
+                Action::CreateBrick("datos".into()),
+                Action::CreateVolume {
+                    name: "datos".into(),
+                    layout,
+                    bricks: vec!["vm1:/gluster/datos".into(), "vm2:/gluster/datos".into(), "vm3:/gluster/arb".into()],
+                    force: false,
+                },
+                Action::SetOption {
+                    volume: "datos".into(),
+                    key: "performance.cache-size".into(),
+                    value: "256MB".into(),
+                },
+                Action::StartVolume("datos".into()),
+                Action::Mount { server: "vm1".into(), volume: "datos".into(), dir: "datos".into() },
+                Action::SetOwner { mount_point: "/media/datos".into(), owner: "operador".into() },
+            ]
+        );
+        assert_eq!(plan.warnings.len(), 1, "{:?}", plan.warnings);
+    }
+
+    #[test]
+    fn apply_converges_and_second_plan_is_empty() {
+        let fake = cluster();
+        let spec = ClusterSpec::parse(SPEC).unwrap();
+
+        apply(&fake, &plan(&fake, &spec).unwrap()).unwrap();
+
+        let volume = fake.volume("datos").unwrap();
+        assert_eq!(volume.state, VolumeState::Started);
+        assert_eq!((volume.replica, volume.arbiter), (3, 1));
+        assert!(plan(&fake, &spec).unwrap().actions.is_empty());
+    }
+
+    #[test]
+    fn apply_keeps_the_kind_of_the_failing_step() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        let plan = Plan { actions: vec![Action::StartVolume("datos".into())], warnings: vec![] };
+
+        match apply(&fake, &plan) {
+            Err(Error::CommandFailed { stderr, .. }) => {
+                assert!(stderr.starts_with(&t!("spec.stopped_at", step = 1, action = plan.actions[0])));
+                assert!(stderr.contains("already started"));
+            }
+            other => panic!("{:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn existing_volume_only_gets_additive_changes() {
+        let fake = cluster()
+            .with_peer("vm3", true)
+            .with_dir("/gluster/datos")
+            .with_volume("datos", &["vm1:/gluster/datos", "vm1:/gluster/viejo"], VolumeState::Stopped);
+        let spec = ClusterSpec::parse(
+            r#"
+[[volumes]]
+name = "datos"
+bricks = ["vm1:/gluster/datos", "vm2:/gluster/datos"]
+"#,
+        )
+        .unwrap();
+
+        let plan = plan(&fake, &spec).unwrap();
+        assert_eq!(
+            plan.actions,
+            vec![
+                Action::AddBricks { volume: "datos".into(), bricks: vec!["vm2:/gluster/datos".into()], force: false },
+                Action::StartVolume("datos".into()),
+            ]
+        );
+        assert!(plan.warnings[0].contains("vm1:/gluster/viejo"));
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        assert!(ClusterSpec::parse("[[volumes]]\nname = \"v\"\nreplica = 3\nbricks = [\"vm1:/a\", \"vm2:/a\"]\n").is_err());
+        assert!(ClusterSpec::parse("[[volumes]]\nname = \"v\"\nbricks = [\"vm1-a\"]\n").is_err());
+        assert!(ClusterSpec::parse("[[volumes]]\nname = \"v\"\ntype = \"raid\"\nbricks = [\"vm1:/a\"]\n").is_err());
+        assert!(ClusterSpec::parse("desconocido = 1\n").is_err());
+    }
+}
@@ -0,0 +1,364 @@
+//! Ficheros en split-brain (`volume heal VOL info split-brain`) y su resolución con las políticas
+//! de gluster: fichero más grande, modificación más reciente o un brick como origen.
+
+use std::fmt;
+
+use dialoguer::{Confirm, Select, theme::ColorfulTheme};
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+
+/// Fichero (o gfid) en split-brain y los bricks que lo informan.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitBrainEntry {
+    /// Ruta desde la raíz del volumen o `gfid:UUID`, tal como la aceptan los comandos de resolución.
+    pub path: String,
+    pub bricks: Vec<String>,
+}
+
+impl SplitBrainEntry {
+    pub fn is_gfid(&self) -> bool {
+        self.path.starts_with("gfid:")
+    }
+}
+
+/// Cómo elegir la copia buena.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Policy {
+    BiggerFile,
+    LatestMtime,
+    /// Brick (`host:/ruta`) cuya copia se toma como buena.
+    SourceBrick(String),
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Policy::BiggerFile => t!("splitbrain.policy_bigger"),
+            Policy::LatestMtime => t!("splitbrain.policy_mtime"),
+            Policy::SourceBrick(brick) => t!("splitbrain.policy_source", brick = brick),
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// gluster lista los gfid sin ruta como `<gfid:UUID>`, pero los comandos esperan `gfid:UUID`.
+fn normalize(entry: &str) -> String {
+    entry.trim_start_matches('<').trim_end_matches('>').to_string()
+}
+
+/// Entradas en split-brain del volumen, agrupadas por fichero.
+pub fn list(backend: &dyn GlusterBackend, volume: &str) -> Result<Vec<SplitBrainEntry>> {
+    crate::heal::ensure_healable(backend, volume)?;
+    let output = backend
+        .gluster_xml(&["volume", "heal", volume, "info", "split-brain"])
+        .map_err(|e| e.context(&t!("heal.info_failed", name = volume)))?;
+
+    let mut entries: Vec<SplitBrainEntry> = vec![];
+    for brick in crate::heal::parse_info(&output)? {
+        for path in brick.entries.iter().map(|e| normalize(e)) {
+            match entries.iter_mut().find(|e| e.path == path) {
+                Some(entry) => entry.bricks.push(brick.brick.clone()),
+                None => entries.push(SplitBrainEntry { path, bricks: vec![brick.brick.clone()] }),
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Tamaño y fecha de modificación de la copia de un brick; `None` si no se pudo consultar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Copy {
+    pub brick: String,
+    pub size: Option<u64>,
+    pub mtime: Option<i64>,
+}
+
+impl Copy {
+    pub fn inspected(&self) -> bool {
+        self.size.is_some() && self.mtime.is_some()
+    }
+}
+
+/// Ruta de la copia dentro del brick; los gfid están en `.glusterfs/aa/bb/UUID`.
+fn path_in_brick(brick_path: &str, entry: &SplitBrainEntry) -> String {
+    let brick_path = brick_path.trim_end_matches('/');
+    if entry.is_gfid() && entry.path.len() > "gfid:".len() + 4 {
+        let gfid = &entry.path["gfid:".len()..];
+        format!("{}/.glusterfs/{}/{}/{}", brick_path, &gfid[..2], &gfid[2..4], gfid)
+    } else {
+        format!("{}/{}", brick_path, entry.path.trim_start_matches('/'))
+    }
+}
+
+/// Comillas simples para pasar una ruta por la shell remota de ssh.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// Consulta cada copia del fichero: las de este nodo directamente y las de otros por ssh
+/// (sin pedir contraseña; si el nodo no responde, la copia queda sin consultar).
+pub fn preview(backend: &dyn GlusterBackend, entry: &SplitBrainEntry) -> Vec<Copy> {
+    let local = backend.local_hostname();
+    entry
+        .bricks
+        .iter()
+        .map(|brick| {
+            let (host, path) = brick.split_once(':').unwrap_or(("", brick));
+            let path = path_in_brick(path, entry);
+            let output = if host == local || host == "localhost" {
+                backend.command("stat", &["-c", "%s %Y", &path])
+            } else {
+                let remote = format!("stat -c '%s %Y' {}", shell_quote(&path));
+                backend.command("ssh", &["-o", "BatchMode=yes", "-o", "ConnectTimeout=5", host, &remote])
+            };
+            let stat = output.ok().and_then(|out| {
+                let (size, mtime) = out.trim().split_once(' ')?;
+                Some((size.parse().ok()?, mtime.parse().ok()?))
+            });
+            Copy {
+                brick: brick.clone(),
+                size: stat.map(|(size, _)| size),
+                mtime: stat.map(|(_, mtime)| mtime),
+            }
+        })
+        .collect()
+}
+
+/// Brick que ganaría con la política, si se conocen todas las copias y no hay empate.
+pub fn predicted_source(policy: &Policy, copies: &[Copy]) -> Option<String> {
+    if let Policy::SourceBrick(brick) = policy {
+        return Some(brick.clone());
+    }
+    let key = |c: &Copy| match policy {
+        Policy::BiggerFile => c.size.map(|s| s as i64),
+        _ => c.mtime,
+    };
+    let values: Vec<i64> = copies.iter().map(key).collect::<Option<_>>()?;
+    let best = *values.iter().max()?;
+    if values.iter().filter(|v| **v == best).count() > 1 {
+        return None;
+    }
+    copies.iter().find(|c| key(c) == Some(best)).map(|c| c.brick.clone())
+}
+
+pub fn print_preview(entry: &SplitBrainEntry, copies: &[Copy]) {
+    println!("{}", t!("splitbrain.preview_title", path = entry.path));
+    let width = copies.iter().map(|c| c.brick.len()).max().unwrap_or(5).max(5);
+    println!("   {:<width$}  {:>14}  {}", t!("heal.column_brick"), t!("splitbrain.column_size"), t!("splitbrain.column_mtime"));
+    for copy in copies {
+        if !copy.inspected() {
+            println!("   {:<width$}  {}", copy.brick, t!("splitbrain.copy_unchecked"));
+            continue;
+        }
+        let mtime = copy
+            .mtime
+            .and_then(|m| chrono::DateTime::from_timestamp(m, 0))
+            .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let size = copy.size.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string());
+        println!("   {:<width$}  {:>14}  {}", copy.brick, size, mtime);
+    }
+    if copies.iter().any(|c| !c.inspected()) {
+        println!("{}", t!("splitbrain.unchecked_hint"));
+    }
+    for policy in [Policy::BiggerFile, Policy::LatestMtime] {
+        if let Some(brick) = predicted_source(&policy, copies) {
+            println!("{}", t!("splitbrain.would_pick", policy = policy, brick = brick));
+        }
+    }
+}
+
+pub fn print_list(volume: &str, entries: &[SplitBrainEntry]) {
+    println!("{}", t!("splitbrain.title", name = volume));
+    if entries.is_empty() {
+        println!("{}", t!("splitbrain.none"));
+        return;
+    }
+    for entry in entries {
+        println!("   {}  ({})", entry.path, entry.bricks.join(", "));
+    }
+    println!("{}", t!("splitbrain.count", count = entries.len()));
+}
+
+/// Resuelve un fichero con la política elegida.
+pub fn resolve_file(backend: &dyn GlusterBackend, volume: &str, path: &str, policy: &Policy) -> Result<()> {
+    let mut args = vec!["volume", "heal", volume, "split-brain"];
+    match policy {
+        Policy::BiggerFile => args.push("bigger-file"),
+        Policy::LatestMtime => args.push("latest-mtime"),
+        Policy::SourceBrick(brick) => args.extend(["source-brick", brick.as_str()]),
+    }
+    args.push(path);
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| e.context(&t!("splitbrain.resolve_failed", path = path)))?;
+    println!("{}", t!("splitbrain.resolved", path = path, policy = policy));
+    Ok(())
+}
+
+/// Resuelve todas las entradas en split-brain tomando las copias de `brick` como buenas.
+pub fn resolve_brick(backend: &dyn GlusterBackend, volume: &str, brick: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["volume", "heal", volume, "split-brain", "source-brick", brick])
+        .map_err(|e| e.context(&t!("splitbrain.resolve_brick_failed", brick = brick)))?;
+    println!("{}", t!("splitbrain.brick_resolved", brick = brick));
+    Ok(())
+}
+
+/// Asistente: elige un fichero (viendo sus copias) o un brick como origen de todo.
+pub fn resolve_interactive(backend: &dyn GlusterBackend, theme: &ColorfulTheme, volume: &str) -> Result<()> {
+    let entries = list(backend, volume)?;
+    print_list(volume, &entries);
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let modes = [t!("splitbrain.mode_file"), t!("splitbrain.mode_brick")];
+    let mode = Select::with_theme(theme)
+        .with_prompt(t!("splitbrain.mode_prompt"))
+        .items(&modes)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    if mode == 1 {
+        let mut bricks: Vec<&String> = entries.iter().flat_map(|e| &e.bricks).collect();
+        bricks.sort();
+        bricks.dedup();
+        let index = Select::with_theme(theme)
+            .with_prompt(t!("splitbrain.brick_prompt"))
+            .items(&bricks)
+            .default(0)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?;
+        let brick = bricks[index];
+        let affected = entries.iter().filter(|e| e.bricks.contains(brick)).count();
+        return if confirm(theme, &t!("splitbrain.brick_confirm", brick = brick, count = affected))? {
+            resolve_brick(backend, volume, brick)
+        } else {
+            println!("{}", t!("common.cancelled"));
+            Ok(())
+        };
+    }
+
+    let items: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("splitbrain.file_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let entry = &entries[index];
+    let copies = preview(backend, entry);
+    print_preview(entry, &copies);
+
+    let mut policies = vec![Policy::BiggerFile, Policy::LatestMtime];
+    policies.extend(entry.bricks.iter().map(|b| Policy::SourceBrick(b.clone())));
+    let index = Select::with_theme(theme)
+        .with_prompt(t!("splitbrain.policy_prompt"))
+        .items(&policies)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    let policy = &policies[index];
+
+    let prompt = match predicted_source(policy, &copies) {
+        Some(brick) => t!("splitbrain.file_confirm_known", path = entry.path, brick = brick),
+        None => t!("splitbrain.file_confirm", path = entry.path, policy = policy),
+    };
+    if confirm(theme, &prompt)? {
+        resolve_file(backend, volume, &entry.path, policy)
+    } else {
+        println!("{}", t!("common.cancelled"));
+        Ok(())
+    }
+}
+
+fn confirm(theme: &ColorfulTheme, prompt: &str) -> Result<bool> {
+    Confirm::with_theme(theme)
+        .with_prompt(prompt)
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn diverged() -> FakeBackend {
+        FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started)
+            .with_replica("datos", 2)
+            .with_split_brain("datos", "/docs/informe.odt", &["vm1:/gluster/a", "vm2:/gluster/a"])
+            .with_split_brain("datos", "gfid:7d3b1c2a-0000-4000-8000-000000000001", &["vm1:/gluster/a", "vm2:/gluster/a"])
+            .with_stat("/gluster/a/docs/informe.odt", 2048, 1_790_000_000)
+    }
+
+    #[test]
+    fn lists_entries_once_with_their_bricks() {
+        let fake = diverged();
+        let entries = list(&fake, "datos").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].bricks, vec!["vm1:/gluster/a", "vm2:/gluster/a"]);
+        assert!(entries[1].is_gfid());
+        // El resumen de self-heal también los cuenta.
+        assert_eq!(crate::heal::summary(&fake, "datos").unwrap()[0].split_brain, Some(2));
+    }
+
+    #[test]
+    fn preview_reads_remote_copies_over_ssh() {
+        let fake = diverged();
+        let entries = list(&fake, "datos").unwrap();
+        let copies = preview(&fake, &entries[0]);
+        assert_eq!((copies[0].size, copies[0].mtime), (Some(2048), Some(1_790_000_000)));
+        // Sin la copia remota no se puede saber cuál ganaría.
+        assert!(!copies[1].inspected());
+        assert_eq!(predicted_source(&Policy::BiggerFile, &copies), None);
+
+        let fake = fake.with_remote_stat("vm2", "/gluster/a/docs/informe.odt", 4096, 1_780_000_000);
+        let copies = preview(&fake, &entries[0]);
+        assert_eq!((copies[1].size, copies[1].mtime), (Some(4096), Some(1_780_000_000)));
+        assert_eq!(predicted_source(&Policy::BiggerFile, &copies).as_deref(), Some("vm2:/gluster/a"));
+        assert_eq!(predicted_source(&Policy::LatestMtime, &copies).as_deref(), Some("vm1:/gluster/a"));
+        assert_eq!(shell_quote("/gluster/a/l'acta.odt"), "'/gluster/a/l'\\''acta.odt'");
+
+        assert_eq!(
+            path_in_brick("/gluster/a", &entries[1]),
+            "/gluster/a/.glusterfs/7d/3b/7d3b1c2a-0000-4000-8000-000000000001"
+        );
+    }
+
+    #[test]
+    fn predicts_the_winning_copy() {
+        let copy = |brick: &str, size, mtime| Copy { brick: brick.to_string(), size: Some(size), mtime: Some(mtime) };
+        let copies = [copy("vm1:/a", 10, 200), copy("vm2:/a", 20, 100)];
+        assert_eq!(predicted_source(&Policy::BiggerFile, &copies).as_deref(), Some("vm2:/a"));
+        assert_eq!(predicted_source(&Policy::LatestMtime, &copies).as_deref(), Some("vm1:/a"));
+        let tied = [copy("vm1:/a", 10, 200), copy("vm2:/a", 10, 200)];
+        assert_eq!(predicted_source(&Policy::BiggerFile, &tied), None);
+    }
+
+    #[test]
+    fn resolves_single_files_and_whole_bricks() {
+        let fake = diverged();
+        resolve_file(&fake, "datos", "/docs/informe.odt", &Policy::SourceBrick("vm2:/gluster/a".into())).unwrap();
+        assert_eq!(list(&fake, "datos").unwrap().len(), 1);
+        assert!(resolve_file(&fake, "datos", "/no/existe", &Policy::LatestMtime).is_err());
+
+        resolve_brick(&fake, "datos", "vm1:/gluster/a").unwrap();
+        assert!(list(&fake, "datos").unwrap().is_empty());
+        assert_eq!(
+            fake.commands(),
+            vec![
+                "gluster --mode=script volume heal datos split-brain source-brick vm2:/gluster/a /docs/informe.odt",
+                "gluster --mode=script volume heal datos split-brain latest-mtime /no/existe",
+                "gluster --mode=script volume heal datos split-brain source-brick vm1:/gluster/a",
+            ]
+        );
+    }
+}
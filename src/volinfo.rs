@@ -0,0 +1,266 @@
+//! Modelo tipado de `gluster volume info --xml`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::backend::GlusterBackend;
+use crate::error::{Error, Result};
+use crate::t;
+use crate::xml;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Brick {
+    /// `host:/ruta`, tal como se pasa a los comandos de gluster.
+    pub name: String,
+    pub host: String,
+    pub path: String,
+    pub uuid: String,
+    pub is_arbiter: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VolumeInfo {
+    pub name: String,
+    pub id: String,
+    /// Tipo tal como lo informa gluster (`Distribute`, `Replicate`, `Distributed-Disperse`, ...).
+    pub vol_type: String,
+    /// `Created`, `Started` o `Stopped`.
+    pub status: String,
+    pub brick_count: u32,
+    pub replica_count: u32,
+    pub arbiter_count: u32,
+    pub disperse_count: u32,
+    pub redundancy_count: u32,
+    pub transport: String,
+    /// Bricks en el orden de gluster: los conjuntos de réplica/dispersión son consecutivos.
+    pub bricks: Vec<Brick>,
+    pub options: BTreeMap<String, String>,
+}
+
+impl VolumeInfo {
+    pub fn brick_names(&self) -> Vec<String> {
+        self.bricks.iter().map(|b| b.name.clone()).collect()
+    }
+}
+
+/// Lee la información de todos los volúmenes o de uno concreto.
+pub fn fetch(backend: &dyn GlusterBackend, volume: Option<&str>) -> Result<Vec<VolumeInfo>> {
+    let mut args = vec!["volume", "info"];
+    args.extend(volume);
+
+    let output = backend.gluster_xml(&args)?;
+    parse(&output)
+}
+
+/// Lee la información de un volumen que debe existir.
+pub fn fetch_one(backend: &dyn GlusterBackend, volume: &str) -> Result<VolumeInfo> {
+    fetch(backend, Some(volume))?
+        .into_iter()
+        .find(|v| v.name == volume)
+        .ok_or_else(|| Error::Message(t!("volinfo.not_found", name = volume)))
+}
+
+pub fn parse(output: &str) -> Result<Vec<VolumeInfo>> {
+    let doc = xml::parse_cli_output(output)?;
+
+    let Some(volumes) = xml::descendant(doc.root_element(), "volumes") else {
+        return Ok(vec![]);
+    };
+
+    xml::children(volumes, "volume").map(parse_volume).collect()
+}
+
+fn parse_volume(node: roxmltree::Node) -> Result<VolumeInfo> {
+    let name = xml::child_text(node, "name")
+        .ok_or_else(|| Error::Parse(t!("volinfo.unnamed_volume")))?
+        .to_string();
+
+    let bricks = match xml::child(node, "bricks") {
+        Some(bricks) => xml::children(bricks, "brick").map(parse_brick).collect::<Result<_>>()?,
+        None => vec![],
+    };
+
+    let options = match xml::child(node, "options") {
+        Some(options) => xml::children(options, "option")
+            .filter_map(|opt| {
+                let key = xml::child_text(opt, "name")?;
+                let value = xml::child_text(opt, "value").unwrap_or_default();
+                Some((key.to_string(), value.to_string()))
+            })
+            .collect(),
+        None => BTreeMap::new(),
+    };
+
+    let transport = match xml::child_text(node, "transport") {
+        Some("1") => "rdma",
+        Some("2") => "tcp,rdma",
+        _ => "tcp",
+    };
+
+    Ok(VolumeInfo {
+        id: xml::child_text(node, "id").unwrap_or_default().to_string(),
+        vol_type: xml::child_text(node, "typeStr").unwrap_or("Distribute").to_string(),
+        status: xml::child_text(node, "statusStr").unwrap_or("Created").to_string(),
+        brick_count: xml::child_parse(node, "brickCount").unwrap_or(bricks.len() as u32),
+        replica_count: xml::child_parse(node, "replicaCount").unwrap_or(1),
+        arbiter_count: xml::child_parse(node, "arbiterCount").unwrap_or(0),
+        disperse_count: xml::child_parse(node, "disperseCount").unwrap_or(0),
+        redundancy_count: xml::child_parse(node, "redundancyCount").unwrap_or(0),
+        transport: transport.to_string(),
+        name,
+        bricks,
+        options,
+    })
+}
+
+fn parse_brick(node: roxmltree::Node) -> Result<Brick> {
+    // Versiones antiguas solo traen el texto "host:/ruta" dentro de <brick>.
+    let name = xml::child_text(node, "name")
+        .or_else(|| node.text().map(str::trim).filter(|t| !t.is_empty()))
+        .ok_or_else(|| Error::Parse(t!("volinfo.unnamed_brick")))?
+        .to_string();
+
+    let (host, path) = name
+        .rsplit_once(":/")
+        .map(|(host, path)| (host.to_string(), format!("/{}", path)))
+        .ok_or_else(|| Error::Parse(t!("volinfo.bad_brick", name = name)))?;
+
+    Ok(Brick {
+        uuid: xml::child_text(node, "hostUuid")
+            .or_else(|| node.attribute("uuid"))
+            .unwrap_or_default()
+            .to_string(),
+        is_arbiter: xml::child_text(node, "isArbiter") == Some("1"),
+        name,
+        host,
+        path,
+    })
+}
+
+impl fmt::Display for VolumeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", t!("volinfo.name", name = self.name))?;
+        writeln!(f, "   ID: {}", self.id)?;
+        writeln!(f, "{}", t!("volinfo.type", value = self.vol_type))?;
+        writeln!(f, "{}", t!("volinfo.status", value = self.status))?;
+        if self.replica_count > 1 {
+            writeln!(
+                f,
+                "{}",
+                t!("volinfo.replica", replica = self.replica_count, arbiter = self.arbiter_count)
+            )?;
+        }
+        if self.disperse_count > 0 {
+            writeln!(
+                f,
+                "{}",
+                t!("volinfo.disperse", disperse = self.disperse_count, redundancy = self.redundancy_count)
+            )?;
+        }
+        writeln!(f, "{}", t!("volinfo.transport", value = self.transport))?;
+        writeln!(f, "{}", t!("volinfo.bricks", count = self.brick_count))?;
+        for (i, brick) in self.bricks.iter().enumerate() {
+            let arbiter = if brick.is_arbiter { t!("volinfo.arbiter") } else { String::new() };
+            writeln!(f, "     {}. {}{}", i + 1, brick.name, arbiter)?;
+        }
+        if !self.options.is_empty() {
+            writeln!(f, "{}", t!("volinfo.options"))?;
+            for (key, value) in &self.options {
+                writeln!(f, "     {} = {}", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLICA: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cliOutput>
+  <opRet>0</opRet>
+  <opErrno>0</opErrno>
+  <opErrstr/>
+  <volInfo>
+    <volumes>
+      <volume>
+        <name>datos</name>
+        <id>5f0d2a5e-9a43-4b55-9a0e-0c1c1f6f2d11</id>
+        <status>1</status>
+        <statusStr>Started</statusStr>
+        <snapshotCount>0</snapshotCount>
+        <brickCount>3</brickCount>
+        <distCount>3</distCount>
+        <replicaCount>3</replicaCount>
+        <arbiterCount>1</arbiterCount>
+        <disperseCount>0</disperseCount>
+        <redundancyCount>0</redundancyCount>
+        <type>2</type>
+        <typeStr>Replicate</typeStr>
+        <transport>0</transport>
+        <bricks>
+          <brick uuid="a1">vm1:/gluster/a<name>vm1:/gluster/a</name><hostUuid>a1</hostUuid><isArbiter>0</isArbiter></brick>
+          <brick uuid="b2">vm2:/gluster/a<name>vm2:/gluster/a</name><hostUuid>b2</hostUuid><isArbiter>0</isArbiter></brick>
+          <brick uuid="c3">vm3:/gluster/arb<name>vm3:/gluster/arb</name><hostUuid>c3</hostUuid><isArbiter>1</isArbiter></brick>
+        </bricks>
+        <optCount>2</optCount>
+        <options>
+          <option><name>cluster.brick-multiplex</name><value>off</value></option>
+          <option><name>performance.client-io-threads</name><value>off</value></option>
+        </options>
+      </volume>
+      <count>1</count>
+    </volumes>
+  </volInfo>
+</cliOutput>"#;
+
+    #[test]
+    fn parses_replica_arbiter_volume() {
+        let volumes = parse(REPLICA).unwrap();
+        assert_eq!(volumes.len(), 1);
+
+        let volume = &volumes[0];
+        assert_eq!(volume.name, "datos");
+        assert_eq!(volume.vol_type, "Replicate");
+        assert_eq!(volume.status, "Started");
+        assert_eq!((volume.replica_count, volume.arbiter_count), (3, 1));
+        assert_eq!(volume.transport, "tcp");
+        assert_eq!(volume.brick_names(), vec!["vm1:/gluster/a", "vm2:/gluster/a", "vm3:/gluster/arb"]);
+        assert_eq!(volume.bricks[2].host, "vm3");
+        assert_eq!(volume.bricks[2].path, "/gluster/arb");
+        assert_eq!(volume.bricks[1].uuid, "b2");
+        assert!(volume.bricks[2].is_arbiter);
+        // Las opciones con "Brick" en el nombre ya no se confunden con bricks.
+        assert_eq!(volume.options["cluster.brick-multiplex"], "off");
+    }
+
+    #[test]
+    fn empty_list_and_errors() {
+        let empty = r#"<cliOutput><opRet>0</opRet><opErrno>0</opErrno><opErrstr/><volInfo><volumes><count>0</count></volumes></volInfo></cliOutput>"#;
+        assert!(parse(empty).unwrap().is_empty());
+
+        let missing = r#"<cliOutput><opRet>-1</opRet><opErrno>30800</opErrno><opErrstr>Volume x does not exist</opErrstr></cliOutput>"#;
+        assert_eq!(parse(missing).unwrap_err().to_string(), "Volume x does not exist");
+
+        assert!(parse("Volume Name: datos").is_err());
+    }
+
+    #[test]
+    fn brick_with_ipv6_host_keeps_full_host() {
+        let xml = r#"<cliOutput><opRet>0</opRet><volInfo><volumes><volume><name>v</name>
+            <bricks><brick>fe80::1:/gluster/a</brick></bricks></volume></volumes></volInfo></cliOutput>"#;
+        let volume = &parse(xml).unwrap()[0];
+        assert_eq!(volume.bricks[0].host, "fe80::1");
+        assert_eq!(volume.bricks[0].path, "/gluster/a");
+    }
+
+    #[test]
+    fn fetch_reports_gluster_error_message() {
+        use crate::fake::{FakeBackend, VolumeState};
+
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+        assert_eq!(fetch_one(&fake, "datos").unwrap().brick_names(), vec!["vm1:/gluster/a"]);
+        assert_eq!(fetch_one(&fake, "otro").unwrap_err().to_string(), "Volume otro does not exist");
+    }
+}
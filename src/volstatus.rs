@@ -0,0 +1,378 @@
+//! Modelo tipado de `gluster volume status --xml` (estado, detalle de disco y clientes).
+
+use console::style;
+
+use crate::backend::GlusterBackend;
+use crate::error::Result;
+use crate::t;
+use crate::xml;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BrickStatus {
+    pub host: String,
+    pub path: String,
+    pub peer_id: String,
+    pub online: bool,
+    pub tcp_port: Option<u16>,
+    pub rdma_port: Option<u16>,
+    pub pid: Option<u32>,
+    pub size_total: Option<u64>,
+    pub size_free: Option<u64>,
+    pub inodes_total: Option<u64>,
+    pub inodes_free: Option<u64>,
+    /// Clientes conectados (solo si se consultó `clients`).
+    pub clients: Option<u32>,
+}
+
+impl BrickStatus {
+    /// `host:/ruta`, igual que `volinfo::Brick::name`.
+    pub fn name(&self) -> String {
+        format!("{}:{}", self.host, self.path)
+    }
+}
+
+/// Proceso auxiliar de un nodo (self-heal, quota, NFS, ...).
+#[derive(Clone, Debug, PartialEq)]
+pub struct DaemonStatus {
+    pub name: String,
+    pub host: String,
+    pub online: bool,
+    pub pid: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VolumeStatus {
+    pub name: String,
+    pub bricks: Vec<BrickStatus>,
+    pub daemons: Vec<DaemonStatus>,
+}
+
+impl VolumeStatus {
+    pub fn brick(&self, name: &str) -> Option<&BrickStatus> {
+        self.bricks.iter().find(|b| b.name() == name)
+    }
+
+    pub fn offline_bricks(&self) -> Vec<&BrickStatus> {
+        self.bricks.iter().filter(|b| !b.online).collect()
+    }
+
+    pub fn self_heal_daemons(&self) -> impl Iterator<Item = &DaemonStatus> {
+        self.daemons.iter().filter(|d| d.name == "Self-heal Daemon")
+    }
+}
+
+/// Estado de los volúmenes iniciados (todos o uno), con detalle de disco y clientes por brick.
+pub fn fetch(backend: &dyn GlusterBackend, volume: Option<&str>) -> Result<Vec<VolumeStatus>> {
+    let target = volume.unwrap_or("all");
+
+    let mut volumes = parse(&backend.gluster_xml(&["volume", "status", target])?)?;
+
+    // `detail` y `clients` son consultas separadas en gluster; si fallan se muestra lo básico.
+    for extra in ["detail", "clients"] {
+        if let Ok(output) = backend.gluster_xml(&["volume", "status", target, extra])
+            && let Ok(parsed) = parse(&output)
+        {
+            merge(&mut volumes, parsed);
+        }
+    }
+
+    Ok(volumes)
+}
+
+pub fn parse(output: &str) -> Result<Vec<VolumeStatus>> {
+    let doc = xml::parse_cli_output(output)?;
+
+    let Some(volumes) = xml::descendant(doc.root_element(), "volumes") else {
+        return Ok(vec![]);
+    };
+
+    Ok(xml::children(volumes, "volume").map(parse_volume).collect())
+}
+
+fn parse_volume(node: roxmltree::Node) -> VolumeStatus {
+    let mut status = VolumeStatus {
+        name: xml::child_text(node, "volName").unwrap_or_default().to_string(),
+        bricks: vec![],
+        daemons: vec![],
+    };
+
+    for n in xml::children(node, "node") {
+        let hostname = xml::child_text(n, "hostname").unwrap_or_default().to_string();
+        let path = xml::child_text(n, "path").unwrap_or_default().to_string();
+        let online = xml::child_text(n, "status") == Some("1");
+        let pid = xml::child_parse(n, "pid").filter(|pid| *pid > 0);
+
+        // Los bricks tienen una ruta absoluta; los demonios traen su nombre en <hostname>
+        // y el nodo en <path>.
+        if !path.starts_with('/') {
+            status.daemons.push(DaemonStatus {
+                name: hostname,
+                host: path,
+                online,
+                pid,
+            });
+            continue;
+        }
+
+        let ports = xml::child(n, "ports");
+        let tcp_port = ports
+            .and_then(|p| xml::child_parse(p, "tcp"))
+            .or_else(|| xml::child_parse(n, "port"));
+        let rdma_port = ports.and_then(|p| xml::child_parse(p, "rdma"));
+
+        status.bricks.push(BrickStatus {
+            host: hostname,
+            path,
+            peer_id: xml::child_text(n, "peerid").unwrap_or_default().to_string(),
+            online,
+            tcp_port,
+            rdma_port,
+            pid,
+            size_total: xml::child_parse(n, "sizeTotal"),
+            size_free: xml::child_parse(n, "sizeFree"),
+            inodes_total: xml::child_parse(n, "inodesTotal"),
+            inodes_free: xml::child_parse(n, "inodesFree"),
+            clients: xml::child(n, "clientsStatus").and_then(|c| xml::child_parse(c, "clientCount")),
+        });
+    }
+
+    status
+}
+
+/// Completa los bricks de `base` con los campos que trae una consulta `detail`/`clients`.
+fn merge(base: &mut [VolumeStatus], extra: Vec<VolumeStatus>) {
+    for extra_volume in extra {
+        let Some(volume) = base.iter_mut().find(|v| v.name == extra_volume.name) else {
+            continue;
+        };
+        for extra_brick in extra_volume.bricks {
+            if let Some(brick) = volume.bricks.iter_mut().find(|b| b.name() == extra_brick.name()) {
+                brick.size_total = brick.size_total.or(extra_brick.size_total);
+                brick.size_free = brick.size_free.or(extra_brick.size_free);
+                brick.inodes_total = brick.inodes_total.or(extra_brick.inodes_total);
+                brick.inodes_free = brick.inodes_free.or(extra_brick.inodes_free);
+                brick.clients = brick.clients.or(extra_brick.clients);
+            }
+        }
+    }
+}
+
+/// Tamaño en bytes en unidades binarias legibles (ej. `9.8 GiB`).
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn or_dash<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+/// Tabla compacta de salud de un volumen; los bricks caídos se resaltan en rojo.
+pub fn print_table(status: &VolumeStatus) {
+    println!("{}", t!("volstatus.title", name = status.name));
+
+    let width = status.bricks.iter().map(|b| b.name().len()).max().unwrap_or(5).max(5);
+    println!(
+        "   {:<width$}  {:<6}  {:>6}  {:>6}  {:>7}  {:>21}  {:>15}  {:>8}",
+        "Brick",
+        "Online",
+        "TCP",
+        "RDMA",
+        "PID",
+        t!("volstatus.free_total"),
+        t!("volstatus.free_inodes"),
+        t!("volstatus.clients"),
+    );
+
+    for brick in &status.bricks {
+        let space = match (brick.size_free, brick.size_total) {
+            (Some(free), Some(total)) => format!("{} / {}", format_size(free), format_size(total)),
+            _ => "-".to_string(),
+        };
+        let line = format!(
+            "   {:<width$}  {:<6}  {:>6}  {:>6}  {:>7}  {:>21}  {:>15}  {:>8}",
+            brick.name(),
+            if brick.online { t!("common.yes") } else { t!("common.no") },
+            or_dash(brick.tcp_port),
+            or_dash(brick.rdma_port),
+            or_dash(brick.pid),
+            space,
+            or_dash(brick.inodes_free),
+            or_dash(brick.clients),
+        );
+        if brick.online {
+            println!("{}", line);
+        } else {
+            println!("{}", style(line).red().bold());
+        }
+    }
+
+    let heal_total = status.self_heal_daemons().count();
+    if heal_total > 0 {
+        let heal_online = status.self_heal_daemons().filter(|d| d.online).count();
+        println!("{}", t!("volstatus.self_heal", online = heal_online, total = heal_total));
+    }
+
+    for daemon in &status.daemons {
+        let state = if daemon.online {
+            style(t!("volstatus.daemon_up")).green()
+        } else {
+            style(t!("volstatus.daemon_down")).red().bold()
+        };
+        println!(
+            "{}",
+            t!("volstatus.daemon", name = daemon.name, host = daemon.host, state = state, pid = or_dash(daemon.pid))
+        );
+    }
+
+    let offline = status.offline_bricks().len();
+    if offline > 0 {
+        println!("{}", style(t!("volstatus.offline", count = offline)).red().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATUS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cliOutput>
+  <opRet>0</opRet>
+  <opErrno>0</opErrno>
+  <opErrstr/>
+  <volStatus>
+    <volumes>
+      <volume>
+        <volName>datos</volName>
+        <nodeCount>4</nodeCount>
+        <node>
+          <hostname>vm1</hostname>
+          <path>/gluster/a</path>
+          <peerid>a1</peerid>
+          <status>1</status>
+          <port>49152</port>
+          <ports><tcp>49152</tcp><rdma>N/A</rdma></ports>
+          <pid>1201</pid>
+        </node>
+        <node>
+          <hostname>vm2</hostname>
+          <path>/gluster/a</path>
+          <peerid>b2</peerid>
+          <status>0</status>
+          <port>N/A</port>
+          <ports><tcp>N/A</tcp><rdma>N/A</rdma></ports>
+          <pid>-1</pid>
+        </node>
+        <node>
+          <hostname>Self-heal Daemon</hostname>
+          <path>localhost</path>
+          <peerid>a1</peerid>
+          <status>1</status>
+          <port>N/A</port>
+          <ports><tcp>N/A</tcp><rdma>N/A</rdma></ports>
+          <pid>1300</pid>
+        </node>
+        <node>
+          <hostname>Self-heal Daemon</hostname>
+          <path>vm2</path>
+          <peerid>b2</peerid>
+          <status>0</status>
+          <port>N/A</port>
+          <ports><tcp>N/A</tcp><rdma>N/A</rdma></ports>
+          <pid>-1</pid>
+        </node>
+        <tasks/>
+      </volume>
+    </volumes>
+  </volStatus>
+</cliOutput>"#;
+
+    const DETAIL: &str = r#"<cliOutput><opRet>0</opRet><volStatus><volumes><volume>
+        <volName>datos</volName>
+        <node>
+          <hostname>vm1</hostname><path>/gluster/a</path><status>1</status><port>49152</port><pid>1201</pid>
+          <sizeTotal>10737418240</sizeTotal><sizeFree>5368709120</sizeFree>
+          <device>/dev/vdb1</device><blockSize>4096</blockSize><fsName>xfs</fsName>
+          <inodeSize>xfs</inodeSize><inodesTotal>5242880</inodesTotal><inodesFree>5242000</inodesFree>
+        </node>
+      </volume></volumes></volStatus></cliOutput>"#;
+
+    const CLIENTS: &str = r#"<cliOutput><opRet>0</opRet><volStatus><volumes><volume>
+        <volName>datos</volName>
+        <node>
+          <hostname>vm1</hostname><path>/gluster/a</path><status>1</status><port>49152</port><pid>1201</pid>
+          <clientsStatus><clientCount>2</clientCount>
+            <client><hostname>10.0.0.5:1021</hostname><bytesRead>100</bytesRead><bytesWrite>200</bytesWrite></client>
+            <client><hostname>10.0.0.6:1020</hostname><bytesRead>100</bytesRead><bytesWrite>200</bytesWrite></client>
+          </clientsStatus>
+        </node>
+      </volume></volumes></volStatus></cliOutput>"#;
+
+    #[test]
+    fn parses_bricks_and_daemons() {
+        let volumes = parse(STATUS).unwrap();
+        let status = &volumes[0];
+
+        assert_eq!(status.name, "datos");
+        assert_eq!(status.bricks.len(), 2);
+        assert_eq!(status.bricks[0].tcp_port, Some(49152));
+        assert_eq!(status.bricks[0].rdma_port, None);
+        assert_eq!(status.bricks[0].pid, Some(1201));
+        assert_eq!(status.bricks[1].pid, None);
+        assert_eq!(
+            status.offline_bricks().iter().map(|b| b.name()).collect::<Vec<_>>(),
+            vec!["vm2:/gluster/a"]
+        );
+        assert_eq!(status.self_heal_daemons().filter(|d| d.online).count(), 1);
+        assert_eq!(status.daemons[1].host, "vm2");
+    }
+
+    #[test]
+    fn merges_detail_and_clients() {
+        let mut volumes = parse(STATUS).unwrap();
+        merge(&mut volumes, parse(DETAIL).unwrap());
+        merge(&mut volumes, parse(CLIENTS).unwrap());
+
+        let brick = volumes[0].brick("vm1:/gluster/a").unwrap();
+        assert_eq!(brick.size_total, Some(10737418240));
+        assert_eq!(brick.size_free, Some(5368709120));
+        assert_eq!(brick.inodes_free, Some(5242000));
+        assert_eq!(brick.clients, Some(2));
+        assert_eq!(volumes[0].brick("vm2:/gluster/a").unwrap().clients, None);
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(10737418240), "10.0 GiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn fetch_combines_queries_from_backend() {
+        use crate::fake::{FakeBackend, VolumeState};
+
+        let fake = FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a", "vm2:/gluster/a"], VolumeState::Started)
+            .with_volume("parado", &["vm1:/gluster/b"], VolumeState::Stopped)
+            .with_offline_brick("datos", "vm2:/gluster/a");
+
+        let volumes = fetch(&fake, None).unwrap();
+        assert_eq!(volumes.len(), 1);
+        let online = volumes[0].brick("vm1:/gluster/a").unwrap();
+        assert_eq!((online.online, online.clients, online.size_total), (true, Some(1), Some(10737418240)));
+        assert_eq!(volumes[0].offline_bricks().len(), 1);
+
+        assert_eq!(fetch(&fake, Some("parado")).unwrap_err().to_string(), "Volume parado is not started");
+    }
+}
@@ -1,524 +1,986 @@
-use dialoguer::{Input, Select, Confirm, theme::ColorfulTheme};
-use std::process::Command;
+use dialoguer::{Input, MultiSelect, Select, Confirm, theme::ColorfulTheme};
 use std::io::{self, Write};
 use std::str;
 
-fn run_command(cmd: &str, args: &[&str]) -> Result<String, String> {
-    let output = Command::new(cmd)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Error al ejecutar comando: {}", e))?;
+use crate::backend::GlusterBackend;
+use crate::error::{self, Error, Result};
+use crate::volinfo::{self, VolumeInfo};
+use crate::volstatus;
+use crate::pool;
+use crate::layout::VolumeLayout;
+use crate::placement;
 
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
-    }
-}
-
-fn list_volumes() -> Vec<String> {
-    match run_command("gluster", &["volume", "info"]) {
-        Ok(output) => {
-            // Extraemos nombres de volumen, buscando líneas que empiecen con "Volume Name: "
-            output.lines()
-                .filter_map(|line| {
-                    if let Some(name) = line.strip_prefix("Volume Name: ") {
-                        Some(name.trim().to_string())
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        }
-        Err(e) => {
-            eprintln!("⚠️ Error listando volúmenes: {}", e);
-            vec![]
-        }
-    }
-}
-
-fn list_peers() -> Vec<String> {
-    match run_command("gluster", &["peer", "status"]) {
-        Ok(output) => {
-            // Buscamos líneas que contienen "Hostname: <host>"
-            output.lines()
-                .filter_map(|line| {
-                    if let Some(host) = line.trim().strip_prefix("Hostname: ") {
-                        Some(host.to_string())
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        }
-        Err(e) => {
-            eprintln!("⚠️ Error listando peers: {}", e);
-            vec![]
-        }
-    }
-}
+use crate::i18n::{is_done, is_exit};
+use crate::t;
 
-fn list_bricks(volume: &str) -> Vec<String> {
-    match run_command("gluster", &["volume", "info", volume]) {
-        Ok(output) => {
-            // Extraemos líneas con bricks: "Brick1: vm1:/ruta/brick"
-            output.lines()
-                .filter_map(|line| {
-                    if let Some(brick) = line.trim().strip_prefix("Brick") {
-                        // brick tiene formato "1: vm1:/ruta/brick"
-                        if let Some(pos) = brick.find(':') {
-                            Some(brick[(pos+1)..].trim().to_string())
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        }
-        Err(e) => {
-            eprintln!("⚠️ Error listando bricks: {}", e);
-            vec![]
-        }
-    }
+/// Un brick válido tiene la forma `host:/ruta`.
+pub fn is_valid_brick(brick: &str) -> bool {
+    brick.contains(':') && brick.contains('/')
 }
 
-pub fn create_volume() {
-    println!("\n📦 Crear volumen GlusterFS");
+pub fn create_volume(backend: &dyn GlusterBackend) -> Result<()> {
+    println!("\n{}", t!("volume.create_title"));
 
     let theme = ColorfulTheme::default();
 
     let vol_name: String = Input::with_theme(&theme)
-        .with_prompt("Nombre del volumen (o escribe 'salir' para cancelar)")
-        .interact_text()
-        .unwrap();
+        .with_prompt(t!("volume.name_prompt", exit = t!("common.exit_word")))
+        .interact_text()?;
 
-    if vol_name.trim().eq_ignore_ascii_case("salir") {
-        println!("❎ Operación cancelada.");
-        return;
+    if is_exit(&vol_name) {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
-    println!("🧱 Ahora ingresa los bricks para este volumen.");
-    println!("Formato: vm1:/gluster/brick1 (uno por línea). Escribe 'fin' para terminar o 'salir' para cancelar completamente.");
+    // `None`: dispersión, cuya combinación se elige cuando ya se sabe cuántos bricks hay.
+    let chosen = choose_layout(&theme)?;
+
+    println!("{}", t!("volume.enter_bricks"));
+    println!("{}", t!("volume.brick_format", done = t!("common.done_word"), exit = t!("common.exit_word")));
+    match chosen {
+        Some(layout) if layout.set_size() > 1 => println!("{}", t!("volume.set_hint", size = layout.set_size())),
+        Some(_) => {}
+        None => println!("{}", t!("volume.disperse_hint")),
+    }
 
     let mut bricks: Vec<String> = Vec::new();
 
     loop {
         let input: String = Input::with_theme(&theme)
             .with_prompt("Brick")
-            .interact_text()
-            .unwrap();
-
-        let trimmed = input.trim().to_lowercase();
+            .interact_text()?;
 
-        if trimmed == "salir" {
-            println!("❎ Operación cancelada.");
-            return;
+        if is_exit(&input) {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
         }
 
-        if trimmed == "fin" {
-            break;
+        if is_done(&input) {
+            // Con conjuntos incompletos se siguen pidiendo bricks en lugar de dejar fallar a gluster.
+            let check = match chosen {
+                Some(layout) => layout.check_brick_count(bricks.len()),
+                None if VolumeLayout::disperse_options(bricks.len()).is_empty() => {
+                    Err(Error::Message(t!("volume.no_disperse_options", count = bricks.len())))
+                }
+                None => Ok(()),
+            };
+            match check {
+                Ok(()) => break,
+                Err(e) => {
+                    println!("⚠️ {}", e);
+                    continue;
+                }
+            }
         }
 
-        if input.contains(':') && input.contains('/') {
+        if is_valid_brick(&input) {
             bricks.push(input);
         } else {
-            println!("⚠️ Formato inválido. Usa: vm1:/gluster/brick");
+            println!("{}", t!("volume.invalid_format"));
         }
     }
 
-    if bricks.is_empty() {
-        eprintln!("❌ Se necesita al menos 1 brick para crear el volumen.");
-        return;
+    let layout = match chosen {
+        Some(layout) => layout,
+        None => choose_disperse(&theme, bricks.len())?,
+    };
+
+    let Some((bricks, force)) = confirm_placement(backend, &theme, &layout, bricks)? else {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    };
+
+    print_sets(&layout, &bricks);
+    print_capacity(&layout, bricks.len());
+
+    if !Confirm::with_theme(&theme)
+        .with_prompt(t!("volume.create_confirm", name = vol_name.trim(), layout = layout))
+        .default(true)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
+    {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
-    let mut cmd: Vec<String> = vec![
-        "gluster".to_string(),
-        "volume".to_string(),
-        "create".to_string(),
-        vol_name.clone(),
+    retry_with_force(&theme, force, |force| create(backend, vol_name.trim(), &layout, &bricks, true, force))?;
+    Ok(())
+}
+
+/// Disposiciones que ofrece el asistente de creación.
+const WIZARD_LAYOUTS: [VolumeLayout; 4] = [
+    VolumeLayout::Distribute,
+    VolumeLayout::Replicate { replica: 2, arbiter: 0 },
+    VolumeLayout::Replicate { replica: 3, arbiter: 0 },
+    VolumeLayout::Replicate { replica: 3, arbiter: 1 },
+];
+
+fn choose_layout(theme: &ColorfulTheme) -> Result<Option<VolumeLayout>> {
+    let items = [
+        t!("volume.layout_distribute"),
+        t!("volume.layout_replica2"),
+        t!("volume.layout_replica3"),
+        t!("volume.layout_arbiter"),
+        t!("volume.layout_disperse"),
     ];
-    cmd.extend(bricks.iter().cloned());
-    cmd.push("force".to_string());
-
-    println!("🚀 Ejecutando comando:");
-    println!("sudo {}", cmd.join(" "));
-
-    let status = Command::new("sudo")
-        .arg("gluster")
-        .args(&cmd[1..])
-        .status()
-        .expect("Error al ejecutar el comando");
-
-    if status.success() {
-        println!("✅ Volumen creado exitosamente.");
-        let start_status = Command::new("sudo")
-            .args(["gluster", "volume", "start", &vol_name])
-            .status();
-
-        if let Ok(st) = start_status {
-            if st.success() {
-                println!("✅ Volumen iniciado correctamente.");
-            } else {
-                println!("⚠️ Volumen creado, pero no pudo iniciarse.");
-            }
+
+    let selection = Select::with_theme(theme)
+        .with_prompt(t!("volume.layout_prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    let Some(&layout) = WIZARD_LAYOUTS.get(selection) else {
+        return Ok(None);
+    };
+    if layout == (VolumeLayout::Replicate { replica: 2, arbiter: 0 }) {
+        println!("{}", t!("volume.replica2_warning"));
+    }
+    Ok(Some(layout))
+}
+
+/// Elige entre las combinaciones de dispersión válidas para `count` bricks.
+fn choose_disperse(theme: &ColorfulTheme, count: usize) -> Result<VolumeLayout> {
+    let options = VolumeLayout::disperse_options(count);
+    let items: Vec<String> = options.iter().map(|layout| describe_capacity(layout, count)).collect();
+
+    let selection = Select::with_theme(theme)
+        .with_prompt(t!("volume.disperse_prompt", count = count))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+
+    Ok(options[selection])
+}
+
+/// `dispersión 6 redundancia 2 ×2: útil 8 de 12 bricks, tolera 2 fallos por conjunto`.
+fn describe_capacity(layout: &VolumeLayout, count: usize) -> String {
+    let mut text = t!(
+        "volume.capacity",
+        layout = layout,
+        sets = count / layout.set_size(),
+        usable = layout.usable_bricks(count),
+        total = count,
+        tolerance = t!("volume.tolerance", count = layout.fault_tolerance())
+    );
+
+    if !layout.is_optimal() {
+        text.push_str(&t!("volume.not_optimal"));
+    }
+    text
+}
+
+/// Capacidad útil y tolerancia a fallos de la disposición elegida.
+pub fn print_capacity(layout: &VolumeLayout, count: usize) {
+    println!("📐 {}", describe_capacity(layout, count));
+}
+
+/// Muestra qué bricks forman cada conjunto de réplica antes de crear el volumen.
+pub fn print_sets(layout: &VolumeLayout, bricks: &[String]) {
+    if layout.set_size() == 1 {
+        println!("{}", t!("volume.distribute_bricks", count = bricks.len()));
+        for brick in bricks {
+            println!("   🧱 {}", brick);
         }
-    } else {
-        println!("❌ Error al crear el volumen.");
+        return;
+    }
+
+    println!("{}", t!("volume.sets_title", layout = layout));
+    for (i, set) in layout.sets(bricks).iter().enumerate() {
+        let members: Vec<String> = set
+            .iter()
+            .enumerate()
+            .map(|(position, brick)| {
+                if layout.is_arbiter(position) {
+                    format!("{}{}", brick, t!("volinfo.arbiter"))
+                } else {
+                    brick.clone()
+                }
+            })
+            .collect();
+        println!("{}", t!("volume.set_line", number = i + 1, bricks = members.join(", ")));
+    }
+}
+
+/// Crea el volumen con los bricks indicados y, si `start` es verdadero, lo inicia.
+/// `force` solo se pasa a gluster cuando el operador lo pide explícitamente.
+pub fn create(
+    backend: &dyn GlusterBackend,
+    vol_name: &str,
+    layout: &VolumeLayout,
+    bricks: &[String],
+    start: bool,
+    force: bool,
+) -> Result<()> {
+    if bricks.is_empty() {
+        return Err(t!("volume.needs_brick").into());
+    }
+
+    if let Some(invalid) = bricks.iter().find(|b| !is_valid_brick(b)) {
+        return Err(Error::Message(t!("volume.invalid_brick", brick = invalid)));
+    }
+
+    layout.check_brick_count(bricks.len()).map_err(|e| e.context("❌"))?;
+
+    let layout_args = layout.create_args();
+
+    let mut args: Vec<&str> = vec!["volume", "create", vol_name];
+    args.extend(layout_args.iter().map(String::as_str));
+    args.extend(bricks.iter().map(String::as_str));
+    if force {
+        args.push("force");
     }
+
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| with_force_hint(e, force).context(&t!("volume.create_failed")))?;
+    println!("{}", t!("volume.created"));
+
+    if start {
+        match backend.sudo_gluster(&["volume", "start", vol_name]) {
+            Ok(_) => println!("{}", t!("volume.started")),
+            Err(e) => return Err(e.context(&t!("volume.created_not_started"))),
+        }
+    }
+
+    Ok(())
 }
 
-pub fn add_bricks() {
+pub fn add_bricks(backend: &dyn GlusterBackend) -> Result<()> {
     let theme = ColorfulTheme::default();
 
-    let mut volumes = list_volumes();
+    let mut volumes = get_volume_names(backend)?;
     if volumes.is_empty() {
-        println!("⚠️ No hay volúmenes para agregar bricks.");
-        return;
+        println!("{}", t!("volume.none_to_add"));
+        return Ok(());
     }
 
-    volumes.insert(0, "Salir".to_string());
+    volumes.insert(0, t!("menu.exit"));
 
     let vol_idx = Select::with_theme(&theme)
-        .with_prompt("Selecciona el volumen al que quieres agregar bricks")
+        .with_prompt(t!("volume.add_prompt"))
         .items(&volumes)
         .default(0)
-        .interact()
-        .unwrap();
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
     if vol_idx == 0 {
-        println!("❎ Operación cancelada.");
-        return;
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
     let selected_vol = &volumes[vol_idx];
 
-    let peers = list_peers();
+    let peers = pool::peers(backend)?;
     if peers.is_empty() {
-        println!("⚠️ No hay peers conectados. No puedes agregar bricks.");
-        return;
+        println!("{}", t!("volume.no_peers"));
+        return Ok(());
     }
 
-    println!("Peers disponibles:");
-    for p in &peers {
-        println!("- {}", p);
+    println!("{}", t!("volume.available_peers"));
+    pool::print_table(&peers);
+    if peers.iter().any(|p| !p.is_healthy()) {
+        println!("{}", t!("volume.unhealthy_peers"));
     }
 
-    println!("🧱 Ingresa los bricks para agregar al volumen.");
-    println!("Formato: vm1:/gluster/brick1 (uno por línea). Escribe 'fin' para terminar o 'salir' para cancelar toda la operación.");
+    println!("{}", t!("volume.enter_bricks_to_add"));
+    println!("{}", t!("volume.brick_format", done = t!("common.done_word"), exit = t!("common.exit_word")));
 
     let mut bricks_to_add: Vec<String> = Vec::new();
 
     loop {
         let input: String = Input::with_theme(&theme)
             .with_prompt("Brick")
-            .interact_text()
-            .unwrap();
-
-        let trimmed = input.trim().to_lowercase();
+            .interact_text()?;
 
-        if trimmed == "salir" {
+        if is_exit(&input) {
             if bricks_to_add.is_empty() {
-                println!("❎ Operación cancelada.");
-                return;
+                println!("{}", t!("common.cancelled"));
+                return Ok(());
             } else {
                 let confirm = dialoguer::Confirm::with_theme(&theme)
-                    .with_prompt("Ya has ingresado bricks válidos. ¿Seguro que quieres salir sin aplicar los cambios?")
+                    .with_prompt(t!("volume.discard_bricks_confirm"))
                     .default(false)
-                    .interact()
-                    .unwrap();
+                    .interact_opt()?
+                    .ok_or(Error::Cancelled)?;
                 if confirm {
-                    println!("❎ Operación cancelada.");
-                    return;
+                    println!("{}", t!("common.cancelled"));
+                    return Ok(());
                 } else {
                     continue;
                 }
             }
         }
 
-        if trimmed == "fin" {
+        if is_done(&input) {
             break;
         }
 
-        if input.contains(':') && input.contains('/') {
+        if is_valid_brick(&input) {
             bricks_to_add.push(input);
         } else {
-            println!("⚠️ Formato inválido. Usa: vm1:/gluster/brick");
+            println!("{}", t!("volume.invalid_format"));
         }
 
     }
 
-    if bricks_to_add.is_empty() {
-        println!("❌ No agregaste bricks válidos.");
-        return;
+    let layout = VolumeLayout::from_info(&volinfo::fetch_one(backend, selected_vol)?);
+    let Some((bricks_to_add, force)) = confirm_placement(backend, &theme, &layout, bricks_to_add)? else {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    };
+
+    if !retry_with_force(&theme, force, |force| add(backend, selected_vol, &bricks_to_add, force))? {
+        return Ok(());
     }
+    crate::rebalance::offer_after_add(backend, &theme, selected_vol)
+}
 
-    let mut cmd = vec![
-        "gluster".to_string(),
-        "volume".to_string(),
-        "add-brick".to_string(),
-        selected_vol.clone(),
-    ];
-    cmd.extend(bricks_to_add);
-    cmd.push("force".to_string());
+/// Agrega bricks a un volumen existente.
+pub fn add(backend: &dyn GlusterBackend, vol_name: &str, bricks: &[String], force: bool) -> Result<()> {
+    if bricks.is_empty() {
+        return Err(t!("volume.no_valid_bricks").into());
+    }
 
-    println!("🚀 Ejecutando comando:");
-    println!("sudo {}", cmd.join(" "));
+    if let Some(invalid) = bricks.iter().find(|b| !is_valid_brick(b)) {
+        return Err(Error::Message(t!("volume.invalid_brick", brick = invalid)));
+    }
 
-    let status = Command::new("sudo")
-        .arg("gluster")
-        .args(&cmd[1..])
-        .status()
-        .expect("Error al ejecutar el comando");
+    let mut args: Vec<&str> = vec!["volume", "add-brick", vol_name];
+    args.extend(bricks.iter().map(String::as_str));
+    if force {
+        args.push("force");
+    }
 
-    if status.success() {
-        println!("✅ Bricks agregados exitosamente.");
-    } else {
-        println!("❌ Error al agregar bricks.");
+    backend
+        .sudo_gluster(&args)
+        .map_err(|e| with_force_hint(e, force).context(&t!("volume.add_failed")))?;
+    println!("{}", t!("volume.bricks_added"));
+    Ok(())
+}
+
+/// Gluster pide `force` para bricks en la partición raíz o conjuntos en un mismo nodo:
+/// se explica cómo forzarlo en lugar de hacerlo siempre.
+fn with_force_hint(error: Error, forced: bool) -> Error {
+    match error {
+        Error::CommandFailed { command, stderr } if !forced && needs_force(&stderr) => Error::CommandFailed {
+            command,
+            stderr: format!("{}\n{}", stderr.trim(), t!("volume.force_hint")),
+        },
+        other => other,
+    }
+}
+
+/// Gluster acaba los rechazos que se pueden saltar con "Use 'force' at the end of the command...".
+fn needs_force(stderr: &str) -> bool {
+    stderr.contains("force")
+}
+
+/// En los asistentes: si gluster rechaza la operación sin `force`, muestra su motivo y ofrece
+/// repetirla forzada. Devuelve `false` si el operador prefiere dejarlo.
+fn retry_with_force(theme: &ColorfulTheme, forced: bool, run: impl Fn(bool) -> Result<()>) -> Result<bool> {
+    match run(forced) {
+        Err(Error::CommandFailed { stderr, .. }) if !forced && needs_force(&stderr) => {
+            println!("{}", stderr.trim());
+            if !Confirm::with_theme(theme)
+                .with_prompt(t!("volume.force_retry_confirm"))
+                .default(false)
+                .interact_opt()?
+                .ok_or(Error::Cancelled)?
+            {
+                println!("{}", t!("common.cancelled"));
+                return Ok(false);
+            }
+            run(true)?;
+            Ok(true)
+        }
+        other => other.map(|()| true),
+    }
+}
+
+/// Propone un orden que reparta cada conjunto entre nodos distintos y decide si hace falta `force`.
+/// Devuelve `None` si el operador prefiere no continuar.
+fn confirm_placement(
+    backend: &dyn GlusterBackend,
+    theme: &ColorfulTheme,
+    layout: &VolumeLayout,
+    bricks: Vec<String>,
+) -> Result<Option<(Vec<String>, bool)>> {
+    let peers = pool::peers(backend).unwrap_or_default();
+    for warning in placement::check_hosts(&bricks, &peers, &backend.local_hostname()) {
+        println!("⚠️ {}", warning);
+    }
+
+    let mut bricks = bricks;
+    let proposal = placement::arrange(layout, &bricks);
+    if proposal.bricks != bricks && proposal.is_spread() {
+        println!("{}", t!("volume.placement_proposal"));
+        print_sets(layout, &proposal.bricks);
+        if Confirm::with_theme(theme)
+            .with_prompt(t!("volume.placement_confirm"))
+            .default(true)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?
+        {
+            bricks = proposal.bricks;
+        }
+    }
+
+    let warnings = placement::check_spread(layout, &bricks);
+    if warnings.is_empty() {
+        return Ok(Some((bricks, false)));
+    }
+
+    for warning in &warnings {
+        println!("⚠️ {}", warning);
     }
+    let force = Confirm::with_theme(theme)
+        .with_prompt(t!("volume.force_confirm"))
+        .default(false)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    Ok(force.then_some((bricks, true)))
 }
 
-fn check_force_migration(volume: &str) -> Result<bool, String> {
+pub fn check_force_migration(backend: &dyn GlusterBackend, volume: &str) -> Result<bool> {
     let args = ["volume", "get", volume, "cluster.force-migration"];
-    match run_command("gluster", &args) {
+    match backend.gluster(&args) {
         Ok(output) => {
+            // Formato: "cluster.force-migration                 off"
             for line in output.lines() {
-                if line.contains("cluster.force-migration") {
-                    if line.contains("on") {
-                        return Ok(true);
-                    } else if line.contains("off") {
-                        return Ok(false);
+                let mut fields = line.split_whitespace();
+                if fields.next() == Some("cluster.force-migration") {
+                    match fields.next() {
+                        Some("on") => return Ok(true),
+                        Some("off") => return Ok(false),
+                        _ => {}
                     }
                 }
             }
-            Err("No se pudo determinar el estado de cluster.force-migration.".into())
+            Err(t!("volume.force_migration_unknown").into())
         }
-        Err(e) => Err(format!("Error al obtener cluster.force-migration: {}", e)),
+        Err(e) => Err(e.context(&t!("volume.force_migration_failed"))),
     }
 }
 
-pub fn remove_bricks() {
+pub fn remove_bricks(backend: &dyn GlusterBackend) -> Result<()> {
     let theme = ColorfulTheme::default();
 
-    let mut volumes = list_volumes();
+    if crate::removal::offer_resume(backend, &theme)? {
+        return Ok(());
+    }
+
+    let mut volumes = get_volume_names(backend)?;
     if volumes.is_empty() {
-        println!("⚠️ No hay volúmenes para eliminar bricks.");
-        return;
+        println!("{}", t!("volume.none_to_remove"));
+        return Ok(());
     }
 
-    volumes.insert(0, "Salir".to_string());
+    volumes.insert(0, t!("menu.exit"));
 
     let vol_idx = Select::with_theme(&theme)
-        .with_prompt("Selecciona el volumen del que quieres eliminar bricks")
+        .with_prompt(t!("volume.remove_prompt"))
         .items(&volumes)
         .default(0)
-        .interact()
-        .unwrap();
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
     if vol_idx == 0 {
-        println!("❎ Operación cancelada.");
-        return;
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
 
     let selected_vol = &volumes[vol_idx];
 
     // ⚠️ Verificación de configuración peligrosa
-    match check_force_migration(selected_vol) {
+    match check_force_migration(backend, selected_vol) {
         Ok(true) => {
-            println!("⚠️ Advertencia: cluster.force-migration está habilitado (ON) en el volumen '{}'. Esto puede causar corrupción de datos al eliminar bricks.", selected_vol);
+            println!("{}", t!("volume.force_migration_on", name = selected_vol));
             if !Confirm::with_theme(&theme)
-                .with_prompt("¿Quieres continuar con la eliminación del brick igual? (no recomendado)")
+                .with_prompt(t!("volume.force_migration_confirm"))
                 .default(false)
-                .interact()
-                .unwrap()
+                .interact_opt()?
+                .ok_or(Error::Cancelled)?
             {
-                println!("🛑 Operación cancelada por seguridad.");
-                return;
+                println!("{}", t!("volume.cancelled_for_safety"));
+                return Ok(());
             }
         }
         Ok(false) => {} // nada
         Err(e) => {
-            println!("⚠️ No se pudo verificar cluster.force-migration: {}", e);
-            println!("Continuando con precaución...");
+            println!("{}", t!("volume.force_migration_unchecked", error = e));
+            println!("{}", t!("volume.continuing"));
         }
     }
 
-    let mut bricks = list_bricks(selected_vol);
+    let info = volinfo::fetch_one(backend, selected_vol).map_err(|e| e.context(&t!("volume.list_bricks_failed")))?;
+    let layout = VolumeLayout::from_info(&info);
+    let bricks = info.brick_names();
     if bricks.is_empty() {
-        println!("⚠️ Este volumen no tiene bricks listados o no se pudieron obtener.");
-        return;
+        println!("{}", t!("volume.no_bricks"));
+        return Ok(());
     }
 
-    bricks.insert(0, "❌ Salir".to_string());
+    // Marcamos los bricks caídos según `volume status`
+    let status = volstatus::fetch(backend, Some(selected_vol))
+        .ok()
+        .and_then(|mut v| v.pop());
+    let label = |b: &String| match status.as_ref().and_then(|s| s.brick(b)) {
+        Some(brick) if !brick.online => t!("volume.brick_offline", brick = b),
+        _ => b.clone(),
+    };
 
-    let brick_idx = Select::with_theme(&theme)
-        .with_prompt("Selecciona el brick que quieres eliminar")
-        .items(&bricks)
-        .default(0)
-        .interact()
-        .unwrap();
+    let reduce = matches!(layout, VolumeLayout::Replicate { .. }) && {
+        let modes = [t!("volume.remove_mode_sets"), t!("volume.remove_mode_replica")];
+        Select::with_theme(&theme)
+            .with_prompt(t!("volume.remove_mode_prompt", name = selected_vol, layout = layout))
+            .items(&modes)
+            .default(0)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?
+            == 1
+    };
 
-    if brick_idx == 0 {
-        println!("❎ Operación cancelada.");
-        return;
+    let (selected, replica) = if reduce {
+        let Some((replica, selected)) = choose_replica_reduction(&theme, &layout, &bricks, &label)? else {
+            println!("{}", t!("common.cancelled"));
+            return Ok(());
+        };
+        (selected, Some(replica))
+    } else {
+        (choose_sets(&theme, &layout, &bricks, &label)?, None)
+    };
+    if selected.is_empty() {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
     }
+    crate::removal::check_selection(&layout, &bricks, &selected, replica)?;
 
-    let selected_brick = &bricks[brick_idx];
-
+    let prompt = match replica {
+        Some(replica) => t!("volume.reduce_replica_confirm", name = selected_vol, replica = replica, bricks = selected.join(", ")),
+        None => t!("volume.remove_bricks_confirm", name = selected_vol, bricks = selected.join(", ")),
+    };
     if !Confirm::with_theme(&theme)
-        .with_prompt(format!(
-            "⚠️ ¿Seguro que deseas eliminar el brick '{}' del volumen '{}'? Esto puede afectar los datos.",
-            selected_brick, selected_vol
-        ))
+        .with_prompt(prompt)
         .default(false)
-        .interact()
-        .unwrap()
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?
     {
-        println!("🛑 Operación cancelada.");
-        return;
+        println!("{}", t!("volume.stopped_by_user"));
+        return Ok(());
     }
 
-    println!("🚀 Iniciando eliminación del brick...");
-    let start_status = Command::new("sudo")
-        .args(&[
-            "gluster",
-            "volume",
-            "remove-brick",
-            selected_vol,
-            selected_brick,
-            "start",
-        ])
-        .status();
-
-    match start_status {
-        Ok(st) if st.success() => {
-            println!("✅ Proceso de eliminación iniciado.");
-            println!("ℹ️ Recuerda ejecutar el comando de confirmación:");
-            println!("   sudo gluster volume remove-brick {} {} commit", selected_vol, selected_brick);
-        }
-        Ok(_) => {
-            println!("❌ Falló iniciar la eliminación del brick.");
-        }
-        Err(e) => {
-            println!("❌ Error al ejecutar el comando: {}", e);
+    match replica {
+        Some(replica) => crate::removal::reduce_replica(backend, selected_vol, replica, &selected),
+        None => {
+            crate::removal::start(backend, selected_vol, &selected)?;
+            crate::removal::manage_pending(backend, &theme, selected_vol)
         }
     }
 }
 
-fn get_volume_names() -> Vec<String> {
-    let output = match Command::new("gluster").args(["volume", "list"]).output() {
-        Ok(out) => out,
-        Err(_) => return vec![], // Devuelve lista vacía si falla
+/// Bricks sueltos en un volumen distribuido; conjuntos completos en uno replicado o disperso.
+fn choose_sets(
+    theme: &ColorfulTheme,
+    layout: &VolumeLayout,
+    bricks: &[String],
+    label: &dyn Fn(&String) -> String,
+) -> Result<Vec<String>> {
+    let sets = layout.sets(bricks);
+    let (prompt, items): (String, Vec<String>) = if layout.set_size() == 1 {
+        (t!("volume.remove_bricks_prompt"), bricks.iter().map(label).collect())
+    } else {
+        let items = sets
+            .iter()
+            .enumerate()
+            .map(|(i, set)| {
+                let members: Vec<String> = set.iter().map(label).collect();
+                t!("volume.set_line", number = i + 1, bricks = members.join(", "))
+            })
+            .collect();
+        (t!("volume.remove_sets_prompt", layout = layout), items)
+    };
+    let chosen = MultiSelect::with_theme(theme)
+        .with_prompt(prompt)
+        .items(&items)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
+    Ok(chosen.into_iter().flat_map(|i| sets[i].iter().cloned()).collect())
+}
+
+/// Nueva réplica y bricks a quitar de cada conjunto. Con árbitro solo cabe quitar los árbitros.
+fn choose_replica_reduction(
+    theme: &ColorfulTheme,
+    layout: &VolumeLayout,
+    bricks: &[String],
+    label: &dyn Fn(&String) -> String,
+) -> Result<Option<(u32, Vec<String>)>> {
+    let VolumeLayout::Replicate { replica: current, arbiter } = *layout else {
+        return Ok(None);
     };
+    let sets = layout.sets(bricks);
+    if arbiter > 0 {
+        let arbiters: Vec<String> = sets.iter().filter_map(|set| set.last().cloned()).collect();
+        println!("{}", t!("volume.arbiters_removed", bricks = arbiters.join(", ")));
+        return Ok(Some((2, arbiters)));
+    }
+
+    let targets: Vec<u32> = (1..current).rev().collect();
+    let items: Vec<String> = targets.iter().map(|r| t!("volume.target_replica_item", replica = r)).collect();
+    let Some(index) = Select::with_theme(theme)
+        .with_prompt(t!("volume.target_replica_prompt", current = current))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    let replica = targets[index];
+    let per_set = (current - replica) as usize;
+
+    let mut selected = vec![];
+    for (i, set) in sets.iter().enumerate() {
+        let items: Vec<String> = set.iter().map(label).collect();
+        // Propone los bricks caídos, que son los candidatos naturales a salir.
+        let defaults: Vec<bool> = set.iter().zip(&items).map(|(b, l)| l != b).collect();
+        let chosen = MultiSelect::with_theme(theme)
+            .with_prompt(t!("volume.replica_set_prompt", number = i + 1, count = per_set))
+            .items(&items)
+            .defaults(&defaults)
+            .interact_opt()?
+            .ok_or(Error::Cancelled)?;
+        selected.extend(chosen.into_iter().map(|p| set[p].clone()));
+    }
+    Ok(Some((replica, selected)))
+}
+
+pub fn get_volume_names(backend: &dyn GlusterBackend) -> Result<Vec<String>> {
+    let volumes = volinfo::fetch(backend, None).map_err(|e| e.context(&t!("volume.list_failed")))?;
+    Ok(volumes.into_iter().map(|v| v.name).collect())
+}
+
+/// Información tipada de todos los volúmenes o de uno concreto.
+pub fn info(backend: &dyn GlusterBackend, volume: Option<&str>) -> Result<Vec<VolumeInfo>> {
+    volinfo::fetch(backend, volume)
+}
+
+pub fn start(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    backend.sudo_gluster(&["volume", "start", name]).map_err(|e| e.context(&t!("volume.start_failed")))?;
+    println!("{}", t!("volume.started"));
+    Ok(())
+}
+
+pub fn stop(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    backend.sudo_gluster(&["volume", "stop", name, "force"]).map_err(|e| e.context(&t!("volume.stop_failed")))?;
+    println!("{}", t!("volume.stopped"));
+    Ok(())
+}
+
+pub fn delete(backend: &dyn GlusterBackend, name: &str) -> Result<()> {
+    backend.sudo_gluster(&["volume", "delete", name])
+        .map_err(|e| e.context(&t!("volume.delete_failed")))?;
+    println!("{}", t!("volume.deleted"));
+    Ok(())
+}
+
+/// Cambia una opción del volumen (`gluster volume set`).
+pub fn set_option(backend: &dyn GlusterBackend, name: &str, key: &str, value: &str) -> Result<()> {
+    backend
+        .sudo_gluster(&["volume", "set", name, key, value])
+        .map_err(|e| e.context(&t!("volume.set_failed", key = key)))?;
+    println!("✅ {} = {}", key, value);
+    Ok(())
+}
+
+/// Pide elegir un volumen existente; `None` si no hay ninguno o el operador sale.
+pub fn choose_volume(backend: &dyn GlusterBackend, theme: &ColorfulTheme, prompt: &str) -> Result<Option<String>> {
+    let mut volumes = get_volume_names(backend)?;
+    if volumes.is_empty() {
+        println!("{}", t!("volume.none_available"));
+        return Ok(None);
+    }
+
+    volumes.insert(0, t!("menu.exit"));
+    let index = Select::with_theme(theme)
+        .with_prompt(prompt)
+        .items(&volumes)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    stdout
-        .lines()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect()
+    if index == 0 {
+        println!("{}", t!("common.cancelled"));
+        return Ok(None);
+    }
+    Ok(Some(volumes.swap_remove(index)))
 }
 
-pub fn manage_volumes() {
+pub fn manage_volumes(backend: &dyn GlusterBackend) -> Result<()> {
     let theme = ColorfulTheme::default();
 
     loop {
         let options = vec![
-            "📋 Listar volúmenes (con detalles)",
-            "▶️ Iniciar volumen",
-            "⏹️ Detener volumen",
-            "🗑️ Eliminar volumen",
-            "➕ Agregar bricks a volumen",
-            "➖ Eliminar bricks de volumen",
-            "↩️ Volver al menú",
+            t!("volume.menu_list"),
+            t!("volume.menu_start"),
+            t!("volume.menu_stop"),
+            t!("volume.menu_delete"),
+            t!("volume.menu_add_bricks"),
+            t!("volume.menu_remove_bricks"),
+            t!("volume.menu_options"),
+            t!("volume.menu_profiles"),
+            t!("volume.menu_quotas"),
+            t!("volume.menu_snapshots"),
+            t!("volume.menu_schedule"),
+            t!("volume.menu_heal"),
+            t!("volume.menu_rebalance"),
+            t!("volume.menu_replace"),
+            t!("volume.menu_back"),
         ];
 
-        let selection = Select::with_theme(&theme)
-            .with_prompt("Gestión de volúmenes")
+        // Esc vuelve al menú principal
+        let Some(selection) = Select::with_theme(&theme)
+            .with_prompt(t!("volume.menu_title"))
             .items(&options)
             .default(0)
-            .interact()
-            .unwrap();
-
-        match selection {
-            0 => {
-                match Command::new("gluster").args(["volume", "info"]).output() {
-                    Ok(output) => println!("{}", String::from_utf8_lossy(&output.stdout)),
-                    Err(e) => eprintln!("⚠️ Error listando volúmenes: {}", e),
-                }
-            }
-            1 | 2 | 3 => {
-                let mut volumes = get_volume_names();
-                if volumes.is_empty() {
-                    println!("⚠️ No hay volúmenes disponibles.");
-                    continue;
-                }
+            .interact_opt()?
+        else {
+            break;
+        };
+
+        let result = match selection {
+            0 => list_with_details(backend),
+            1..=3 => volume_action(backend, &theme, selection),
+            4 => add_bricks(backend),
+            5 => remove_bricks(backend),
+            6 => crate::options::manage_options(backend),
+            7 => crate::profiles::manage_profiles(backend),
+            8 => crate::quota::manage_quotas(backend),
+            9 => crate::snapshot::manage_snapshots(backend),
+            10 => crate::schedule::manage_schedule(backend),
+            11 => crate::heal::manage_heal(backend),
+            12 => crate::rebalance::manage_rebalance(backend),
+            13 => crate::replace::manage_replace(backend),
+            _ => break,
+        };
+        error::report(result);
+
+        print!("\n{}", t!("common.press_enter"));
+        io::stdout().flush()?;
+        let _ = io::stdin().read_line(&mut String::new());
+    }
+    Ok(())
+}
+
+fn list_with_details(backend: &dyn GlusterBackend) -> Result<()> {
+    let volumes = info(backend, None).map_err(|e| e.context(&t!("volume.list_failed")))?;
+    if volumes.is_empty() {
+        println!("{}", t!("volume.none_available"));
+    }
+    for volume in volumes {
+        println!("{}", volume);
+    }
+    Ok(())
+}
 
-                // Insertamos la opción salir primero
-                volumes.insert(0, "Salir".to_string());
+/// Iniciar (1), detener (2) o eliminar (3) un volumen elegido de la lista.
+fn volume_action(backend: &dyn GlusterBackend, theme: &ColorfulTheme, selection: usize) -> Result<()> {
+    let mut volumes = get_volume_names(backend)?;
+    if volumes.is_empty() {
+        println!("{}", t!("volume.none_available"));
+        return Ok(());
+    }
 
-                let vol_index = Select::with_theme(&theme)
-                    .with_prompt("Selecciona el volumen")
-                    .items(&volumes)
-                    .default(0)
-                    .interact()
-                    .unwrap();
+    // Insertamos la opción salir primero
+    volumes.insert(0, t!("menu.exit"));
 
-                if vol_index == 0 {
-                    println!("❎ Operación cancelada.");
-                    continue; // Regresa al menú principal
-                }
+    let vol_index = Select::with_theme(theme)
+        .with_prompt(t!("volume.select_prompt"))
+        .items(&volumes)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Cancelled)?;
 
-                let name = &volumes[vol_index];
-
-                match selection {
-                    1 => {
-                        match Command::new("sudo")
-                            .args(["gluster", "volume", "start", name])
-                            .status()
-                        {
-                            Ok(st) if st.success() => println!("✅ Volumen iniciado."),
-                            _ => println!("❌ Falló iniciar volumen."),
-                        }
-                    }
-                    2 => {
-                        match Command::new("sudo")
-                            .args(["gluster", "volume", "stop", name, "force"])
-                            .status()
-                        {
-                            Ok(st) if st.success() => println!("✅ Volumen detenido."),
-                            _ => println!("❌ Falló detener volumen."),
-                        }
-                    }
-                    3 => {
-                        if Confirm::with_theme(&theme)
-                            .with_prompt(format!("⚠️ ¿Eliminar el volumen '{}'?", name))
-                            .default(false)
-                            .interact()
-                            .unwrap()
-                        {
-                            match Command::new("sudo")
-                                .args(["gluster", "volume", "delete", name])
-                                .status()
-                            {
-                                Ok(st) if st.success() => println!("✅ Volumen eliminado."),
-                                _ => println!("❌ Falló eliminar volumen."),
-                            }
-                        } else {
-                            println!("🛑 Eliminación cancelada.");
-                        }
-                    }
-                    _ => {}
-                }
+    if vol_index == 0 {
+        println!("{}", t!("common.cancelled"));
+        return Ok(());
+    }
+
+    let name = &volumes[vol_index];
+
+    match selection {
+        1 => start(backend, name),
+        2 => stop(backend, name),
+        _ => {
+            if Confirm::with_theme(theme)
+                .with_prompt(t!("volume.delete_confirm", name = name))
+                .default(false)
+                .interact_opt()?
+                .ok_or(Error::Cancelled)?
+            {
+                delete(backend, name)
+            } else {
+                println!("{}", t!("volume.delete_cancelled"));
+                Ok(())
             }
-            4 => add_bricks(),
-            5 => remove_bricks(),
-            _ => break,
         }
+    }
+}
 
-        print!("\nPresiona Enter para continuar...");
-        io::stdout().flush().unwrap();
-        let _ = io::stdin().read_line(&mut String::new());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeBackend, VolumeState};
+
+    fn bricks(list: &[&str]) -> Vec<String> {
+        list.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn create_builds_and_starts_volume() {
+        let fake = FakeBackend::new().with_peer("vm2", true);
+
+        create(&fake, "datos", &VolumeLayout::Distribute, &bricks(&["vm1:/gluster/a", "vm2:/gluster/a"]), true, false).unwrap();
+
+        let volume = fake.volume("datos").unwrap();
+        assert_eq!(volume.state, VolumeState::Started);
+        assert_eq!(volume.bricks, bricks(&["vm1:/gluster/a", "vm2:/gluster/a"]));
+        assert_eq!(
+            fake.commands(),
+            vec![
+                "gluster --mode=script volume create datos vm1:/gluster/a vm2:/gluster/a",
+                "gluster --mode=script volume start datos",
+            ]
+        );
+    }
+
+    #[test]
+    fn create_replica_with_arbiter() {
+        let fake = FakeBackend::new().with_peer("vm2", true).with_peer("vm3", true);
+        let layout = VolumeLayout::Replicate { replica: 3, arbiter: 1 };
+
+        create(&fake, "datos", &layout, &bricks(&["vm1:/gluster/a", "vm2:/gluster/a", "vm3:/gluster/arb"]), false, false).unwrap();
+
+        let info = volinfo::fetch_one(&fake, "datos").unwrap();
+        assert_eq!(VolumeLayout::from_info(&info), layout);
+        assert!(info.bricks[2].is_arbiter);
+        assert_eq!(
+            fake.commands(),
+            vec!["gluster --mode=script volume create datos replica 3 arbiter 1 vm1:/gluster/a vm2:/gluster/a vm3:/gluster/arb"]
+        );
+    }
+
+    #[test]
+    fn create_distributed_disperse() {
+        let fake = FakeBackend::new().with_peer("vm2", true).with_peer("vm3", true);
+        let layout = VolumeLayout::Disperse { disperse: 3, redundancy: 1 };
+        let all: Vec<String> = ["a", "b"]
+            .iter()
+            .flat_map(|b| (1..=3).map(move |i| format!("vm{}:/gluster/{}", i, b)))
+            .collect();
+
+        create(&fake, "archivo", &layout, &all, false, false).unwrap();
+
+        let info = volinfo::fetch_one(&fake, "archivo").unwrap();
+        assert_eq!(VolumeLayout::from_info(&info), layout);
+        assert_eq!(info.vol_type, "Distributed-Disperse");
+        assert!(create(&fake, "otro", &VolumeLayout::Disperse { disperse: 3, redundancy: 2 }, &all, false, false).is_err());
+        assert_eq!(fake.commands().len(), 1);
+    }
+
+    #[test]
+    fn force_is_only_passed_when_requested() {
+        let fake = FakeBackend::new().with_peer("vm2", true);
+        let layout = VolumeLayout::Replicate { replica: 2, arbiter: 0 };
+        let same_host = bricks(&["vm1:/gluster/a", "vm1:/gluster/b", "vm2:/gluster/a", "vm2:/gluster/b"]);
+
+        let err = create(&fake, "datos", &layout, &same_host, false, false).unwrap_err();
+        assert!(err.to_string().contains(&t!("volume.force_hint")));
+        assert!(fake.volume("datos").is_none());
+
+        create(&fake, "datos", &layout, &same_host, false, true).unwrap();
+        assert!(fake.commands().last().unwrap().ends_with("vm2:/gluster/b force"));
+    }
+
+    #[test]
+    fn root_partition_rejections_can_be_forced() {
+        assert!(needs_force(
+            "volume create: datos: failed: The brick vm1:/srv/brick is being created in the root partition. \
+             It is recommended that you don't use the system's root partition for storage backend. \
+             Or use 'force' at the end of the command if you want to override this behavior."
+        ));
+        assert!(!needs_force("volume create: datos: failed: Volume datos already exists"));
+    }
+
+    #[test]
+    fn create_rejects_incomplete_replica_sets() {
+        let fake = FakeBackend::new().with_peer("vm2", true);
+        let layout = VolumeLayout::Replicate { replica: 2, arbiter: 0 };
+
+        assert!(create(&fake, "datos", &layout, &bricks(&["vm1:/gluster/a", "vm2:/gluster/a", "vm1:/gluster/b"]), true, false).is_err());
+        assert!(fake.commands().is_empty());
+    }
+
+    #[test]
+    fn create_rejects_invalid_bricks_without_calling_gluster() {
+        let fake = FakeBackend::new();
+
+        assert!(create(&fake, "datos", &VolumeLayout::Distribute, &bricks(&["vm1-gluster-a"]), true, false).is_err());
+        assert!(create(&fake, "datos", &VolumeLayout::Distribute, &[], true, false).is_err());
+        assert!(fake.commands().is_empty());
+    }
+
+    #[test]
+    fn create_reports_gluster_failure() {
+        let fake = FakeBackend::new();
+
+        let err = create(&fake, "datos", &VolumeLayout::Distribute, &bricks(&["vm9:/gluster/a"]), true, false).unwrap_err();
+        assert!(matches!(err, Error::CommandFailed { .. }));
+        assert!(err.to_string().contains("vm9"));
+        assert!(fake.volume("datos").is_none());
+    }
+
+    #[test]
+    fn lifecycle_start_stop_delete() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Created);
+
+        start(&fake, "datos").unwrap();
+        assert!(delete(&fake, "datos").is_err());
+        stop(&fake, "datos").unwrap();
+        delete(&fake, "datos").unwrap();
+        assert!(get_volume_names(&fake).unwrap().is_empty());
+    }
+
+    #[test]
+    fn add_and_remove_bricks() {
+        let fake = FakeBackend::new()
+            .with_peer("vm2", true)
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+
+        add(&fake, "datos", &bricks(&["vm2:/gluster/a"]), false).unwrap();
+        assert_eq!(volinfo::fetch_one(&fake, "datos").unwrap().brick_names(), bricks(&["vm1:/gluster/a", "vm2:/gluster/a"]));
+
+        crate::removal::start(&fake, "datos", &bricks(&["vm2:/gluster/a"])).unwrap();
+        assert_eq!(fake.volume("datos").unwrap().removing, bricks(&["vm2:/gluster/a"]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn lists_read_from_gluster() {
+        let fake = FakeBackend::new()
+            .with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started)
+            .with_volume("logs", &["vm1:/gluster/b"], VolumeState::Stopped);
+
+        assert_eq!(get_volume_names(&fake).unwrap(), vec!["datos", "logs"]);
+        assert!(volinfo::fetch_one(&fake, "otro").is_err());
+    }
+
+    #[test]
+    fn force_migration_reads_option_value() {
+        let fake = FakeBackend::new().with_volume("datos", &["vm1:/gluster/a"], VolumeState::Started);
+
+        assert!(!check_force_migration(&fake, "datos").unwrap());
+        let fake = fake.with_option("datos", "cluster.force-migration", "on");
+        assert!(check_force_migration(&fake, "datos").unwrap());
+    }
+}
@@ -0,0 +1,77 @@
+//! Utilidades comunes para la salida `--xml` de la CLI de gluster.
+//!
+//! Toda respuesta tiene la forma `<cliOutput><opRet/><opErrno/><opErrstr/>...</cliOutput>`;
+//! un `opRet` distinto de 0 indica que la operación falló aunque el proceso termine bien.
+
+use roxmltree::{Document, Node};
+
+use crate::error::{Error, Result};
+use crate::t;
+
+/// Parsea la salida y comprueba `opRet`; devuelve el documento listo para recorrer.
+pub fn parse_cli_output(xml: &str) -> Result<Document<'_>> {
+    let doc = Document::parse(xml).map_err(|e| Error::Parse(t!("xml.invalid", error = e)))?;
+
+    let root = doc.root_element();
+    if root.tag_name().name() != "cliOutput" {
+        return Err(Error::Parse(t!("xml.unexpected", tag = root.tag_name().name())));
+    }
+
+    let op_ret: i32 = child_parse(root, "opRet").unwrap_or(0);
+    if op_ret != 0 {
+        let err = child_text(root, "opErrstr")
+            .map(str::to_string)
+            .unwrap_or_else(|| t!("xml.unknown_error"));
+        return Err(Error::CommandFailed {
+            command: "gluster --xml".to_string(),
+            stderr: err,
+        });
+    }
+
+    Ok(doc)
+}
+
+/// Mensaje legible de una respuesta fallida: `opErrstr` si la salida es XML, o el texto tal cual.
+pub fn error_message(output: String) -> String {
+    let message = Document::parse(&output)
+        .ok()
+        .and_then(|doc| child_text(doc.root_element(), "opErrstr").map(str::to_string));
+    message.unwrap_or(output)
+}
+
+/// Primer hijo directo con la etiqueta indicada.
+pub fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(name))
+}
+
+/// Hijos directos con la etiqueta indicada.
+pub fn children<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children().filter(move |n| n.has_tag_name(name))
+}
+
+/// Texto de un hijo directo, recortado; `None` si no existe o está vacío.
+pub fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name)
+        .and_then(|n| n.text())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+}
+
+/// Texto de un hijo directo convertido al tipo pedido.
+pub fn child_parse<T: std::str::FromStr>(node: Node<'_, '_>, name: &str) -> Option<T> {
+    child_text(node, name).and_then(|t| t.parse().ok())
+}
+
+/// Primer descendiente (a cualquier profundidad) con la etiqueta indicada.
+pub fn descendant<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.descendants().find(|n| n.has_tag_name(name))
+}
+
+/// Escapa texto para incluirlo en XML (usado por el backend de pruebas).
+#[cfg(test)]
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}